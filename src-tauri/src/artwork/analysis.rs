@@ -0,0 +1,222 @@
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::LauncherError;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Side of the tiny copy colors and the blurhash are computed from
+const SAMPLE_SIZE: u32 = 32;
+
+/// Colors kept in the palette
+const PALETTE_SIZE: usize = 5;
+
+/// Share of the pixels a color needs before it can be the accent
+const ACCENT_MIN_SHARE: f32 = 0.05;
+
+const THUMBNAIL_QUALITY: u8 = 80;
+
+/// Colors, blurhash and thumbnail info for a cached image
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtworkInfo {
+    pub width: u32,
+    pub height: u32,
+    /// Most common color, as "#rrggbb"
+    pub dominant_color: String,
+    /// Most vivid of the main colors, for highlights
+    pub accent_color: String,
+    /// Main colors, most common first
+    pub palette: Vec<String>,
+    pub blurhash: String,
+    /// Whether a downscaled copy is cached (served at `.../{art}/thumb`)
+    #[serde(default)]
+    pub has_thumbnail: bool,
+}
+
+/// Result of processing a downloaded image
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtworkAnalysis {
+    pub info: ArtworkInfo,
+    /// JPEG thumbnail, if the image is bigger than the thumbnail size
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+/// Width thumbnails are scaled down to, for the artwork worth processing
+pub fn thumbnail_width(art_type: ArtworkType) -> Option<u32> {
+    match art_type {
+        ArtworkType::Cover => Some(200),
+        ArtworkType::Hero => Some(640),
+        ArtworkType::Logo | ArtworkType::Icon => None,
+    }
+}
+
+/// Decode an image and extract its palette, blurhash and a thumbnail
+pub fn analyze(bytes: &[u8], thumb_width: u32) -> Result<ArtworkAnalysis, LauncherError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to decode artwork: {e}")))?;
+    let (width, height) = image.dimensions();
+
+    let sample = image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgba8();
+    let (components_x, components_y) = if width >= height { (4, 3) } else { (3, 4) };
+    let blurhash = blurhash::encode(
+        components_x,
+        components_y,
+        sample.width(),
+        sample.height(),
+        sample.as_raw(),
+    )
+    .map_err(|e| LauncherError::ParseError(format!("Failed to compute blurhash: {e}")))?;
+
+    let palette = palette(sample.pixels().map(|p| p.0));
+    let dominant = palette.first().map(|c| c.rgb).unwrap_or([0, 0, 0]);
+    let accent = palette
+        .iter()
+        .filter(|c| c.share >= ACCENT_MIN_SHARE)
+        .max_by(|a, b| vividness(a.rgb).total_cmp(&vividness(b.rgb)))
+        .map(|c| c.rgb)
+        .unwrap_or(dominant);
+
+    let thumbnail = (width > thumb_width)
+        .then(|| encode_thumbnail(&image, thumb_width))
+        .transpose()?;
+
+    Ok(ArtworkAnalysis {
+        info: ArtworkInfo {
+            width,
+            height,
+            dominant_color: hex(dominant),
+            accent_color: hex(accent),
+            palette: palette.iter().map(|c| hex(c.rgb)).collect(),
+            blurhash,
+            has_thumbnail: thumbnail.is_some(),
+        },
+        thumbnail,
+    })
+}
+
+fn encode_thumbnail(image: &DynamicImage, thumb_width: u32) -> Result<Vec<u8>, LauncherError> {
+    let thumb_height = (image.height() as u64 * thumb_width as u64 / image.width() as u64).max(1);
+    let thumbnail = image
+        .resize_exact(thumb_width, thumb_height as u32, FilterType::Triangle)
+        .to_rgb8();
+
+    let mut bytes = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, THUMBNAIL_QUALITY)
+        .encode_image(&thumbnail)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to encode thumbnail: {e}")))?;
+    Ok(bytes)
+}
+
+struct PaletteColor {
+    rgb: [u8; 3],
+    /// Fraction of the (opaque) pixels in this color's bucket
+    share: f32,
+}
+
+/// Group pixels into coarse color buckets and average the biggest ones
+fn palette(pixels: impl Iterator<Item = [u8; 4]>) -> Vec<PaletteColor> {
+    let mut buckets: HashMap<(u8, u8, u8), ([u64; 3], u64)> = HashMap::new();
+    let mut total = 0u64;
+
+    for [r, g, b, a] in pixels {
+        if a < 128 {
+            continue;
+        }
+        let (sum, count) = buckets.entry((r >> 4, g >> 4, b >> 4)).or_default();
+        sum[0] += r as u64;
+        sum[1] += g as u64;
+        sum[2] += b as u64;
+        *count += 1;
+        total += 1;
+    }
+
+    let mut buckets: Vec<_> = buckets.into_iter().collect();
+    // Ties broken by bucket so the palette doesn't depend on hash order
+    buckets.sort_by(|(ka, (_, a)), (kb, (_, b))| b.cmp(a).then(ka.cmp(kb)));
+
+    buckets
+        .into_iter()
+        .take(PALETTE_SIZE)
+        .map(|(_, (sum, count))| PaletteColor {
+            rgb: sum.map(|s| (s / count) as u8),
+            share: count as f32 / total as f32,
+        })
+        .collect()
+}
+
+/// Saturation weighted by brightness, so near-black colors don't win
+fn vividness([r, g, b]: [u8; 3]) -> f32 {
+    let max = r.max(g).max(b) as f32;
+    let min = r.min(g).min(b) as f32;
+    if max == 0.0 {
+        return 0.0;
+    }
+    (max - min) / max * (max / 255.0)
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    /// PNG split between two colors: `main` on the left 3/4, `accent` on the right
+    pub(crate) fn two_tone_png(width: u32, height: u32, main: [u8; 3], accent: [u8; 3]) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, _| {
+            let [r, g, b] = if x < width * 3 / 4 { main } else { accent };
+            Rgba([r, g, b, 255])
+        });
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_analyze_colors() {
+        let png = two_tone_png(64, 64, [20, 30, 40], [230, 40, 20]);
+        let analysis = analyze(&png, 200).unwrap();
+        let info = analysis.info;
+
+        assert_eq!((info.width, info.height), (64, 64));
+        assert_eq!(info.dominant_color, "#141e28");
+        assert_eq!(info.accent_color, "#e62814");
+        assert_eq!(info.palette.len(), 2);
+        assert!(!info.blurhash.is_empty());
+        // Already smaller than the thumbnail size
+        assert!(!info.has_thumbnail);
+        assert!(analysis.thumbnail.is_none());
+    }
+
+    #[test]
+    fn test_analyze_thumbnail() {
+        let png = two_tone_png(400, 600, [200, 200, 200], [0, 0, 255]);
+        let analysis = analyze(&png, 200).unwrap();
+
+        assert!(analysis.info.has_thumbnail);
+        let thumbnail = image::load_from_memory(&analysis.thumbnail.unwrap()).unwrap();
+        assert_eq!(thumbnail.dimensions(), (200, 300));
+    }
+
+    #[test]
+    fn test_analyze_rejects_non_images() {
+        assert!(matches!(
+            analyze(b"<svg></svg>", 200),
+            Err(LauncherError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_vividness_prefers_saturated_bright_colors() {
+        assert!(vividness([230, 40, 20]) > vividness([120, 110, 100]));
+        assert!(vividness([230, 40, 20]) > vividness([40, 5, 2]));
+        assert_eq!(vividness([0, 0, 0]), 0.0);
+        assert_eq!(hex([255, 0, 16]), "#ff0010");
+    }
+}
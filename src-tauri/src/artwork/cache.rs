@@ -0,0 +1,807 @@
+use super::analysis::{analyze, thumbnail_width, ArtworkInfo};
+use super::placeholder::{render_placeholder, PLACEHOLDER_SOURCE};
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, LauncherError};
+use crate::storage::files::write_atomic;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const INDEX_FILE: &str = "index.json";
+
+/// Total size the cache may grow to before old images are evicted
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Largest single image that will be cached
+pub const DEFAULT_MAX_IMAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+const THUMBNAIL_CONTENT_TYPE: &str = "image/jpeg";
+
+/// Images downloaded in parallel while warming the cache
+const WARM_WORKERS: usize = 4;
+
+/// A cached image file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ArtworkEntry {
+    /// File name relative to the cache directory
+    file: String,
+    /// Remote URL the image was downloaded from
+    source_url: String,
+    content_type: String,
+    /// Bytes on disk, including the thumbnail
+    size: u64,
+    /// Value of the index clock when the image was last served (for LRU eviction)
+    last_used: u64,
+    /// Colors and blurhash (covers and heroes only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    info: Option<ArtworkInfo>,
+}
+
+impl ArtworkEntry {
+    fn has_thumbnail(&self) -> bool {
+        self.info.as_ref().is_some_and(|i| i.has_thumbnail)
+    }
+}
+
+/// Index of cached images keyed by "{game_key}:{art_type}" (e.g. "steam:440:cover")
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ArtworkIndex {
+    #[serde(default)]
+    clock: u64,
+    #[serde(default)]
+    entries: HashMap<String, ArtworkEntry>,
+}
+
+impl ArtworkIndex {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn total_bytes(&self) -> u64 {
+        self.entries.values().map(|e| e.size).sum()
+    }
+}
+
+/// An image read from the cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedArtwork {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+/// Processed info for a game's cover and hero
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GameArtworkInfo {
+    pub cover: Option<ArtworkInfo>,
+    pub hero: Option<ArtworkInfo>,
+}
+
+/// Result of warming the cache for a batch of games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct WarmSummary {
+    pub downloaded: usize,
+    pub already_cached: usize,
+    /// Images with no working URL that got a generated placeholder
+    pub generated: usize,
+    pub failed: usize,
+}
+
+/// Downloads game artwork into the data dir so the library works offline.
+///
+/// The cache is bounded: images over the per-image limit are refused and the
+/// least recently served images are evicted once the total limit is exceeded.
+/// Access times are kept in memory and saved with the next write.
+pub struct ArtworkCache {
+    dir: PathBuf,
+    client: reqwest::blocking::Client,
+    max_bytes: AtomicU64,
+    max_image_bytes: AtomicU64,
+    index: Mutex<ArtworkIndex>,
+}
+
+impl ArtworkCache {
+    /// Open (or create) a cache in `dir`. Index entries whose files have gone
+    /// missing are dropped.
+    pub fn new(dir: PathBuf) -> Result<Self, LauncherError> {
+        fs::create_dir_all(&dir).map_err(|e| {
+            LauncherError::ConfigError(format!("Failed to create artwork cache dir: {e}"))
+        })?;
+
+        let mut index: ArtworkIndex = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        index
+            .entries
+            .retain(|_, entry| dir.join(&entry.file).exists());
+
+        Ok(Self {
+            dir,
+            client: reqwest::blocking::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| reqwest::blocking::Client::new()),
+            max_bytes: AtomicU64::new(DEFAULT_MAX_CACHE_BYTES),
+            max_image_bytes: AtomicU64::new(DEFAULT_MAX_IMAGE_BYTES),
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Change the total size limit, evicting images if the cache is now too big
+    pub fn set_max_bytes(&self, max_bytes: u64) -> Result<(), LauncherError> {
+        self.max_bytes.store(max_bytes, Ordering::Relaxed);
+        let mut index = self.lock_index()?;
+        if self.evict(&mut index, None) {
+            self.save_index(&index)?;
+        }
+        Ok(())
+    }
+
+    /// Change the largest image that will be cached
+    pub fn set_max_image_bytes(&self, max_image_bytes: u64) {
+        self.max_image_bytes
+            .store(max_image_bytes, Ordering::Relaxed);
+    }
+
+    /// Directory the images are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Total size of all cached images
+    pub fn total_bytes(&self) -> u64 {
+        self.lock_index().map(|i| i.total_bytes()).unwrap_or(0)
+    }
+
+    /// Read a cached image, marking it as recently used
+    pub fn get(&self, game_key: &str, art_type: ArtworkType) -> Option<CachedArtwork> {
+        let key = entry_key(game_key, art_type);
+        let mut index = self.lock_index().ok()?;
+        let entry = index.entries.get(&key)?.clone();
+
+        match fs::read(self.dir.join(&entry.file)) {
+            Ok(bytes) => {
+                let tick = index.tick();
+                if let Some(e) = index.entries.get_mut(&key) {
+                    e.last_used = tick;
+                }
+                Some(CachedArtwork {
+                    bytes,
+                    content_type: entry.content_type,
+                })
+            }
+            Err(_) => {
+                index.entries.remove(&key);
+                None
+            }
+        }
+    }
+
+    /// Read the downscaled copy of a cached image, if one was made
+    pub fn get_thumbnail(&self, game_key: &str, art_type: ArtworkType) -> Option<CachedArtwork> {
+        let entry = self
+            .lock_index()
+            .ok()?
+            .entries
+            .get(&entry_key(game_key, art_type))?
+            .clone();
+        if !entry.has_thumbnail() {
+            return None;
+        }
+
+        fs::read(self.dir.join(thumbnail_file(&entry.file)))
+            .ok()
+            .map(|bytes| CachedArtwork {
+                bytes,
+                content_type: THUMBNAIL_CONTENT_TYPE.to_string(),
+            })
+    }
+
+    /// Colors and blurhash of a cached image
+    pub fn info(&self, game_key: &str, art_type: ArtworkType) -> Option<ArtworkInfo> {
+        self.lock_index()
+            .ok()?
+            .entries
+            .get(&entry_key(game_key, art_type))?
+            .info
+            .clone()
+    }
+
+    /// Colors and blurhash of a game's cached cover and hero
+    pub fn game_info(&self, game_key: &str) -> GameArtworkInfo {
+        GameArtworkInfo {
+            cover: self.info(game_key, ArtworkType::Cover),
+            hero: self.info(game_key, ArtworkType::Hero),
+        }
+    }
+
+    /// Serve a cached image if it came from `url`, otherwise download it
+    pub fn get_or_fetch(
+        &self,
+        game_key: &str,
+        art_type: ArtworkType,
+        url: &str,
+    ) -> Result<CachedArtwork, LauncherError> {
+        if self.is_cached_from(game_key, art_type, url) {
+            if let Some(artwork) = self.get(game_key, art_type) {
+                return Ok(artwork);
+            }
+        }
+        self.fetch(game_key, art_type, url)
+    }
+
+    /// Download an image and add it to the cache, replacing any older copy
+    pub fn fetch(
+        &self,
+        game_key: &str,
+        art_type: ArtworkType,
+        url: &str,
+    ) -> Result<CachedArtwork, LauncherError> {
+        let artwork = self.download(url)?;
+        self.insert(game_key, art_type, url, &artwork)?;
+        Ok(artwork)
+    }
+
+    /// Download any artwork the games have that isn't cached yet. `resolve`
+    /// picks the URL for each image and is called from the worker threads,
+    /// since it may hit the network. Images with no URL (or whose download
+    /// fails) get a generated placeholder unless something is already cached,
+    /// so every game ends up with every kind of artwork.
+    pub fn warm(
+        &self,
+        games: &[Game],
+        resolve: impl Fn(&Game, ArtworkType) -> Option<String> + Sync,
+    ) -> WarmSummary {
+        let jobs: Vec<(&Game, ArtworkType)> = games
+            .iter()
+            .flat_map(|game| ArtworkType::ALL.into_iter().map(move |art| (game, art)))
+            .collect();
+
+        let summary = Mutex::new(WarmSummary::default());
+        let next = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..WARM_WORKERS.min(jobs.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(game, art)) = jobs.get(i) else {
+                        break;
+                    };
+                    let key = game.unique_key();
+                    let mut failed = false;
+                    let downloaded = match resolve(game, art) {
+                        Some(url) if self.is_cached_from(&key, art, &url) => false,
+                        Some(url) => match self.fetch(&key, art, &url) {
+                            Ok(_) => true,
+                            Err(e) => {
+                                log::debug!("Failed to cache {} for {key}: {e}", art.as_str());
+                                failed = true;
+                                false
+                            }
+                        },
+                        None => false,
+                    };
+                    let generated = !downloaded
+                        && !self.contains(&key, art)
+                        && self.store_placeholder(game, art).is_ok();
+
+                    let mut summary = summary.lock().unwrap_or_else(|e| e.into_inner());
+                    if downloaded {
+                        summary.downloaded += 1;
+                    } else if failed {
+                        summary.failed += 1;
+                    } else if generated {
+                        summary.generated += 1;
+                    } else if self.contains(&key, art) {
+                        summary.already_cached += 1;
+                    } else {
+                        summary.failed += 1;
+                    }
+                });
+            }
+        });
+
+        summary.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Largest single image that will be cached
+    pub fn max_image_bytes(&self) -> u64 {
+        self.max_image_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Whether any image (downloaded or generated) is cached for the artwork
+    pub fn contains(&self, game_key: &str, art_type: ArtworkType) -> bool {
+        self.lock_index()
+            .map(|index| index.entries.contains_key(&entry_key(game_key, art_type)))
+            .unwrap_or(false)
+    }
+
+    /// Whether the cached image is a generated placeholder
+    pub fn is_placeholder(&self, game_key: &str, art_type: ArtworkType) -> bool {
+        self.is_cached_from(game_key, art_type, PLACEHOLDER_SOURCE)
+    }
+
+    /// Generate a placeholder for the artwork and cache it
+    pub fn store_placeholder(
+        &self,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Result<CachedArtwork, LauncherError> {
+        let artwork = render_placeholder(game, art_type);
+        self.insert(&game.unique_key(), art_type, PLACEHOLDER_SOURCE, &artwork)?;
+        Ok(artwork)
+    }
+
+    /// Delete every cached image
+    pub fn clear(&self) -> Result<(), LauncherError> {
+        let mut index = self.lock_index()?;
+        for entry in index.entries.values() {
+            self.remove_files(entry);
+        }
+        index.entries.clear();
+        self.save_index(&index)
+    }
+
+    fn is_cached_from(&self, game_key: &str, art_type: ArtworkType, url: &str) -> bool {
+        self.lock_index()
+            .map(|index| {
+                index
+                    .entries
+                    .get(&entry_key(game_key, art_type))
+                    .is_some_and(|e| e.source_url == url)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Download an image without caching it (same type and size checks)
+    pub fn download(&self, url: &str) -> Result<CachedArtwork, LauncherError> {
+        let max = self.max_image_bytes.load(Ordering::Relaxed);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .map_err(LauncherError::from_request)?;
+
+        if !response.status().is_success() {
+            return Err(LauncherError::HttpError(format!(
+                "{} returned {}",
+                url,
+                response.status()
+            )));
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if !content_type.starts_with("image/") {
+            return Err(LauncherError::HttpError(format!(
+                "{url} is not an image ({content_type})"
+            )));
+        }
+
+        if response.content_length().is_some_and(|len| len > max) {
+            return Err(LauncherError::HttpError(format!(
+                "{url} is larger than {max} bytes"
+            )));
+        }
+
+        let mut bytes = Vec::new();
+        response
+            .take(max + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+        if bytes.len() as u64 > max {
+            return Err(LauncherError::HttpError(format!(
+                "{url} is larger than {max} bytes"
+            )));
+        }
+        if bytes.is_empty() {
+            return Err(LauncherError::HttpError(format!("{url} returned no data")));
+        }
+
+        Ok(CachedArtwork {
+            bytes,
+            content_type,
+        })
+    }
+
+    fn insert(
+        &self,
+        game_key: &str,
+        art_type: ArtworkType,
+        url: &str,
+        artwork: &CachedArtwork,
+    ) -> Result<(), LauncherError> {
+        let key = entry_key(game_key, art_type);
+        let file = file_name(&key);
+        write_atomic(&self.dir.join(&file), &artwork.bytes)
+            .map_err(|e| LauncherError::IoError(format!("Failed to write artwork: {e}")))?;
+
+        // Vector placeholders have nothing worth extracting
+        let analysis = thumbnail_width(art_type)
+            .filter(|_| artwork.content_type != "image/svg+xml")
+            .and_then(|width| match analyze(&artwork.bytes, width) {
+                Ok(analysis) => Some(analysis),
+                Err(e) => {
+                    log::debug!("Failed to process {key}: {e}");
+                    None
+                }
+            });
+        let thumbnail = analysis.as_ref().and_then(|a| a.thumbnail.as_ref());
+        let thumb_path = self.dir.join(thumbnail_file(&file));
+        let thumb_size = match thumbnail {
+            Some(bytes) => {
+                write_atomic(&thumb_path, bytes).map_err(|e| {
+                    LauncherError::IoError(format!("Failed to write thumbnail: {e}"))
+                })?;
+                bytes.len() as u64
+            }
+            None => {
+                let _ = fs::remove_file(&thumb_path);
+                0
+            }
+        };
+
+        let mut index = self.lock_index()?;
+        let last_used = index.tick();
+        let previous = index.entries.insert(
+            key.clone(),
+            ArtworkEntry {
+                file: file.clone(),
+                source_url: url.to_string(),
+                content_type: artwork.content_type.clone(),
+                size: artwork.bytes.len() as u64 + thumb_size,
+                last_used,
+                info: analysis.map(|a| a.info),
+            },
+        );
+        // Entries cached under an older naming scheme leave their files behind
+        if let Some(old) = previous.filter(|old| old.file != file) {
+            self.remove_files(&old);
+        }
+        self.evict(&mut index, Some(&key));
+        self.save_index(&index)
+    }
+
+    /// Remove least recently used images until the cache fits its limit.
+    /// `keep` is never evicted (the image that was just added).
+    fn evict(&self, index: &mut ArtworkIndex, keep: Option<&str>) -> bool {
+        let max = self.max_bytes.load(Ordering::Relaxed);
+        let mut total = index.total_bytes();
+        if total <= max {
+            return false;
+        }
+
+        let mut candidates: Vec<(String, u64)> = index
+            .entries
+            .iter()
+            .filter(|(key, _)| Some(key.as_str()) != keep)
+            .map(|(key, entry)| (key.clone(), entry.last_used))
+            .collect();
+        candidates.sort_by_key(|(_, last_used)| *last_used);
+
+        for (key, _) in candidates {
+            if total <= max {
+                break;
+            }
+            if let Some(entry) = index.entries.remove(&key) {
+                self.remove_files(&entry);
+                total -= entry.size;
+            }
+        }
+        true
+    }
+
+    fn remove_files(&self, entry: &ArtworkEntry) {
+        let _ = fs::remove_file(self.dir.join(&entry.file));
+        if entry.has_thumbnail() {
+            let _ = fs::remove_file(self.dir.join(thumbnail_file(&entry.file)));
+        }
+    }
+
+    fn lock_index(&self) -> Result<std::sync::MutexGuard<'_, ArtworkIndex>, LauncherError> {
+        self.index
+            .lock()
+            .map_err(|e| LauncherError::ConfigError(e.to_string()))
+    }
+
+    fn save_index(&self, index: &ArtworkIndex) -> Result<(), LauncherError> {
+        let content = serde_json::to_string_pretty(index).map_err(|e| {
+            LauncherError::ParseError(format!("Failed to serialize artwork index: {e}"))
+        })?;
+        write_atomic(&self.dir.join(INDEX_FILE), content.as_bytes())
+            .map_err(|e| LauncherError::ConfigError(format!("Failed to write artwork index: {e}")))
+    }
+}
+
+pub(super) fn entry_key(game_key: &str, art_type: ArtworkType) -> String {
+    format!("{game_key}:{}", art_type.as_str())
+}
+
+/// File the downscaled copy of an image is kept in
+fn thumbnail_file(file: &str) -> String {
+    format!("{file}.thumb.jpg")
+}
+
+/// Turn an index key into a safe file name: the hex SHA-256 of the key, so
+/// keys that only differ in punctuation or case never share a file
+pub(super) fn file_name(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artwork::analysis::tests::two_tone_png;
+    use crate::launcher_core::StoreType;
+    use std::collections::HashSet;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    /// Serve `body` as `content_type` to every request on a local port
+    fn serve(content_type: &'static str, body: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn create_cache() -> (TempDir, ArtworkCache) {
+        let temp = TempDir::new().unwrap();
+        let cache = ArtworkCache::new(temp.path().join("artwork")).unwrap();
+        (temp, cache)
+    }
+
+    #[test]
+    fn test_fetch_and_get() {
+        let (_temp, cache) = create_cache();
+        let url = serve("image/png", vec![1, 2, 3]);
+
+        let fetched = cache
+            .fetch("steam:440", ArtworkType::Cover, &format!("{url}/cover.png"))
+            .unwrap();
+        assert_eq!(fetched.bytes, vec![1, 2, 3]);
+        assert_eq!(fetched.content_type, "image/png");
+
+        let cached = cache.get("steam:440", ArtworkType::Cover).unwrap();
+        assert_eq!(cached, fetched);
+        assert!(cache.get("steam:440", ArtworkType::Hero).is_none());
+        assert_eq!(cache.total_bytes(), 3);
+    }
+
+    #[test]
+    fn test_cache_survives_reopen() {
+        let (temp, cache) = create_cache();
+        let url = serve("image/jpeg", vec![7; 10]);
+        cache
+            .fetch("epic:fn", ArtworkType::Hero, &format!("{url}/hero.jpg"))
+            .unwrap();
+        drop(cache);
+
+        let reopened = ArtworkCache::new(temp.path().join("artwork")).unwrap();
+        let cached = reopened.get("epic:fn", ArtworkType::Hero).unwrap();
+        assert_eq!(cached.bytes, vec![7; 10]);
+        assert_eq!(cached.content_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_get_or_fetch_refetches_when_url_changes() {
+        let (_temp, cache) = create_cache();
+        let first = serve("image/png", vec![1]);
+        let second = serve("image/png", vec![2]);
+
+        cache
+            .get_or_fetch("steam:440", ArtworkType::Cover, &first)
+            .unwrap();
+        let same = cache
+            .get_or_fetch("steam:440", ArtworkType::Cover, &first)
+            .unwrap();
+        assert_eq!(same.bytes, vec![1]);
+
+        let changed = cache
+            .get_or_fetch("steam:440", ArtworkType::Cover, &second)
+            .unwrap();
+        assert_eq!(changed.bytes, vec![2]);
+    }
+
+    #[test]
+    fn test_rejects_non_images_and_oversized_files() {
+        let (_temp, cache) = create_cache();
+        let html = serve("text/html", b"<html></html>".to_vec());
+        assert!(cache.fetch("steam:1", ArtworkType::Cover, &html).is_err());
+
+        cache.set_max_image_bytes(4);
+        let big = serve("image/png", vec![0; 5]);
+        assert!(cache.fetch("steam:1", ArtworkType::Cover, &big).is_err());
+        assert!(cache.get("steam:1", ArtworkType::Cover).is_none());
+    }
+
+    #[test]
+    fn test_unreachable_url_is_an_error() {
+        let (_temp, cache) = create_cache();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(matches!(
+            cache.fetch("steam:1", ArtworkType::Cover, &url),
+            Err(LauncherError::NetworkError(_))
+        ));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let (_temp, cache) = create_cache();
+        cache.set_max_bytes(25).unwrap();
+        let url = serve("image/png", vec![0; 10]);
+
+        cache.fetch("steam:1", ArtworkType::Cover, &url).unwrap();
+        cache.fetch("steam:2", ArtworkType::Cover, &url).unwrap();
+        // Touch the first image so the second becomes least recently used
+        cache.get("steam:1", ArtworkType::Cover).unwrap();
+        cache.fetch("steam:3", ArtworkType::Cover, &url).unwrap();
+
+        assert!(cache.get("steam:1", ArtworkType::Cover).is_some());
+        assert!(cache.get("steam:2", ArtworkType::Cover).is_none());
+        assert!(cache.get("steam:3", ArtworkType::Cover).is_some());
+        assert_eq!(cache.total_bytes(), 20);
+
+        cache.set_max_bytes(10).unwrap();
+        assert_eq!(cache.total_bytes(), 10);
+        assert!(cache.get("steam:3", ArtworkType::Cover).is_some());
+    }
+
+    #[test]
+    fn test_warm_downloads_missing_artwork() {
+        let (_temp, cache) = create_cache();
+        let url = serve("image/png", vec![9; 4]);
+
+        let mut portal = Game::new("400", "Portal", StoreType::Steam);
+        portal.cover_url = Some(format!("{url}/400/cover.png"));
+        portal.hero_url = Some(format!("{url}/400/hero.png"));
+        let mut broken = Game::new("1", "Broken", StoreType::Steam);
+        broken.cover_url = Some("http://127.0.0.1:1/cover.png".to_string());
+        let bare = Game::new("2", "No Art", StoreType::Gog);
+        let games = vec![portal, broken, bare];
+
+        let resolve = |game: &Game, art| game.artwork_url(art).map(String::from);
+        let summary = cache.warm(&games, resolve);
+        assert_eq!(
+            summary,
+            WarmSummary {
+                downloaded: 2,
+                already_cached: 0,
+                generated: 9,
+                failed: 1,
+            }
+        );
+        assert!(cache.get("steam:400", ArtworkType::Hero).is_some());
+        assert!(!cache.is_placeholder("steam:400", ArtworkType::Hero));
+
+        // Everything without a working URL still has an image
+        for key in ["steam:1", "gog:2"] {
+            for art in ArtworkType::ALL {
+                assert!(cache.is_placeholder(key, art));
+            }
+        }
+        let cover = cache.get("gog:2", ArtworkType::Cover).unwrap();
+        assert_eq!(cover.content_type, "image/svg+xml");
+
+        let again = cache.warm(&games, resolve);
+        assert_eq!(again.already_cached, 11);
+        assert_eq!(again.downloaded, 0);
+        assert_eq!(again.generated, 0);
+    }
+
+    #[test]
+    fn test_placeholder_replaced_by_real_artwork() {
+        let (_temp, cache) = create_cache();
+        let url = serve("image/png", vec![5; 3]);
+        let mut game = Game::new("7", "Late Art", StoreType::Local);
+
+        cache.warm(std::slice::from_ref(&game), |_, _| None);
+        assert!(cache.is_placeholder("local:7", ArtworkType::Cover));
+
+        game.cover_url = Some(format!("{url}/cover.png"));
+        let summary = cache.warm(&[game], |game, art| game.artwork_url(art).map(String::from));
+        assert_eq!(summary.downloaded, 1);
+        assert!(!cache.is_placeholder("local:7", ArtworkType::Cover));
+        assert_eq!(
+            cache.get("local:7", ArtworkType::Cover).unwrap().bytes,
+            vec![5; 3]
+        );
+    }
+
+    #[test]
+    fn test_fetch_extracts_info_and_thumbnail() {
+        let (_temp, cache) = create_cache();
+        let png = two_tone_png(400, 600, [20, 30, 40], [230, 40, 20]);
+        let url = serve("image/png", png.clone());
+
+        cache.fetch("steam:440", ArtworkType::Cover, &url).unwrap();
+
+        let info = cache.info("steam:440", ArtworkType::Cover).unwrap();
+        assert_eq!((info.width, info.height), (400, 600));
+        assert!(info.has_thumbnail);
+        assert!(!info.blurhash.is_empty());
+        assert_eq!(cache.game_info("steam:440").cover, Some(info));
+
+        let thumbnail = cache
+            .get_thumbnail("steam:440", ArtworkType::Cover)
+            .unwrap();
+        assert_eq!(thumbnail.content_type, "image/jpeg");
+        assert_eq!(
+            cache.total_bytes(),
+            (png.len() + thumbnail.bytes.len()) as u64
+        );
+
+        // Icons aren't processed
+        cache.fetch("steam:440", ArtworkType::Icon, &url).unwrap();
+        assert!(cache.info("steam:440", ArtworkType::Icon).is_none());
+
+        cache.clear().unwrap();
+        assert!(cache
+            .get_thumbnail("steam:440", ArtworkType::Cover)
+            .is_none());
+        assert_eq!(fs::read_dir(cache.dir()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let (_temp, cache) = create_cache();
+        let url = serve("image/png", vec![1, 2]);
+        cache.fetch("steam:1", ArtworkType::Icon, &url).unwrap();
+
+        cache.clear().unwrap();
+
+        assert_eq!(cache.total_bytes(), 0);
+        assert!(cache.get("steam:1", ArtworkType::Icon).is_none());
+    }
+
+    #[test]
+    fn test_file_name_is_safe() {
+        let name = file_name("local:../etc:icon");
+        assert_eq!(name.len(), 64);
+        assert!(name.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(name, file_name("local:../etc:icon"));
+    }
+
+    #[test]
+    fn test_file_name_is_injective() {
+        let keys = [
+            "local:a:b:cover",
+            "local:a_b:cover",
+            "epic:Game:cover",
+            "epic:game:cover",
+        ];
+        let names: HashSet<String> = keys.iter().map(|k| file_name(k)).collect();
+        assert_eq!(names.len(), keys.len());
+    }
+}
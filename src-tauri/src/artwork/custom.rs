@@ -0,0 +1,207 @@
+use super::art_url;
+use super::cache::{entry_key, file_name, ArtworkCache, CachedArtwork};
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, LauncherError};
+use crate::storage::{ArtworkOverrides, CustomArtwork};
+use std::fs;
+use std::path::Path;
+
+/// Directory (inside the data dir) custom artwork is copied into
+pub const CUSTOM_ARTWORK_DIR: &str = "custom_artwork";
+
+/// Read the image a user picked: downloaded if `source` is an http(s) URL,
+/// otherwise read from the local file system
+pub fn load_custom_source(artwork: &ArtworkCache, source: &str) -> Result<Vec<u8>, LauncherError> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return artwork.download(source).map(|a| a.bytes);
+    }
+
+    let max = artwork.max_image_bytes();
+    let size = fs::metadata(source)
+        .map_err(|e| LauncherError::IoError(format!("Failed to read {source}: {e}")))?
+        .len();
+    if size > max {
+        return Err(LauncherError::IoError(format!(
+            "{source} is larger than {max} bytes"
+        )));
+    }
+    fs::read(source).map_err(|e| LauncherError::IoError(format!("Failed to read {source}: {e}")))
+}
+
+/// Copy an image into the data dir as a game's custom artwork
+pub fn import_custom_artwork(
+    data_dir: &Path,
+    game_key: &str,
+    art_type: ArtworkType,
+    source: &str,
+    bytes: &[u8],
+    now: i64,
+) -> Result<CustomArtwork, LauncherError> {
+    let (content_type, ext) = sniff_image(bytes)
+        .ok_or_else(|| LauncherError::ParseError(format!("{source} is not a supported image")))?;
+
+    let dir = data_dir.join(CUSTOM_ARTWORK_DIR);
+    fs::create_dir_all(&dir).map_err(|e| {
+        LauncherError::ConfigError(format!("Failed to create custom artwork dir: {e}"))
+    })?;
+
+    let file = format!(
+        "{CUSTOM_ARTWORK_DIR}/{}.{ext}",
+        file_name(&entry_key(game_key, art_type))
+    );
+    fs::write(data_dir.join(&file), bytes)
+        .map_err(|e| LauncherError::IoError(format!("Failed to write custom artwork: {e}")))?;
+
+    Ok(CustomArtwork {
+        file,
+        source: source.to_string(),
+        content_type: content_type.to_string(),
+        updated_at: now,
+    })
+}
+
+/// Read a custom image back from the data dir
+pub fn read_custom_artwork(
+    data_dir: &Path,
+    custom: &CustomArtwork,
+) -> Result<CachedArtwork, LauncherError> {
+    let bytes = fs::read(data_dir.join(&custom.file))
+        .map_err(|e| LauncherError::IoError(format!("Failed to read custom artwork: {e}")))?;
+    Ok(CachedArtwork {
+        bytes,
+        content_type: custom.content_type.clone(),
+    })
+}
+
+/// Delete the copy of a custom image
+pub fn remove_custom_artwork(data_dir: &Path, custom: &CustomArtwork) {
+    let _ = fs::remove_file(data_dir.join(&custom.file));
+}
+
+/// Point the games' artwork URLs at their custom images. The URLs carry the
+/// override's timestamp so the webview doesn't keep showing the old image.
+pub fn apply_artwork_overrides(games: &mut [Game], overrides: &ArtworkOverrides) {
+    if overrides.games.is_empty() {
+        return;
+    }
+
+    for game in games {
+        let key = game.unique_key();
+        for art_type in ArtworkType::ALL {
+            if let Some(custom) = overrides.get(&key, art_type) {
+                let url = format!("{}?v={}", art_url(&key, art_type), custom.updated_at);
+                game.set_artwork_url(art_type, url);
+            }
+        }
+    }
+}
+
+/// Content type and file extension of a supported image
+fn sniff_image(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    use image::ImageFormat;
+
+    match image::guess_format(bytes).ok()? {
+        ImageFormat::Png => Some(("image/png", "png")),
+        ImageFormat::Jpeg => Some(("image/jpeg", "jpg")),
+        ImageFormat::WebP => Some(("image/webp", "webp")),
+        ImageFormat::Gif => Some(("image/gif", "gif")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artwork::analysis::tests::two_tone_png;
+    use crate::launcher_core::StoreType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_import_and_read_custom_artwork() {
+        let temp = TempDir::new().unwrap();
+        let png = two_tone_png(8, 12, [1, 2, 3], [4, 5, 6]);
+
+        let custom = import_custom_artwork(
+            temp.path(),
+            "steam:440",
+            ArtworkType::Cover,
+            "/home/me/tf2.png",
+            &png,
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(
+            custom.file,
+            format!("custom_artwork/{}.png", file_name("steam:440:cover"))
+        );
+        assert_eq!(custom.content_type, "image/png");
+        assert_eq!(custom.source, "/home/me/tf2.png");
+        assert_eq!(custom.updated_at, 100);
+
+        let read = read_custom_artwork(temp.path(), &custom).unwrap();
+        assert_eq!(read.bytes, png);
+
+        remove_custom_artwork(temp.path(), &custom);
+        assert!(read_custom_artwork(temp.path(), &custom).is_err());
+    }
+
+    #[test]
+    fn test_import_rejects_non_images() {
+        let temp = TempDir::new().unwrap();
+        let result = import_custom_artwork(
+            temp.path(),
+            "steam:440",
+            ArtworkType::Hero,
+            "notes.txt",
+            b"not an image",
+            0,
+        );
+        assert!(matches!(result, Err(LauncherError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_load_custom_source_from_file() {
+        let temp = TempDir::new().unwrap();
+        let cache = ArtworkCache::new(temp.path().join("artwork")).unwrap();
+        let path = temp.path().join("cover.png");
+        fs::write(&path, [1, 2, 3, 4]).unwrap();
+
+        let bytes = load_custom_source(&cache, path.to_str().unwrap()).unwrap();
+        assert_eq!(bytes, vec![1, 2, 3, 4]);
+
+        cache.set_max_image_bytes(3);
+        assert!(load_custom_source(&cache, path.to_str().unwrap()).is_err());
+        assert!(load_custom_source(&cache, "/does/not/exist.png").is_err());
+    }
+
+    #[test]
+    fn test_apply_artwork_overrides() {
+        let mut overrides = ArtworkOverrides::default();
+        overrides.set(
+            "steam:440",
+            ArtworkType::Logo,
+            CustomArtwork {
+                file: "custom_artwork/steam_440_logo.png".to_string(),
+                source: "https://example.com/logo.png".to_string(),
+                content_type: "image/png".to_string(),
+                updated_at: 42,
+            },
+        );
+
+        let mut tf2 = Game::new("440", "Team Fortress 2", StoreType::Steam);
+        tf2.cover_url = Some("https://cdn.example.com/cover.jpg".to_string());
+        let other = Game::new("10", "Counter-Strike", StoreType::Steam);
+        let mut games = vec![tf2, other.clone()];
+
+        apply_artwork_overrides(&mut games, &overrides);
+
+        let logo = games[0].logo_url.as_deref().unwrap();
+        assert!(logo.ends_with("/steam/440/logo?v=42"));
+        assert_eq!(
+            games[0].cover_url.as_deref(),
+            Some("https://cdn.example.com/cover.jpg")
+        );
+        assert_eq!(games[1], other);
+    }
+}
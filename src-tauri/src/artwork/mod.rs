@@ -0,0 +1,150 @@
+mod analysis;
+mod cache;
+mod custom;
+mod placeholder;
+mod provider;
+mod resolver;
+mod steamgriddb;
+
+use crate::launcher_core::store::ArtworkType;
+
+pub use analysis::ArtworkInfo;
+pub use cache::{
+    ArtworkCache, CachedArtwork, GameArtworkInfo, WarmSummary, DEFAULT_MAX_CACHE_BYTES,
+    DEFAULT_MAX_IMAGE_BYTES,
+};
+pub use custom::{
+    apply_artwork_overrides, import_custom_artwork, load_custom_source, read_custom_artwork,
+    remove_custom_artwork, CUSTOM_ARTWORK_DIR,
+};
+pub use placeholder::{render_placeholder, PLACEHOLDER_SOURCE};
+pub use provider::{ArtworkCandidate, ArtworkProvider, GogArtworkProvider, SteamCdnProvider};
+pub use resolver::{ArtworkResolver, PROBE_MISS_TTL_SECS, PROBE_OK_TTL_SECS};
+pub use steamgriddb::{SteamGridDbProvider, STEAMGRIDDB_API_BASE};
+
+/// URI scheme the UI loads cached artwork from
+pub const ART_PROTOCOL: &str = "tenfoot-art";
+
+/// URL the webview loads a game's artwork from (in the form it expects on
+/// this platform)
+pub fn art_url(game_key: &str, art_type: ArtworkType) -> String {
+    let (store, id) = game_key.split_once(':').unwrap_or((game_key, ""));
+    let path = format!("{store}/{}/{}", urlencoding::encode(id), art_type.as_str());
+    if cfg!(target_os = "windows") {
+        format!("http://{ART_PROTOCOL}.localhost/{path}")
+    } else {
+        format!("{ART_PROTOCOL}://localhost/{path}")
+    }
+}
+
+/// A request for a game's artwork over the art protocol
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtRequest {
+    pub game_key: String,
+    pub art_type: ArtworkType,
+    /// Whether the downscaled copy was asked for (a trailing `/thumb`)
+    pub thumbnail: bool,
+}
+
+/// Parse an artwork URI into a game key and artwork type.
+///
+/// Accepts `tenfoot-art://steam/440/cover` as well as the forms the webview
+/// actually requests: `tenfoot-art://localhost/steam/440/cover` (macOS/Linux)
+/// and `http://tenfoot-art.localhost/steam/440/cover` (Windows). The path may
+/// be percent-encoded as a single segment, and may end in `/thumb`.
+pub fn parse_art_uri(uri: &str) -> Option<ArtRequest> {
+    let rest = uri.split_once("://")?.1;
+    let rest = rest.split(['?', '#']).next()?;
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let path = urlencoding::decode(path).ok()?;
+
+    let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if host != "localhost" && host != format!("{ART_PROTOCOL}.localhost") {
+        segments.insert(0, host);
+    }
+
+    let thumbnail = segments.len() > 3 && segments.last() == Some(&"thumb");
+    if thumbnail {
+        segments.pop();
+    }
+
+    match segments.as_slice() {
+        [store, id @ .., art] if !id.is_empty() => Some(ArtRequest {
+            game_key: format!("{store}:{}", id.join("/")),
+            art_type: ArtworkType::from_name(art)?,
+            thumbnail,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(uri: &str) -> Option<(String, ArtworkType)> {
+        parse_art_uri(uri).map(|r| (r.game_key, r.art_type))
+    }
+
+    #[test]
+    fn test_parse_art_uri_forms() {
+        let expected = Some(("steam:440".to_string(), ArtworkType::Cover));
+        assert_eq!(parsed("tenfoot-art://steam/440/cover"), expected);
+        assert_eq!(parsed("tenfoot-art://localhost/steam/440/cover"), expected);
+        assert_eq!(
+            parsed("http://tenfoot-art.localhost/steam/440/cover"),
+            expected
+        );
+        assert_eq!(
+            parsed("tenfoot-art://localhost/steam%2F440%2Fcover?v=2"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_art_uri_other_types() {
+        assert_eq!(
+            parsed("tenfoot-art://localhost/epic/Fortnite/hero"),
+            Some(("epic:Fortnite".to_string(), ArtworkType::Hero))
+        );
+        assert_eq!(
+            parsed("tenfoot-art://localhost/local/my-game/icon"),
+            Some(("local:my-game".to_string(), ArtworkType::Icon))
+        );
+    }
+
+    #[test]
+    fn test_parse_art_uri_thumbnail() {
+        let request = parse_art_uri("tenfoot-art://localhost/steam/440/hero/thumb").unwrap();
+        assert_eq!(request.game_key, "steam:440");
+        assert_eq!(request.art_type, ArtworkType::Hero);
+        assert!(request.thumbnail);
+
+        assert!(
+            !parse_art_uri("tenfoot-art://localhost/steam/440/hero")
+                .unwrap()
+                .thumbnail
+        );
+        // A game whose ID happens to be "thumb"
+        assert_eq!(
+            parsed("tenfoot-art://localhost/local/thumb/cover"),
+            Some(("local:thumb".to_string(), ArtworkType::Cover))
+        );
+    }
+
+    #[test]
+    fn test_art_url_round_trips() {
+        for key in ["steam:440", "epic:Fortnite", "local:my game"] {
+            let url = art_url(key, ArtworkType::Logo);
+            assert_eq!(parsed(&url), Some((key.to_string(), ArtworkType::Logo)));
+        }
+    }
+
+    #[test]
+    fn test_parse_art_uri_invalid() {
+        assert_eq!(parsed("tenfoot-art://localhost/steam/440"), None);
+        assert_eq!(parsed("tenfoot-art://localhost/steam/440/banner"), None);
+        assert_eq!(parsed("tenfoot-art://localhost/"), None);
+        assert_eq!(parsed("not a uri"), None);
+    }
+}
@@ -0,0 +1,274 @@
+use super::cache::CachedArtwork;
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::Game;
+
+/// Source URL recorded in the cache for generated images
+pub const PLACEHOLDER_SOURCE: &str = "tenfoot:placeholder";
+
+const SVG_CONTENT_TYPE: &str = "image/svg+xml";
+
+/// Canvas size and title layout for each kind of artwork
+struct Layout {
+    width: u32,
+    height: u32,
+    font_size: u32,
+    /// Characters per line before the title wraps
+    line_chars: usize,
+    max_lines: usize,
+}
+
+impl Layout {
+    fn for_type(art_type: ArtworkType) -> Self {
+        match art_type {
+            ArtworkType::Cover => Self {
+                width: 600,
+                height: 900,
+                font_size: 64,
+                line_chars: 14,
+                max_lines: 5,
+            },
+            ArtworkType::Hero => Self {
+                width: 1920,
+                height: 620,
+                font_size: 96,
+                line_chars: 28,
+                max_lines: 2,
+            },
+            ArtworkType::Logo => Self {
+                width: 800,
+                height: 310,
+                font_size: 72,
+                line_chars: 18,
+                max_lines: 2,
+            },
+            ArtworkType::Icon => Self {
+                width: 256,
+                height: 256,
+                font_size: 112,
+                line_chars: 2,
+                max_lines: 1,
+            },
+        }
+    }
+}
+
+/// Render stand-in artwork for a game with none: the title over a gradient
+/// whose colors are derived from the game's unique key, with a store badge.
+/// Logos are just the title on a transparent background.
+pub fn render_placeholder(game: &Game, art_type: ArtworkType) -> CachedArtwork {
+    let layout = Layout::for_type(art_type);
+    let (width, height) = (layout.width, layout.height);
+    let hue = hue_for(&game.unique_key());
+    let second_hue = (hue + 40) % 360;
+
+    let lines = match art_type {
+        ArtworkType::Icon => vec![initials(&game.name)],
+        _ => wrap_title(&game.name, layout.line_chars, layout.max_lines),
+    };
+    let line_height = layout.font_size * 6 / 5;
+    let first_baseline =
+        height / 2 + layout.font_size / 3 - line_height * (lines.len() as u32 - 1) / 2;
+    let title: String = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            format!(
+                r#"<tspan x="{}" y="{}">{}</tspan>"#,
+                width / 2,
+                first_baseline + line_height * i as u32,
+                escape_xml(line)
+            )
+        })
+        .collect();
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    if art_type != ArtworkType::Logo {
+        svg.push_str(&format!(
+            concat!(
+                r#"<defs><linearGradient id="bg" x1="0" y1="0" x2="1" y2="1">"#,
+                r#"<stop offset="0" stop-color="hsl({hue},55%,38%)"/>"#,
+                r#"<stop offset="1" stop-color="hsl({second},60%,18%)"/>"#,
+                r#"</linearGradient></defs>"#,
+                r#"<rect width="100%" height="100%" fill="url(#bg)"/>"#
+            ),
+            hue = hue,
+            second = second_hue
+        ));
+    }
+    svg.push_str(&format!(
+        r##"<text font-family="sans-serif" font-weight="700" font-size="{}" fill="#ffffff" text-anchor="middle">{title}</text>"##,
+        layout.font_size
+    ));
+    if matches!(art_type, ArtworkType::Cover | ArtworkType::Hero) {
+        svg.push_str(&store_badge(game, width, height));
+    }
+    svg.push_str("</svg>");
+
+    CachedArtwork {
+        bytes: svg.into_bytes(),
+        content_type: SVG_CONTENT_TYPE.to_string(),
+    }
+}
+
+/// Pill with the store name in the bottom-left corner
+fn store_badge(game: &Game, width: u32, height: u32) -> String {
+    let label = game.store.to_string().to_uppercase();
+    let font_size = (width.min(height) / 24).max(14);
+    let pad = font_size;
+    let badge_height = font_size * 2;
+    let badge_width = font_size * (label.chars().count() as u32 + 2) * 3 / 4;
+    let x = pad;
+    let y = height - pad - badge_height;
+
+    format!(
+        concat!(
+            r##"<rect x="{x}" y="{y}" width="{bw}" height="{bh}" rx="{r}" fill="#000000" fill-opacity="0.45"/>"##,
+            r#"<text x="{tx}" y="{ty}" font-family="sans-serif" font-weight="700" font-size="{fs}" "#,
+            r##"fill="#ffffff" text-anchor="middle" letter-spacing="2">{label}</text>"##
+        ),
+        x = x,
+        y = y,
+        bw = badge_width,
+        bh = badge_height,
+        r = badge_height / 2,
+        tx = x + badge_width / 2,
+        ty = y + badge_height * 2 / 3,
+        fs = font_size,
+        label = escape_xml(&label)
+    )
+}
+
+/// Stable hue (0-359) for a key, so a game always gets the same colors
+fn hue_for(key: &str) -> u32 {
+    // FNV-1a: tiny, and stable across builds unlike std's hasher
+    let hash = key.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+    hash % 360
+}
+
+/// Up to two initials ("Hollow Knight" -> "HK")
+fn initials(title: &str) -> String {
+    let initials: String = title
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+/// Greedy word wrap; the last line gets an ellipsis if the title doesn't fit
+fn wrap_title(title: &str, line_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in title.split_whitespace() {
+        let fits =
+            current.is_empty() || current.chars().count() + 1 + word.chars().count() <= line_chars;
+        if fits {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            last.push('…');
+        }
+    }
+    if lines.is_empty() {
+        lines.push("?".to_string());
+    }
+    lines
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+
+    fn svg(game: &Game, art_type: ArtworkType) -> String {
+        String::from_utf8(render_placeholder(game, art_type).bytes).unwrap()
+    }
+
+    #[test]
+    fn test_placeholder_has_title_and_badge() {
+        let game = Game::new("1207658930", "Tom & Jerry's <Quest>", StoreType::Gog);
+        let cover = svg(&game, ArtworkType::Cover);
+
+        assert!(cover.starts_with("<svg"));
+        assert!(cover.contains(r#"width="600" height="900""#));
+        assert!(cover.contains("Tom &amp;"));
+        assert!(cover.contains("&lt;Quest&gt;"));
+        assert!(cover.contains(">GOG</text>"));
+        assert_eq!(
+            render_placeholder(&game, ArtworkType::Cover).content_type,
+            "image/svg+xml"
+        );
+    }
+
+    #[test]
+    fn test_placeholder_sizes() {
+        let game = Game::new("x", "Celeste", StoreType::Local);
+        assert!(svg(&game, ArtworkType::Hero).contains(r#"width="1920" height="620""#));
+        assert!(svg(&game, ArtworkType::Icon).contains(r#"width="256" height="256""#));
+        // Logos are transparent and unbadged
+        let logo = svg(&game, ArtworkType::Logo);
+        assert!(!logo.contains("<rect"));
+        assert!(logo.contains(">Celeste</tspan>"));
+    }
+
+    #[test]
+    fn test_placeholder_colors_are_deterministic() {
+        let a = Game::new("1", "Same Name", StoreType::Steam);
+        let b = Game::new("2", "Same Name", StoreType::Steam);
+
+        assert_eq!(svg(&a, ArtworkType::Cover), svg(&a, ArtworkType::Cover));
+        assert_eq!(hue_for("steam:1"), hue_for("steam:1"));
+        assert_ne!(hue_for("steam:1"), hue_for("steam:2"));
+        assert_ne!(svg(&a, ArtworkType::Cover), svg(&b, ArtworkType::Cover));
+    }
+
+    #[test]
+    fn test_wrap_title() {
+        assert_eq!(wrap_title("Hollow Knight", 14, 5), vec!["Hollow Knight"]);
+        assert_eq!(
+            wrap_title("The Elder Scrolls V: Skyrim", 14, 5),
+            vec!["The Elder", "Scrolls V:", "Skyrim"]
+        );
+        assert_eq!(wrap_title("one two three four", 5, 2), vec!["one", "two…"]);
+        assert_eq!(wrap_title("   ", 14, 5), vec!["?"]);
+    }
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(initials("Hollow Knight"), "HK");
+        assert_eq!(initials("celeste"), "C");
+        assert_eq!(initials("The Witcher 3: Wild Hunt"), "TW");
+        assert_eq!(initials(""), "?");
+    }
+}
@@ -0,0 +1,151 @@
+use crate::launcher_core::game::GameArtwork;
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, LauncherError, StoreType};
+use crate::stores::gog::GogApi;
+use crate::stores::steam;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// An image a provider offers for a game
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtworkCandidate {
+    pub url: String,
+    /// Smaller copy for showing the choices, if the provider has one
+    pub thumbnail_url: Option<String>,
+    /// Id of the provider that offered it
+    pub provider: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Provider-specific style tag (e.g. "alternate", "blurred")
+    pub style: Option<String>,
+}
+
+impl ArtworkCandidate {
+    /// A candidate known only by its URL
+    pub fn from_url(provider: &str, url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            thumbnail_url: None,
+            provider: provider.to_string(),
+            width: None,
+            height: None,
+            style: None,
+        }
+    }
+}
+
+/// A source of artwork for games, independent of the store they come from
+pub trait ArtworkProvider: Send + Sync {
+    /// Short stable id, recorded on the candidates it returns
+    fn id(&self) -> &'static str;
+
+    /// Images this provider has for a game, best first. Providers that don't
+    /// cover the game return an empty list rather than an error.
+    fn candidates(
+        &self,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Result<Vec<ArtworkCandidate>, LauncherError>;
+}
+
+/// Steam CDN images (e.g. `header.jpg` for titles without library art)
+pub struct SteamCdnProvider;
+
+impl ArtworkProvider for SteamCdnProvider {
+    fn id(&self) -> &'static str {
+        "steam"
+    }
+
+    fn candidates(
+        &self,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Result<Vec<ArtworkCandidate>, LauncherError> {
+        if game.store != StoreType::Steam {
+            return Ok(Vec::new());
+        }
+        Ok(steam::artwork_candidates(&game.id, art_type)
+            .into_iter()
+            .map(|url| ArtworkCandidate::from_url(self.id(), url))
+            .collect())
+    }
+}
+
+/// Real image links from GOG's catalog API, looked up once per game
+pub struct GogArtworkProvider {
+    api: GogApi,
+    found: Mutex<HashMap<String, GameArtwork>>,
+}
+
+impl GogArtworkProvider {
+    pub fn new(api: GogApi) -> Self {
+        Self {
+            api,
+            found: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl ArtworkProvider for GogArtworkProvider {
+    fn id(&self) -> &'static str {
+        "gog"
+    }
+
+    fn candidates(
+        &self,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Result<Vec<ArtworkCandidate>, LauncherError> {
+        if game.store != StoreType::Gog {
+            return Ok(Vec::new());
+        }
+
+        let cached = self
+            .found
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&game.id)
+            .cloned();
+        let artwork = match cached {
+            Some(artwork) => artwork,
+            None => {
+                let artwork = self.api.get_artwork(&game.id)?;
+                self.found
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(game.id.clone(), artwork.clone());
+                artwork
+            }
+        };
+
+        Ok(artwork
+            .get(art_type)
+            .map(|url| ArtworkCandidate::from_url(self.id(), url))
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steam_provider_includes_header_fallback() {
+        let game = Game::new("10", "Counter-Strike", StoreType::Steam);
+        let candidates = SteamCdnProvider
+            .candidates(&game, ArtworkType::Cover)
+            .unwrap();
+        assert!(candidates[0].url.ends_with("/10/library_600x900.jpg"));
+        assert!(candidates.iter().any(|c| c.url.ends_with("/10/header.jpg")));
+        assert!(candidates.iter().all(|c| c.provider == "steam"));
+
+        let other = Game::new("10", "Something", StoreType::Epic);
+        assert!(SteamCdnProvider
+            .candidates(&other, ArtworkType::Cover)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,511 @@
+use super::provider::{ArtworkCandidate, ArtworkProvider, GogArtworkProvider, SteamCdnProvider};
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, LauncherError};
+use crate::storage::files::write_atomic;
+use crate::stores::gog::GogApi;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// How long a URL that served an image is trusted without re-checking
+pub const PROBE_OK_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// How long a URL (or a game with no working candidate) is skipped after failing
+pub const PROBE_MISS_TTL_SECS: i64 = 3 * 24 * 60 * 60;
+
+/// Result of checking whether a URL serves an image
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+struct Probe {
+    ok: bool,
+    checked_at: i64,
+}
+
+impl Probe {
+    fn is_fresh(&self, now: i64) -> bool {
+        let ttl = if self.ok {
+            PROBE_OK_TTL_SECS
+        } else {
+            PROBE_MISS_TTL_SECS
+        };
+        now - self.checked_at < ttl
+    }
+}
+
+/// The candidate that worked for a game's artwork (None if none did)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Choice {
+    url: Option<String>,
+    checked_at: i64,
+}
+
+/// Persisted probe results keyed by URL and choices keyed by "{game_key}:{art_type}"
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ResolverState {
+    #[serde(default)]
+    probes: HashMap<String, Probe>,
+    #[serde(default)]
+    chosen: HashMap<String, Choice>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+/// Picks a working URL for each piece of artwork.
+///
+/// Candidates are tried in order: the URL the store gave the game, then each
+/// registered provider's. Every candidate is validated with a HEAD request and
+/// the outcome is remembered, as is the candidate that worked, so later
+/// lookups don't touch the network until the result expires.
+pub struct ArtworkResolver {
+    path: PathBuf,
+    client: reqwest::blocking::Client,
+    providers: Vec<Arc<dyn ArtworkProvider>>,
+    state: Mutex<ResolverState>,
+}
+
+impl ArtworkResolver {
+    /// Open the resolver, loading earlier probe results from `path`
+    pub fn new(path: PathBuf) -> Self {
+        let state = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            client: reqwest::blocking::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .connect_timeout(std::time::Duration::from_secs(5))
+                .build()
+                .unwrap_or_else(|_| reqwest::blocking::Client::new()),
+            providers: Vec::new(),
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Open the resolver with the built-in Steam CDN and GOG catalog providers
+    pub fn with_default_providers(path: PathBuf) -> Self {
+        let mut resolver = Self::new(path);
+        resolver.add_provider(Arc::new(SteamCdnProvider));
+        resolver.add_provider(Arc::new(GogArtworkProvider::new(GogApi::new())));
+        resolver
+    }
+
+    /// Add a provider of fallback candidates (tried after those already added)
+    pub fn add_provider(&mut self, provider: Arc<dyn ArtworkProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Everything the providers offer for a game's artwork, for the user to
+    /// pick from. A provider that fails is logged and skipped.
+    pub fn browse(&self, game: &Game, art_type: ArtworkType) -> Vec<ArtworkCandidate> {
+        let mut seen = HashSet::new();
+        self.providers
+            .iter()
+            .flat_map(|provider| self.candidates_from(provider.as_ref(), game, art_type))
+            .filter(|candidate| seen.insert(candidate.url.clone()))
+            .collect()
+    }
+
+    /// Find a URL that serves this artwork, probing candidates as needed
+    pub fn resolve(&self, game: &Game, art_type: ArtworkType, now: i64) -> Option<String> {
+        let key = choice_key(&game.unique_key(), art_type);
+
+        let remembered = self.lock_state().chosen.get(&key).cloned();
+        if let Some(choice) = remembered {
+            match &choice.url {
+                Some(url) if self.probe(url, now) => return Some(url.clone()),
+                None if now - choice.checked_at < PROBE_MISS_TTL_SECS => return None,
+                _ => {}
+            }
+        }
+
+        let url = self.first_valid(game, art_type, now);
+        let mut state = self.lock_state();
+        state.chosen.insert(
+            key,
+            Choice {
+                url: url.clone(),
+                checked_at: now,
+            },
+        );
+        state.dirty = true;
+        url
+    }
+
+    /// Record that a resolved URL didn't work after all (e.g. the download
+    /// failed) so the next lookup moves on to the other candidates
+    pub fn reject(&self, game_key: &str, art_type: ArtworkType, url: &str, now: i64) {
+        let mut state = self.lock_state();
+        state.probes.insert(
+            url.to_string(),
+            Probe {
+                ok: false,
+                checked_at: now,
+            },
+        );
+        let key = choice_key(game_key, art_type);
+        if state
+            .chosen
+            .get(&key)
+            .is_some_and(|c| c.url.as_deref() == Some(url))
+        {
+            state.chosen.remove(&key);
+        }
+        state.dirty = true;
+    }
+
+    /// Forget every probe result and choice
+    pub fn clear(&self) -> Result<(), LauncherError> {
+        {
+            let mut state = self.lock_state();
+            state.probes.clear();
+            state.chosen.clear();
+            state.dirty = true;
+        }
+        self.flush()
+    }
+
+    /// Save probe results if anything changed since the last save
+    pub fn flush(&self) -> Result<(), LauncherError> {
+        let mut state = self.lock_state();
+        if !state.dirty {
+            return Ok(());
+        }
+
+        let content = serde_json::to_string_pretty(&*state).map_err(|e| {
+            LauncherError::ParseError(format!("Failed to serialize artwork probes: {e}"))
+        })?;
+        write_atomic(&self.path, content.as_bytes()).map_err(|e| {
+            LauncherError::ConfigError(format!("Failed to write artwork probes: {e}"))
+        })?;
+        state.dirty = false;
+        Ok(())
+    }
+
+    /// Walk the candidates in order and return the first one that validates.
+    /// Providers are only asked for candidates once the earlier ones have failed.
+    fn first_valid(&self, game: &Game, art_type: ArtworkType, now: i64) -> Option<String> {
+        let mut tried = HashSet::new();
+        let mut check = |url: String| {
+            if tried.insert(url.clone()) && self.probe(&url, now) {
+                Some(url)
+            } else {
+                None
+            }
+        };
+
+        if let Some(url) = game
+            .artwork_url(art_type)
+            .and_then(|u| check(u.to_string()))
+        {
+            return Some(url);
+        }
+        self.providers.iter().find_map(|provider| {
+            self.candidates_from(provider.as_ref(), game, art_type)
+                .into_iter()
+                .find_map(|candidate| check(candidate.url))
+        })
+    }
+
+    fn candidates_from(
+        &self,
+        provider: &dyn ArtworkProvider,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Vec<ArtworkCandidate> {
+        provider.candidates(game, art_type).unwrap_or_else(|e| {
+            log::debug!(
+                "{} artwork lookup failed for {}: {e}",
+                provider.id(),
+                game.unique_key()
+            );
+            Vec::new()
+        })
+    }
+
+    /// Check a URL, using the remembered result while it is fresh
+    fn probe(&self, url: &str, now: i64) -> bool {
+        let remembered = self.lock_state().probes.get(url).copied();
+        if let Some(probe) = remembered.filter(|p| p.is_fresh(now)) {
+            return probe.ok;
+        }
+
+        let ok = self.check_url(url);
+        let mut state = self.lock_state();
+        state.probes.insert(
+            url.to_string(),
+            Probe {
+                ok,
+                checked_at: now,
+            },
+        );
+        state.dirty = true;
+        ok
+    }
+
+    /// HEAD the URL; servers that don't allow HEAD get a GET (body unread)
+    fn check_url(&self, url: &str) -> bool {
+        let response = match self.client.head(url).send() {
+            Ok(r) if r.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED => {
+                self.client.get(url).send()
+            }
+            other => other,
+        };
+
+        match response {
+            Ok(response) => {
+                response.status().is_success()
+                    && response
+                        .headers()
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .is_some_and(|ct| ct.starts_with("image/"))
+            }
+            Err(e) => {
+                log::debug!("Artwork probe failed for {url}: {e}");
+                false
+            }
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ResolverState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn choice_key(game_key: &str, art_type: ArtworkType) -> String {
+    format!("{game_key}:{}", art_type.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Serve images under paths starting with `/ok`, 404 everything else.
+    /// Returns the base URL and a counter of requests received.
+    fn serve() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let status = if path.starts_with("/ok") {
+                    "200 OK\r\nContent-Type: image/jpeg"
+                } else {
+                    "404 Not Found\r\nContent-Type: text/html"
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+            }
+        });
+        (format!("http://{addr}"), hits)
+    }
+
+    fn game_with_cover(url: &str) -> Game {
+        let mut game = Game::new("10", "Counter-Strike", StoreType::Steam);
+        game.cover_url = Some(url.to_string());
+        game
+    }
+
+    /// Offers the same URLs for every game
+    struct FixedProvider(Vec<String>);
+
+    impl ArtworkProvider for FixedProvider {
+        fn id(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn candidates(
+            &self,
+            _game: &Game,
+            _art_type: ArtworkType,
+        ) -> Result<Vec<ArtworkCandidate>, LauncherError> {
+            Ok(self
+                .0
+                .iter()
+                .map(|url| ArtworkCandidate::from_url(self.id(), url))
+                .collect())
+        }
+    }
+
+    /// Always fails, like a provider whose API is down
+    struct FailingProvider;
+
+    impl ArtworkProvider for FailingProvider {
+        fn id(&self) -> &'static str {
+            "failing"
+        }
+
+        fn candidates(
+            &self,
+            _game: &Game,
+            _art_type: ArtworkType,
+        ) -> Result<Vec<ArtworkCandidate>, LauncherError> {
+            Err(LauncherError::NetworkError("offline".to_string()))
+        }
+    }
+
+    fn resolver_with(temp: &TempDir, fallbacks: Vec<String>) -> ArtworkResolver {
+        let mut resolver = ArtworkResolver::new(temp.path().join("probes.json"));
+        resolver.add_provider(Arc::new(FixedProvider(fallbacks)));
+        resolver
+    }
+
+    #[test]
+    fn test_resolve_prefers_game_url() {
+        let temp = TempDir::new().unwrap();
+        let (base, _) = serve();
+        let resolver = resolver_with(&temp, vec![format!("{base}/ok/header.jpg")]);
+        let game = game_with_cover(&format!("{base}/ok/library.jpg"));
+
+        assert_eq!(
+            resolver.resolve(&game, ArtworkType::Cover, 0),
+            Some(format!("{base}/ok/library.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_falls_back_when_missing() {
+        let temp = TempDir::new().unwrap();
+        let (base, _) = serve();
+        let resolver = resolver_with(
+            &temp,
+            vec![
+                format!("{base}/missing/logo.png"),
+                format!("{base}/ok/header.jpg"),
+            ],
+        );
+        let game = game_with_cover(&format!("{base}/missing/library.jpg"));
+
+        assert_eq!(
+            resolver.resolve(&game, ArtworkType::Cover, 0),
+            Some(format!("{base}/ok/header.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_remembers_choice() {
+        let temp = TempDir::new().unwrap();
+        let (base, hits) = serve();
+        let resolver = resolver_with(&temp, vec![format!("{base}/ok/header.jpg")]);
+        let game = game_with_cover(&format!("{base}/missing/library.jpg"));
+
+        resolver.resolve(&game, ArtworkType::Cover, 0);
+        let probed = hits.load(Ordering::SeqCst);
+        assert_eq!(probed, 2);
+
+        let again = resolver.resolve(&game, ArtworkType::Cover, 60);
+        assert_eq!(again, Some(format!("{base}/ok/header.jpg")));
+        assert_eq!(hits.load(Ordering::SeqCst), probed);
+
+        // Expired results are checked again
+        resolver.resolve(&game, ArtworkType::Cover, PROBE_OK_TTL_SECS + 1);
+        assert!(hits.load(Ordering::SeqCst) > probed);
+    }
+
+    #[test]
+    fn test_resolve_persists_across_reopen() {
+        let temp = TempDir::new().unwrap();
+        let (base, hits) = serve();
+        let game = game_with_cover(&format!("{base}/missing/library.jpg"));
+
+        let resolver = resolver_with(&temp, vec![format!("{base}/ok/header.jpg")]);
+        resolver.resolve(&game, ArtworkType::Cover, 0);
+        resolver.flush().unwrap();
+        let probed = hits.load(Ordering::SeqCst);
+
+        let reopened = resolver_with(&temp, Vec::new());
+        assert_eq!(
+            reopened.resolve(&game, ArtworkType::Cover, 10),
+            Some(format!("{base}/ok/header.jpg"))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), probed);
+    }
+
+    #[test]
+    fn test_resolve_none_when_nothing_works() {
+        let temp = TempDir::new().unwrap();
+        let (base, hits) = serve();
+        let resolver = resolver_with(&temp, vec![format!("{base}/missing/header.jpg")]);
+        let game = game_with_cover(&format!("{base}/missing/library.jpg"));
+
+        assert_eq!(resolver.resolve(&game, ArtworkType::Cover, 0), None);
+        let probed = hits.load(Ordering::SeqCst);
+        assert_eq!(resolver.resolve(&game, ArtworkType::Cover, 10), None);
+        assert_eq!(hits.load(Ordering::SeqCst), probed);
+    }
+
+    #[test]
+    fn test_reject_moves_to_next_candidate() {
+        let temp = TempDir::new().unwrap();
+        let (base, _) = serve();
+        let resolver = resolver_with(&temp, vec![format!("{base}/ok/header.jpg")]);
+        let game = game_with_cover(&format!("{base}/ok/library.jpg"));
+
+        let first = resolver.resolve(&game, ArtworkType::Cover, 0).unwrap();
+        resolver.reject(&game.unique_key(), ArtworkType::Cover, &first, 1);
+
+        assert_eq!(
+            resolver.resolve(&game, ArtworkType::Cover, 2),
+            Some(format!("{base}/ok/header.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_skips_failing_provider() {
+        let temp = TempDir::new().unwrap();
+        let (base, _) = serve();
+        let mut resolver = ArtworkResolver::new(temp.path().join("probes.json"));
+        resolver.add_provider(Arc::new(FailingProvider));
+        resolver.add_provider(Arc::new(FixedProvider(vec![format!(
+            "{base}/ok/header.jpg"
+        )])));
+        let game = game_with_cover(&format!("{base}/missing/library.jpg"));
+
+        assert_eq!(
+            resolver.resolve(&game, ArtworkType::Cover, 0),
+            Some(format!("{base}/ok/header.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_browse_collects_all_providers() {
+        let temp = TempDir::new().unwrap();
+        let mut resolver = resolver_with(
+            &temp,
+            vec!["https://a/1.jpg".to_string(), "https://a/2.jpg".to_string()],
+        );
+        resolver.add_provider(Arc::new(FailingProvider));
+        resolver.add_provider(Arc::new(FixedProvider(vec![
+            "https://a/2.jpg".to_string(),
+            "https://b/3.jpg".to_string(),
+        ])));
+        let game = Game::new("10", "Counter-Strike", StoreType::Steam);
+
+        let urls: Vec<String> = resolver
+            .browse(&game, ArtworkType::Hero)
+            .into_iter()
+            .map(|c| c.url)
+            .collect();
+        assert_eq!(
+            urls,
+            ["https://a/1.jpg", "https://a/2.jpg", "https://b/3.jpg"]
+        );
+    }
+}
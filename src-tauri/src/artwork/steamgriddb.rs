@@ -0,0 +1,358 @@
+use super::provider::{ArtworkCandidate, ArtworkProvider};
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, LauncherError, StoreType};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Public SteamGridDB API
+pub const STEAMGRIDDB_API_BASE: &str = "https://www.steamgriddb.com/api/v2";
+
+/// Portrait grid sizes that fit the library's cover slots
+const COVER_DIMENSIONS: &str = "600x900,342x482,660x930";
+
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    #[serde(default)]
+    success: bool,
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Image {
+    url: String,
+    #[serde(default)]
+    thumb: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    style: Option<String>,
+}
+
+/// Community artwork from a SteamGridDB-compatible API.
+///
+/// Steam games are looked up by app id; everything else by title, through
+/// the API's autocomplete search. Needs an API key, and offers nothing
+/// until one is set.
+pub struct SteamGridDbProvider {
+    base_url: String,
+    client: reqwest::blocking::Client,
+    api_key: Mutex<Option<String>>,
+    /// SteamGridDB game id found for each title (None if the search had no match)
+    game_ids: Mutex<HashMap<String, Option<u64>>>,
+}
+
+impl SteamGridDbProvider {
+    pub fn new() -> Self {
+        Self::with_base_url(STEAMGRIDDB_API_BASE)
+    }
+
+    /// Use a different API root (e.g. a mirror, or a local server in tests)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::builder()
+                .timeout(std::time::Duration::from_secs(15))
+                .connect_timeout(std::time::Duration::from_secs(5))
+                .build()
+                .unwrap_or_else(|_| reqwest::blocking::Client::new()),
+            api_key: Mutex::new(None),
+            game_ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set or clear the API key requests are made with
+    pub fn set_api_key(&self, api_key: Option<String>) {
+        let api_key = api_key.filter(|k| !k.trim().is_empty());
+        *self.api_key.lock().unwrap_or_else(|e| e.into_inner()) = api_key;
+    }
+
+    pub fn has_api_key(&self) -> bool {
+        self.api_key
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+
+    /// SteamGridDB's id for a title, searched once and then remembered
+    fn find_game_id(&self, api_key: &str, title: &str) -> Result<Option<u64>, LauncherError> {
+        let remembered = self
+            .game_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(title)
+            .copied();
+        if let Some(id) = remembered {
+            return Ok(id);
+        }
+
+        let path = format!("/search/autocomplete/{}", urlencoding::encode(title));
+        let id = self
+            .get::<SearchResult>(api_key, &path)?
+            .first()
+            .map(|result| result.id);
+        self.game_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(title.to_string(), id);
+        Ok(id)
+    }
+
+    /// GET an endpoint and return its `data` list (empty if it 404s)
+    fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        api_key: &str,
+        path: &str,
+    ) -> Result<Vec<T>, LauncherError> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(api_key)
+            .send()
+            .map_err(LauncherError::from_request)?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => return Ok(Vec::new()),
+            status if !status.is_success() => {
+                return Err(LauncherError::from_status("SteamGridDB", status, ""))
+            }
+            _ => {}
+        }
+
+        let body: Response<T> = response
+            .json()
+            .map_err(|e| LauncherError::ParseError(format!("Invalid SteamGridDB response: {e}")))?;
+        if !body.success {
+            return Ok(Vec::new());
+        }
+        Ok(body.data)
+    }
+}
+
+impl Default for SteamGridDbProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtworkProvider for SteamGridDbProvider {
+    fn id(&self) -> &'static str {
+        "steamgriddb"
+    }
+
+    fn candidates(
+        &self,
+        game: &Game,
+        art_type: ArtworkType,
+    ) -> Result<Vec<ArtworkCandidate>, LauncherError> {
+        let api_key = self
+            .api_key
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let Some(api_key) = api_key else {
+            return Ok(Vec::new());
+        };
+
+        let target = if game.store == StoreType::Steam {
+            format!("steam/{}", urlencoding::encode(&game.id))
+        } else {
+            match self.find_game_id(&api_key, &game.name)? {
+                Some(id) => format!("game/{id}"),
+                None => return Ok(Vec::new()),
+            }
+        };
+        let path = match art_type {
+            ArtworkType::Cover => format!("/grids/{target}?dimensions={COVER_DIMENSIONS}"),
+            ArtworkType::Hero => format!("/heroes/{target}"),
+            ArtworkType::Logo => format!("/logos/{target}"),
+            ArtworkType::Icon => format!("/icons/{target}"),
+        };
+
+        Ok(self
+            .get::<Image>(&api_key, &path)?
+            .into_iter()
+            .map(|image| ArtworkCandidate {
+                url: image.url,
+                thumbnail_url: image.thumb,
+                provider: self.id().to_string(),
+                width: image.width,
+                height: image.height,
+                style: image.style,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    /// Stand-in for the API: answers requests with the given key from a
+    /// table of path -> JSON body, 401s other keys and 404s unknown paths.
+    /// Returns the base URL and the paths requested.
+    fn serve(routes: Vec<(&'static str, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requested);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                log.lock().unwrap().push(path.clone());
+
+                let authorized = request
+                    .lines()
+                    .any(|l| l.eq_ignore_ascii_case("authorization: Bearer test-key"));
+                let (status, body) = if !authorized {
+                    ("401 Unauthorized", r#"{"success":false}"#)
+                } else {
+                    routes
+                        .iter()
+                        .find(|(route, _)| *route == path)
+                        .map(|(_, body)| ("200 OK", *body))
+                        .unwrap_or(("404 Not Found", r#"{"success":false}"#))
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+        (format!("http://{addr}"), requested)
+    }
+
+    fn provider(base: &str) -> SteamGridDbProvider {
+        let provider = SteamGridDbProvider::with_base_url(base);
+        provider.set_api_key(Some("test-key".to_string()));
+        provider
+    }
+
+    const GRIDS: &str = r#"{"success":true,"data":[
+        {"id":1,"url":"https://cdn.example.com/grid/a.png","thumb":"https://cdn.example.com/thumb/a.png","width":600,"height":900,"style":"alternate"},
+        {"id":2,"url":"https://cdn.example.com/grid/b.png","width":600,"height":900}
+    ]}"#;
+
+    #[test]
+    fn test_steam_games_use_app_id() {
+        let (base, requested) = serve(vec![(
+            "/grids/steam/440?dimensions=600x900,342x482,660x930",
+            GRIDS,
+        )]);
+        let game = Game::new("440", "Team Fortress 2", StoreType::Steam);
+
+        let candidates = provider(&base)
+            .candidates(&game, ArtworkType::Cover)
+            .unwrap();
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(
+            candidates[0],
+            ArtworkCandidate {
+                url: "https://cdn.example.com/grid/a.png".to_string(),
+                thumbnail_url: Some("https://cdn.example.com/thumb/a.png".to_string()),
+                provider: "steamgriddb".to_string(),
+                width: Some(600),
+                height: Some(900),
+                style: Some("alternate".to_string()),
+            }
+        );
+        assert_eq!(candidates[1].thumbnail_url, None);
+        // No title search for Steam games
+        assert_eq!(requested.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_other_games_are_searched_by_title() {
+        let (base, requested) = serve(vec![
+            (
+                "/search/autocomplete/Hollow%20Knight",
+                r#"{"success":true,"data":[{"id":77,"name":"Hollow Knight"},{"id":78,"name":"Hollow Knight: Silksong"}]}"#,
+            ),
+            (
+                "/heroes/game/77",
+                r#"{"success":true,"data":[{"url":"https://cdn.example.com/hero.png"}]}"#,
+            ),
+            ("/logos/game/77", r#"{"success":true,"data":[]}"#),
+        ]);
+        let provider = provider(&base);
+        let game = Game::new("1308320724", "Hollow Knight", StoreType::Gog);
+
+        let heroes = provider.candidates(&game, ArtworkType::Hero).unwrap();
+        assert_eq!(heroes.len(), 1);
+        assert_eq!(heroes[0].url, "https://cdn.example.com/hero.png");
+        assert!(provider
+            .candidates(&game, ArtworkType::Logo)
+            .unwrap()
+            .is_empty());
+
+        // The search result is remembered
+        let searches = requested
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.starts_with("/search/"))
+            .count();
+        assert_eq!(searches, 1);
+    }
+
+    #[test]
+    fn test_unknown_games_have_no_candidates() {
+        let (base, _) = serve(vec![(
+            "/search/autocomplete/Nothing",
+            r#"{"success":true,"data":[]}"#,
+        )]);
+        let provider = provider(&base);
+
+        let local = Game::new("x", "Nothing", StoreType::Local);
+        assert!(provider
+            .candidates(&local, ArtworkType::Icon)
+            .unwrap()
+            .is_empty());
+        let steam = Game::new("1", "Missing", StoreType::Steam);
+        assert!(provider
+            .candidates(&steam, ArtworkType::Hero)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_api_key_required() {
+        let (base, requested) = serve(vec![("/heroes/steam/440", GRIDS)]);
+        let game = Game::new("440", "Team Fortress 2", StoreType::Steam);
+
+        let provider = SteamGridDbProvider::with_base_url(&base);
+        assert!(!provider.has_api_key());
+        assert!(provider
+            .candidates(&game, ArtworkType::Hero)
+            .unwrap()
+            .is_empty());
+        assert!(requested.lock().unwrap().is_empty());
+
+        provider.set_api_key(Some("wrong-key".to_string()));
+        assert!(matches!(
+            provider.candidates(&game, ArtworkType::Hero),
+            Err(LauncherError::AuthRequired(_))
+        ));
+
+        provider.set_api_key(Some("  ".to_string()));
+        assert!(!provider.has_api_key());
+    }
+}
@@ -0,0 +1,5 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    tenfoot_lib::cli::run(std::env::args().skip(1))
+}
@@ -0,0 +1,527 @@
+//! `tenfoot-cli`: the library from a terminal, for scripts, SSH sessions and
+//! other frontends. It works on the same data dir as the app, so syncs and
+//! launches from either show up in both.
+
+use crate::collections::UserCollections;
+use crate::launcher_core::supervisor::{ProcessSource, SystemProcesses};
+use crate::launcher_core::{Game, GameLibrary, GameLinks, LauncherError, StoreStatus, StoreType};
+use crate::logging::{self, LogLevel};
+use crate::metadata::MetadataService;
+use crate::storage::{self, PlayHistory, PlaySession, Storage};
+use crate::stores::epic::EpicApi;
+use crate::stores::steam::SteamApi;
+use crate::sync::{self, combine_games, merge_owned_games};
+use crate::vault::CredentialVault;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Unlocks a passphrase-protected credential vault for `sync`
+pub const PASSPHRASE_VAR: &str = "TENFOOT_PASSPHRASE";
+
+pub const USAGE: &str = "\
+Usage: tenfoot-cli <command> [options]
+
+Commands:
+  list [--store <store>] [--installed] [--json]
+                      List the games in the library (hidden games are left out)
+  launch <store:id>   Launch a game, e.g. `launch steam:440`
+  sync <steam|epic>   Fetch the owned games of a connected store
+  status [--json]     Show what was found for each store
+  export [--output <file>]
+                      Write the library, collections and play history as JSON
+  help                Show this message
+
+Stores: steam, epic, gog, local. `sync` needs the store connected in the app;
+set TENFOOT_PASSPHRASE if the credentials are protected by a passphrase.";
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    List {
+        store: Option<StoreType>,
+        installed: bool,
+        json: bool,
+    },
+    Launch {
+        game_key: String,
+    },
+    Sync {
+        store: StoreType,
+    },
+    Status {
+        json: bool,
+    },
+    Export {
+        output: Option<PathBuf>,
+    },
+    Help,
+}
+
+impl Command {
+    /// Parse the arguments after the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let Some(command) = args.next() else {
+            return Ok(Command::Help);
+        };
+
+        let parsed = match command.as_str() {
+            "list" => {
+                let (mut store, mut installed, mut json) = (None, false, false);
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--store" => store = Some(parse_store(&value_of("--store", &mut args)?)?),
+                        "--installed" => installed = true,
+                        "--json" => json = true,
+                        _ => return Err(unexpected(&arg)),
+                    }
+                }
+                Command::List {
+                    store,
+                    installed,
+                    json,
+                }
+            }
+            "launch" => {
+                let game_key = args.next().ok_or("launch needs a game, e.g. steam:440")?;
+                if !game_key.contains(':') {
+                    return Err(format!("'{game_key}' is not a game key like steam:440"));
+                }
+                Command::Launch { game_key }
+            }
+            "sync" => {
+                let store = args.next().ok_or("sync needs a store: steam or epic")?;
+                let store = sync::synced_store(&store)
+                    .ok_or_else(|| format!("'{store}' can't be synced, only steam and epic"))?;
+                Command::Sync { store }
+            }
+            "status" => {
+                let mut json = false;
+                for arg in args.by_ref() {
+                    match arg.as_str() {
+                        "--json" => json = true,
+                        _ => return Err(unexpected(&arg)),
+                    }
+                }
+                Command::Status { json }
+            }
+            "export" => {
+                let mut output = None;
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--output" | "-o" => output = Some(value_of("--output", &mut args)?.into()),
+                        _ => return Err(unexpected(&arg)),
+                    }
+                }
+                Command::Export { output }
+            }
+            "help" | "--help" | "-h" => Command::Help,
+            _ => return Err(format!("unknown command '{command}'")),
+        };
+
+        match args.next() {
+            Some(arg) => Err(unexpected(&arg)),
+            None => Ok(parsed),
+        }
+    }
+}
+
+fn parse_store(value: &str) -> Result<StoreType, String> {
+    match value {
+        "steam" => Ok(StoreType::Steam),
+        "epic" => Ok(StoreType::Epic),
+        "gog" => Ok(StoreType::Gog),
+        "local" => Ok(StoreType::Local),
+        _ => Err(format!("unknown store '{value}'")),
+    }
+}
+
+/// The value after a flag
+fn value_of(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next()
+        .filter(|value| !value.starts_with('-'))
+        .ok_or_else(|| format!("{flag} needs a value"))
+}
+
+fn unexpected(arg: &str) -> String {
+    format!("unexpected argument '{arg}'")
+}
+
+/// Run the CLI with the arguments after the program name
+pub fn run(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let command = match Command::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("tenfoot-cli: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if command == Command::Help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match Cli::open().and_then(|cli| cli.execute(command)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("tenfoot-cli: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Everything `export` writes
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LibraryExport {
+    version: &'static str,
+    exported_at: u64,
+    games: Vec<Game>,
+    collections: UserCollections,
+    game_links: GameLinks,
+    play_history: PlayHistory,
+    play_sessions: Vec<PlaySession>,
+}
+
+/// The app's data, opened the way the app opens it
+struct Cli {
+    data_dir: PathBuf,
+    storage: Storage,
+    library: GameLibrary,
+}
+
+impl Cli {
+    fn open() -> Result<Self, LauncherError> {
+        let data_dir = storage::default_data_dir()?;
+        // Only problems go to stderr; stdout is for the output
+        logging::init(Some(&data_dir), LogLevel::Warn);
+
+        let storage = Storage::open(&data_dir)?;
+        let mut library = GameLibrary::new();
+        crate::stores::register_default_stores(&mut library)?;
+        library.set_store_timeout(storage.load_settings()?.store_timeout());
+
+        Ok(Self {
+            data_dir,
+            storage,
+            library,
+        })
+    }
+
+    fn execute(&self, command: Command) -> Result<(), LauncherError> {
+        match command {
+            Command::List {
+                store,
+                installed,
+                json,
+            } => {
+                let games = filter_games(self.visible_games()?, store.as_ref(), installed);
+                if json {
+                    println!("{}", to_json(&games)?);
+                } else {
+                    print!("{}", format_games(&games));
+                }
+            }
+            Command::Launch { game_key } => {
+                self.library.launch_game(&game_key)?;
+                self.storage.record_game_launch_with_timestamp(&game_key)?;
+                println!("Launched {game_key}");
+            }
+            Command::Sync { store } => {
+                let count = self.sync(store.clone())?;
+                println!("Synced {count} {store} games");
+            }
+            Command::Status { json } => {
+                let statuses = self.store_statuses()?;
+                if json {
+                    println!("{}", to_json(&statuses)?);
+                } else {
+                    print!("{}", format_statuses(&statuses));
+                }
+            }
+            Command::Export { output } => {
+                let export = to_json(&self.export()?)?;
+                match output {
+                    Some(path) => {
+                        fs::write(&path, export)?;
+                        println!("Exported the library to {}", path.display());
+                    }
+                    None => println!("{export}"),
+                }
+            }
+            Command::Help => println!("{USAGE}"),
+        }
+        Ok(())
+    }
+
+    /// Every known game, except the ones the user hid
+    fn visible_games(&self) -> Result<Vec<Game>, LauncherError> {
+        let collections = self.storage.load_collections()?;
+        Ok(self
+            .all_games()?
+            .into_iter()
+            .filter(|g| !collections.is_hidden(&g.unique_key()))
+            .collect())
+    }
+
+    fn all_games(&self) -> Result<Vec<Game>, LauncherError> {
+        let installed_games = self.library.refresh_all()?;
+        Ok(combine_games(
+            self.storage.load_games_cache()?,
+            installed_games,
+        ))
+    }
+
+    /// Fetch and cache a store's owned games, like the app's sync does.
+    /// Returns the number of games.
+    fn sync(&self, store: StoreType) -> Result<usize, LauncherError> {
+        let vault = self.unlocked_vault()?;
+        let credentials = vault.load()?;
+        let now = now_secs();
+
+        let (owned_games, details) = match store {
+            StoreType::Steam => {
+                let creds = credentials
+                    .steam
+                    .ok_or_else(|| LauncherError::NotConnected("Steam".to_string()))?;
+                (SteamApi::new().get_owned_games(&creds)?, None)
+            }
+            StoreType::Epic => {
+                let creds = credentials
+                    .epic
+                    .ok_or_else(|| LauncherError::NotConnected("Epic".to_string()))?;
+                let api = EpicApi::new();
+                let valid_creds = api.ensure_valid_token(&creds)?;
+                if valid_creds.access_token != creds.access_token {
+                    vault.update(|creds| creds.epic = Some(valid_creds.clone()))?;
+                }
+                let (games, metadata) = api.get_library(&valid_creds)?;
+                (games, Some(metadata))
+            }
+            other => {
+                return Err(LauncherError::PlatformNotSupported(format!(
+                    "{other} has no online library to sync"
+                )))
+            }
+        };
+
+        let installed_games = self.library.refresh_all()?;
+        let merged_games = merge_owned_games(owned_games, &installed_games, store.clone());
+        self.storage.save_owned_games(store, &merged_games, now)?;
+        if let Some(metadata) = details {
+            let details = sync::save_epic_metadata(&self.storage, metadata)?;
+            MetadataService::new(Storage::open(&self.data_dir)?).store(details, now)?;
+        }
+        Ok(merged_games.len())
+    }
+
+    /// The credential vault, unlocked with `TENFOOT_PASSPHRASE` if it needs a
+    /// passphrase
+    fn unlocked_vault(&self) -> Result<CredentialVault, LauncherError> {
+        let vault = CredentialVault::open(&self.data_dir);
+        if vault.status().locked {
+            match std::env::var(PASSPHRASE_VAR) {
+                Ok(passphrase) => vault.unlock(Some(&passphrase))?,
+                Err(_) => {
+                    return Err(LauncherError::ConfigError(format!(
+                        "The credentials are locked; set {PASSPHRASE_VAR} to unlock them"
+                    )))
+                }
+            }
+        }
+        Ok(vault)
+    }
+
+    fn store_statuses(&self) -> Result<Vec<StoreStatus>, LauncherError> {
+        // Scan first so the statuses have a last scan to report
+        self.library.refresh_all()?;
+        let mut statuses = self
+            .library
+            .store_statuses(&SystemProcesses::new().processes());
+        let credentials = CredentialVault::open(&self.data_dir).load().ok();
+        sync::apply_account_status(&mut statuses, credentials.as_ref(), &self.storage)?;
+        Ok(statuses)
+    }
+
+    fn export(&self) -> Result<LibraryExport, LauncherError> {
+        Ok(LibraryExport {
+            version: env!("CARGO_PKG_VERSION"),
+            exported_at: now_secs(),
+            games: self.all_games()?,
+            collections: self.storage.load_collections()?,
+            game_links: self.storage.load_game_links()?,
+            play_history: self.storage.load_play_history()?,
+            play_sessions: self.storage.load_play_sessions()?.sessions,
+        })
+    }
+}
+
+/// Games of one store and/or only installed ones, sorted by name
+fn filter_games(games: Vec<Game>, store: Option<&StoreType>, installed: bool) -> Vec<Game> {
+    let mut games: Vec<Game> = games
+        .into_iter()
+        .filter(|g| store.is_none_or(|store| g.store == *store))
+        .filter(|g| !installed || g.installed)
+        .collect();
+    games.sort_by_cached_key(|g| g.name.to_lowercase());
+    games
+}
+
+/// One game per line: key, whether it's installed, name
+fn format_games(games: &[Game]) -> String {
+    let keys: Vec<String> = games.iter().map(Game::unique_key).collect();
+    let width = keys.iter().map(String::len).max().unwrap_or(0);
+    games
+        .iter()
+        .zip(&keys)
+        .map(|(game, key)| {
+            let installed = if game.installed { "installed" } else { "" };
+            format!("{key:<width$}  {installed:<9}  {}\n", game.name)
+        })
+        .collect()
+}
+
+/// One block per store
+fn format_statuses(statuses: &[StoreStatus]) -> String {
+    let mut text = String::new();
+    for status in statuses {
+        let mut facts = vec![if status.available {
+            "available"
+        } else {
+            "not found"
+        }
+        .to_string()];
+        if status.client_running {
+            facts.push("client running".to_string());
+        }
+        text.push_str(&format!("{}: {}\n", status.display_name, facts.join(", ")));
+
+        if let Some(path) = &status.client_path {
+            text.push_str(&format!("  client: {}\n", path.display()));
+        }
+        if let Some(at) = status.last_scan_at {
+            let result = match (&status.game_count, &status.last_error) {
+                (Some(count), _) => format!("{count} games"),
+                (None, Some(error)) => format!("failed: {error}"),
+                (None, None) => "skipped".to_string(),
+            };
+            text.push_str(&format!(
+                "  last scan: {} ({result}, {} ms)\n",
+                format_time(at),
+                status.last_scan_duration_ms.unwrap_or_default()
+            ));
+        }
+        if sync::synced_store(&status.store_id).is_some() {
+            let credentials = serde_json::to_value(status.credentials)
+                .ok()
+                .and_then(|v| v.as_str().map(|s| s.replace('_', " ")))
+                .unwrap_or_default();
+            let last_sync = status
+                .last_sync_at
+                .map(format_time)
+                .unwrap_or_else(|| "never".to_string());
+            text.push_str(&format!(
+                "  credentials: {credentials}, last sync: {last_sync}\n"
+            ));
+        }
+    }
+    text
+}
+
+fn format_time(secs: u64) -> String {
+    logging::format_timestamp(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, LauncherError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| LauncherError::JsonError(format!("Failed to write JSON: {e}")))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        Command::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]), Ok(Command::Help));
+        assert_eq!(
+            parse(&["list", "--installed", "--store", "gog"]),
+            Ok(Command::List {
+                store: Some(StoreType::Gog),
+                installed: true,
+                json: false,
+            })
+        );
+        assert_eq!(
+            parse(&["launch", "steam:440"]),
+            Ok(Command::Launch {
+                game_key: "steam:440".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["sync", "epic"]),
+            Ok(Command::Sync {
+                store: StoreType::Epic
+            })
+        );
+        assert_eq!(
+            parse(&["status", "--json"]),
+            Ok(Command::Status { json: true })
+        );
+        assert_eq!(
+            parse(&["export", "-o", "library.json"]),
+            Ok(Command::Export {
+                output: Some(PathBuf::from("library.json"))
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["play"]).is_err());
+        assert!(parse(&["list", "--store"]).is_err());
+        assert!(parse(&["list", "--store", "--json"]).is_err());
+        assert!(parse(&["list", "--store", "origin"]).is_err());
+        assert!(parse(&["launch"]).is_err());
+        assert!(parse(&["launch", "440"]).is_err());
+        assert!(parse(&["launch", "steam:440", "steam:570"]).is_err());
+        assert!(parse(&["sync", "gog"]).is_err());
+        assert!(parse(&["status", "--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_list_games() {
+        let game = |id: &str, name: &str, store: StoreType, installed: bool| Game {
+            installed,
+            ..Game::new(id, name, store)
+        };
+        let games = vec![
+            game("440", "Team Fortress 2", StoreType::Steam, true),
+            game("Fortnite", "Fortnite", StoreType::Epic, false),
+            game("570", "dota 2", StoreType::Steam, false),
+        ];
+
+        let steam = filter_games(games.clone(), Some(&StoreType::Steam), false);
+        assert_eq!(
+            format_games(&steam),
+            "steam:570             dota 2\nsteam:440  installed  Team Fortress 2\n"
+        );
+        let installed = filter_games(games, None, true);
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].id, "440");
+    }
+}
@@ -0,0 +1,489 @@
+use crate::launcher_core::LauncherError;
+use crate::query::Query;
+use crate::storage::slugify;
+use serde::{Deserialize, Serialize};
+
+/// A named, user-created group of games
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    /// Unique keys ("steam:440") in the order they were added
+    #[serde(default)]
+    pub game_keys: Vec<String>,
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// A saved query whose games are worked out each time it's opened
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SmartCollection {
+    pub id: String,
+    pub name: String,
+    /// Query in the library filter language, e.g. `installed:true playtime:<60`
+    pub query: String,
+    /// Built-in views can't be edited or deleted
+    #[serde(default)]
+    pub builtin: bool,
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// Smart collections offered to every user
+const BUILTIN_SMART_COLLECTIONS: &[(&str, &str, &str)] = &[
+    (
+        "builtin-unplayed",
+        "Unplayed",
+        "last_played:never playtime:0",
+    ),
+    (
+        "builtin-recently-played",
+        "Recently played",
+        "last_played:<14d",
+    ),
+    (
+        "builtin-quick-sessions",
+        "Quick sessions",
+        "installed:true playtime:>0 playtime:<2h",
+    ),
+    (
+        "builtin-gathering-dust",
+        "Gathering dust",
+        "installed:true last_played:>90d",
+    ),
+];
+
+/// User-owned library organization: favorites, hidden games and collections
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct UserCollections {
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    #[serde(default)]
+    pub hidden: Vec<String>,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+    /// User-defined smart collections (built-ins are not stored)
+    #[serde(default)]
+    pub smart: Vec<SmartCollection>,
+}
+
+impl UserCollections {
+    /// Check if a game is a favorite
+    pub fn is_favorite(&self, game_key: &str) -> bool {
+        self.favorites.iter().any(|k| k == game_key)
+    }
+
+    /// Check if a game is hidden from the library
+    pub fn is_hidden(&self, game_key: &str) -> bool {
+        self.hidden.iter().any(|k| k == game_key)
+    }
+
+    /// Mark or unmark games as favorites
+    pub fn set_favorite(&mut self, game_keys: &[String], favorite: bool) {
+        set_membership(&mut self.favorites, game_keys, favorite);
+    }
+
+    /// Hide or unhide games
+    pub fn set_hidden(&mut self, game_keys: &[String], hidden: bool) {
+        set_membership(&mut self.hidden, game_keys, hidden);
+    }
+
+    /// Get a collection by id
+    pub fn get(&self, collection_id: &str) -> Option<&Collection> {
+        self.collections.iter().find(|c| c.id == collection_id)
+    }
+
+    /// Create an empty collection; names must be unique (ignoring case)
+    pub fn create(&mut self, name: &str, created_at: u64) -> Result<Collection, LauncherError> {
+        let name = self.validate_name(name, None)?;
+
+        let base = slugify(&name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.get(&id).is_some() {
+            id = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+
+        let collection = Collection {
+            id,
+            name,
+            game_keys: Vec::new(),
+            created_at,
+        };
+        self.collections.push(collection.clone());
+        Ok(collection)
+    }
+
+    /// Rename a collection (its id stays the same)
+    pub fn rename(&mut self, collection_id: &str, name: &str) -> Result<Collection, LauncherError> {
+        let name = self.validate_name(name, Some(collection_id))?;
+        let collection = self.get_mut(collection_id)?;
+        collection.name = name;
+        Ok(collection.clone())
+    }
+
+    /// Delete a collection (the games themselves are untouched)
+    pub fn delete(&mut self, collection_id: &str) -> Result<(), LauncherError> {
+        let before = self.collections.len();
+        self.collections.retain(|c| c.id != collection_id);
+        if self.collections.len() == before {
+            return Err(not_found(collection_id));
+        }
+        Ok(())
+    }
+
+    /// Add games to a collection, skipping ones already in it
+    pub fn add_games(
+        &mut self,
+        collection_id: &str,
+        game_keys: &[String],
+    ) -> Result<Collection, LauncherError> {
+        let collection = self.get_mut(collection_id)?;
+        set_membership(&mut collection.game_keys, game_keys, true);
+        Ok(collection.clone())
+    }
+
+    /// Remove games from a collection
+    pub fn remove_games(
+        &mut self,
+        collection_id: &str,
+        game_keys: &[String],
+    ) -> Result<Collection, LauncherError> {
+        let collection = self.get_mut(collection_id)?;
+        set_membership(&mut collection.game_keys, game_keys, false);
+        Ok(collection.clone())
+    }
+
+    /// Ids of the collections a game belongs to
+    pub fn collections_for(&self, game_key: &str) -> Vec<String> {
+        self.collections
+            .iter()
+            .filter(|c| c.game_keys.iter().any(|k| k == game_key))
+            .map(|c| c.id.clone())
+            .collect()
+    }
+
+    /// Built-in smart collections followed by the user's own
+    pub fn smart_collections(&self) -> Vec<SmartCollection> {
+        BUILTIN_SMART_COLLECTIONS
+            .iter()
+            .map(|(id, name, query)| SmartCollection {
+                id: id.to_string(),
+                name: name.to_string(),
+                query: query.to_string(),
+                builtin: true,
+                created_at: 0,
+            })
+            .chain(self.smart.iter().cloned())
+            .collect()
+    }
+
+    /// Get a smart collection (built-in or user-defined) by id
+    pub fn get_smart(&self, collection_id: &str) -> Option<SmartCollection> {
+        self.smart_collections()
+            .into_iter()
+            .find(|c| c.id == collection_id)
+    }
+
+    /// Save a new smart collection after validating its query
+    pub fn create_smart(
+        &mut self,
+        name: &str,
+        query: &str,
+        created_at: u64,
+    ) -> Result<SmartCollection, LauncherError> {
+        let name = self.validate_smart(name, query, None)?;
+
+        let base = slugify(&name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.get_smart(&id).is_some() {
+            id = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+
+        let collection = SmartCollection {
+            id,
+            name,
+            query: query.trim().to_string(),
+            builtin: false,
+            created_at,
+        };
+        self.smart.push(collection.clone());
+        Ok(collection)
+    }
+
+    /// Change a smart collection's name and query
+    pub fn update_smart(
+        &mut self,
+        collection_id: &str,
+        name: &str,
+        query: &str,
+    ) -> Result<SmartCollection, LauncherError> {
+        let name = self.validate_smart(name, query, Some(collection_id))?;
+        let collection = self
+            .smart
+            .iter_mut()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| not_found(collection_id))?;
+
+        collection.name = name;
+        collection.query = query.trim().to_string();
+        Ok(collection.clone())
+    }
+
+    /// Delete a user-defined smart collection
+    pub fn delete_smart(&mut self, collection_id: &str) -> Result<(), LauncherError> {
+        let before = self.smart.len();
+        self.smart.retain(|c| c.id != collection_id);
+        if self.smart.len() == before {
+            return Err(not_found(collection_id));
+        }
+        Ok(())
+    }
+
+    fn validate_smart(
+        &self,
+        name: &str,
+        query: &str,
+        except_id: Option<&str>,
+    ) -> Result<String, LauncherError> {
+        Query::parse(query)?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(LauncherError::ConfigError(
+                "Collection name is required".to_string(),
+            ));
+        }
+
+        let taken = self
+            .smart_collections()
+            .iter()
+            .any(|c| Some(c.id.as_str()) != except_id && c.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(LauncherError::ConfigError(format!(
+                "A smart collection named \"{name}\" already exists"
+            )));
+        }
+
+        Ok(name.to_string())
+    }
+
+    fn get_mut(&mut self, collection_id: &str) -> Result<&mut Collection, LauncherError> {
+        self.collections
+            .iter_mut()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| not_found(collection_id))
+    }
+
+    fn validate_name(&self, name: &str, except_id: Option<&str>) -> Result<String, LauncherError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(LauncherError::ConfigError(
+                "Collection name is required".to_string(),
+            ));
+        }
+
+        let taken = self
+            .collections
+            .iter()
+            .any(|c| Some(c.id.as_str()) != except_id && c.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(LauncherError::ConfigError(format!(
+                "A collection named \"{name}\" already exists"
+            )));
+        }
+
+        Ok(name.to_string())
+    }
+}
+
+fn not_found(collection_id: &str) -> LauncherError {
+    LauncherError::ConfigError(format!("Collection not found: {collection_id}"))
+}
+
+/// Add or remove keys from a list, keeping insertion order and no duplicates
+fn set_membership(list: &mut Vec<String>, game_keys: &[String], member: bool) {
+    if member {
+        for key in game_keys {
+            if !list.contains(key) {
+                list.push(key.clone());
+            }
+        }
+    } else {
+        list.retain(|k| !game_keys.contains(k));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_favorites_and_hidden() {
+        let mut user = UserCollections::default();
+        user.set_favorite(&keys(&["steam:440", "epic:fn", "steam:440"]), true);
+        user.set_hidden(&keys(&["gog:1"]), true);
+
+        assert_eq!(user.favorites, keys(&["steam:440", "epic:fn"]));
+        assert!(user.is_favorite("epic:fn"));
+        assert!(user.is_hidden("gog:1"));
+
+        user.set_favorite(&keys(&["steam:440"]), false);
+        user.set_hidden(&keys(&["gog:1"]), false);
+        assert_eq!(user.favorites, keys(&["epic:fn"]));
+        assert!(!user.is_hidden("gog:1"));
+    }
+
+    #[test]
+    fn test_create_collection() {
+        let mut user = UserCollections::default();
+        let collection = user.create("  Couch Co-op ", 100).unwrap();
+
+        assert_eq!(collection.id, "couch-co-op");
+        assert_eq!(collection.name, "Couch Co-op");
+        assert_eq!(collection.created_at, 100);
+        assert!(user.get("couch-co-op").is_some());
+    }
+
+    #[test]
+    fn test_create_collection_rejects_empty_and_duplicate_names() {
+        let mut user = UserCollections::default();
+        user.create("RPGs", 0).unwrap();
+
+        assert!(matches!(
+            user.create(" ", 0),
+            Err(LauncherError::ConfigError(_))
+        ));
+        assert!(matches!(
+            user.create("rpgs", 0),
+            Err(LauncherError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_collection_ids_stay_unique_after_rename() {
+        let mut user = UserCollections::default();
+        let first = user.create("Backlog", 0).unwrap();
+        user.rename(&first.id, "Old Backlog").unwrap();
+        let second = user.create("Backlog", 0).unwrap();
+
+        assert_eq!(second.id, "backlog-2");
+        assert_eq!(user.get("backlog").unwrap().name, "Old Backlog");
+    }
+
+    #[test]
+    fn test_rename_collection() {
+        let mut user = UserCollections::default();
+        let rpgs = user.create("RPGs", 0).unwrap();
+        user.create("Shooters", 0).unwrap();
+
+        // Renaming to its own name with different case is allowed
+        assert_eq!(user.rename(&rpgs.id, "rpgs").unwrap().name, "rpgs");
+        assert!(user.rename(&rpgs.id, "Shooters").is_err());
+        assert!(user.rename("missing", "Anything").is_err());
+    }
+
+    #[test]
+    fn test_add_and_remove_games_in_bulk() {
+        let mut user = UserCollections::default();
+        let id = user.create("Roguelikes", 0).unwrap().id;
+
+        user.add_games(&id, &keys(&["steam:1145360", "epic:dead-cells"]))
+            .unwrap();
+        let collection = user
+            .add_games(&id, &keys(&["epic:dead-cells", "gog:spelunky"]))
+            .unwrap();
+        assert_eq!(
+            collection.game_keys,
+            keys(&["steam:1145360", "epic:dead-cells", "gog:spelunky"])
+        );
+        assert_eq!(user.collections_for("gog:spelunky"), vec![id.clone()]);
+
+        let collection = user
+            .remove_games(&id, &keys(&["steam:1145360", "gog:spelunky"]))
+            .unwrap();
+        assert_eq!(collection.game_keys, keys(&["epic:dead-cells"]));
+    }
+
+    #[test]
+    fn test_delete_collection() {
+        let mut user = UserCollections::default();
+        let id = user.create("Temp", 0).unwrap().id;
+
+        user.delete(&id).unwrap();
+        assert!(user.collections.is_empty());
+        assert!(user.delete(&id).is_err());
+    }
+
+    #[test]
+    fn test_builtin_smart_collections_are_valid() {
+        let user = UserCollections::default();
+        let smart = user.smart_collections();
+
+        assert!(!smart.is_empty());
+        for collection in &smart {
+            assert!(collection.builtin);
+            assert!(
+                Query::parse(&collection.query).is_ok(),
+                "{}",
+                collection.query
+            );
+        }
+        assert!(user.get_smart("builtin-unplayed").is_some());
+    }
+
+    #[test]
+    fn test_create_update_delete_smart_collection() {
+        let mut user = UserCollections::default();
+        let created = user
+            .create_smart("Epic RPGs", " store:epic genre:rpg ", 5)
+            .unwrap();
+        assert_eq!(created.id, "epic-rpgs");
+        assert_eq!(created.query, "store:epic genre:rpg");
+        assert!(!created.builtin);
+
+        let updated = user
+            .update_smart(
+                &created.id,
+                "Epic RPGs",
+                "store:epic genre:rpg installed:true",
+            )
+            .unwrap();
+        assert_eq!(updated.query, "store:epic genre:rpg installed:true");
+        assert_eq!(
+            user.smart_collections().len(),
+            BUILTIN_SMART_COLLECTIONS.len() + 1
+        );
+
+        user.delete_smart(&created.id).unwrap();
+        assert!(user.smart.is_empty());
+    }
+
+    #[test]
+    fn test_smart_collection_validation() {
+        let mut user = UserCollections::default();
+
+        assert!(matches!(
+            user.create_smart("Bad", "colour:red", 0),
+            Err(LauncherError::ParseError(_))
+        ));
+        assert!(user.create_smart("unplayed", "playtime:0", 0).is_err());
+        assert!(user
+            .update_smart("builtin-unplayed", "Mine", "playtime:0")
+            .is_err());
+        assert!(user.delete_smart("builtin-unplayed").is_err());
+    }
+
+    #[test]
+    fn test_user_collections_deserialize_defaults() {
+        let user: UserCollections = serde_json::from_str("{}").unwrap();
+        assert_eq!(user, UserCollections::default());
+    }
+}
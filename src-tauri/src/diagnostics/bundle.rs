@@ -0,0 +1,150 @@
+use super::DoctorReport;
+use crate::launcher_core::redact::redact;
+use crate::launcher_core::LauncherError;
+use crate::logging::{self, LOG_DIR};
+use crate::storage::Storage;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Zip up what's needed to look into a problem: the doctor report, settings,
+/// the JSON data files and the log files. Credentials are never included, and every file
+/// is redacted on the way in.
+pub fn write_support_bundle(
+    path: &Path,
+    report: &DoctorReport,
+    storage: &Storage,
+) -> Result<(), LauncherError> {
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+
+    add(&mut zip, "report.txt", &report.to_text())?;
+    add(&mut zip, "report.json", &to_json(report)?)?;
+    match storage.load_settings() {
+        Ok(settings) => add(&mut zip, "settings.json", &to_json(&settings)?)?,
+        Err(e) => add(&mut zip, "settings.error.txt", &e.to_string())?,
+    }
+    let data_files = storage.json_files().into_iter().map(|(_, path)| path);
+    for data_file in data_files {
+        add_file(&mut zip, "data", &data_file)?;
+    }
+    for log_file in logging::log_files(storage.data_dir()) {
+        add_file(&mut zip, LOG_DIR, &log_file)?;
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// Add a file under `dir` if it can be read
+fn add_file(zip: &mut ZipWriter<File>, dir: &str, path: &Path) -> Result<(), LauncherError> {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(());
+    };
+    match fs::read(path) {
+        Ok(content) => add(
+            zip,
+            &format!("{dir}/{name}"),
+            &String::from_utf8_lossy(&content),
+        ),
+        Err(_) => Ok(()),
+    }
+}
+
+fn add(zip: &mut ZipWriter<File>, name: &str, content: &str) -> Result<(), LauncherError> {
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(zip_error)?;
+    zip.write_all(redact(content).as_bytes())?;
+    Ok(())
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, LauncherError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to serialize bundle file: {e}")))
+}
+
+fn zip_error(e: zip::result::ZipError) -> LauncherError {
+    LauncherError::IoError(format!("Failed to write support bundle: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{CheckStatus, Doctor};
+    use crate::stores::epic::EpicPaths;
+    use crate::stores::gog::GogPaths;
+    use crate::stores::steam::SteamPaths;
+    use std::io::Read;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_bundle_contents_are_redacted() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(&temp.path().join("data")).unwrap();
+        fs::write(
+            temp.path().join("data/local_games.json"),
+            r#"{"games": [{"args": "token=s3cr3t-launch-token"}]}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp.path().join("data/logs")).unwrap();
+        fs::write(
+            temp.path().join("data/logs/tenfoot.log"),
+            "2026-10-15T00:00:00.000Z INFO  tenfoot_lib: GET /?key=0123456789ABCDEF\n",
+        )
+        .unwrap();
+        let doctor = Doctor::with_paths(
+            SteamPaths {
+                steam_path: None,
+                steam_exe: None,
+            },
+            EpicPaths {
+                launcher_path: None,
+                launcher_exe: None,
+                manifests_path: None,
+            },
+            GogPaths {
+                galaxy_path: None,
+                galaxy_exe: None,
+                database_path: None,
+            },
+        );
+        let mut report = doctor.run(&storage, Vec::new());
+        report.checks[0].detail = "GET /?key=0123456789ABCDEF".to_string();
+        assert_eq!(report.checks[0].status, CheckStatus::Ok);
+
+        let path = temp.path().join("bundle.zip");
+        write_support_bundle(&path, &report, &storage).unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "data/local_games.json",
+                "logs/tenfoot.log",
+                "report.json",
+                "report.txt",
+                "settings.json"
+            ]
+        );
+
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+        let text = read("report.txt");
+        assert!(text.contains("key=[REDACTED]"));
+        assert!(!text.contains("0123456789ABCDEF"));
+        assert!(!read("data/local_games.json").contains("s3cr3t-launch-token"));
+        assert!(!read("logs/tenfoot.log").contains("0123456789ABCDEF"));
+    }
+}
@@ -0,0 +1,769 @@
+//! The "doctor": checks of everything TenFoot depends on (its data dir and
+//! files, the store clients' files, the tools used to launch games) for
+//! troubleshooting, and support bundles to attach to bug reports.
+
+mod bundle;
+
+pub use bundle::write_support_bundle;
+
+use crate::launcher_core::StoreStatus;
+use crate::storage::{files, Storage};
+use crate::stores::epic::{parse_manifest_file, EpicPaths};
+use crate::stores::gog::{missing_tables, GogPaths};
+use crate::stores::steam::{listed_library_folders, SteamPaths};
+use crate::vault::{MACHINE_KEY_FILE, VAULT_FILE};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How a check went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but something looks off
+    Warning,
+    /// Something TenFoot needs is broken
+    Error,
+    /// Doesn't apply here (e.g. the store isn't installed)
+    Skipped,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warn",
+            CheckStatus::Error => "FAIL",
+            CheckStatus::Skipped => "skip",
+        }
+    }
+}
+
+/// One thing the doctor looked at
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Check {
+    /// What part of the setup it belongs to ("Data", "Steam", ...)
+    pub area: &'static str,
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(
+        area: &'static str,
+        name: impl Into<String>,
+        status: CheckStatus,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            area,
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Everything the doctor found
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    /// Unix time the checks ran
+    pub generated_at: u64,
+    pub version: String,
+    pub os: String,
+    pub checks: Vec<Check>,
+    pub stores: Vec<StoreStatus>,
+}
+
+impl DoctorReport {
+    /// The worst status among the checks
+    pub fn overall(&self) -> CheckStatus {
+        if self.has(CheckStatus::Error) {
+            CheckStatus::Error
+        } else if self.has(CheckStatus::Warning) {
+            CheckStatus::Warning
+        } else {
+            CheckStatus::Ok
+        }
+    }
+
+    fn has(&self, status: CheckStatus) -> bool {
+        self.checks.iter().any(|c| c.status == status)
+    }
+
+    /// The report as plain text, for people to read
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "TenFoot {} diagnostics ({})", self.version, self.os);
+        let _ = writeln!(text, "Generated at {} (Unix time)", self.generated_at);
+
+        let mut area = "";
+        for check in &self.checks {
+            if check.area != area {
+                area = check.area;
+                let _ = writeln!(text, "\n{area}");
+            }
+            let _ = write!(text, "  [{:>4}] {}", check.status.label(), check.name);
+            if !check.detail.is_empty() {
+                let _ = write!(text, ": {}", check.detail);
+            }
+            text.push('\n');
+        }
+
+        if !self.stores.is_empty() {
+            text.push_str("\nStores\n");
+        }
+        for store in &self.stores {
+            let _ = writeln!(text, "  {} ({})", store.display_name, store.store_id);
+            let _ = writeln!(
+                text,
+                "    available: {}, client installed: {}, client running: {}, credentials: {:?}",
+                store.available, store.client_installed, store.client_running, store.credentials
+            );
+            for path in &store.paths {
+                let shown = path
+                    .path
+                    .as_ref()
+                    .map_or("not found".to_string(), |p| p.display().to_string());
+                let missing = if path.path.is_some() && !path.exists {
+                    " (missing)"
+                } else {
+                    ""
+                };
+                let _ = writeln!(text, "    {}: {shown}{missing}", path.name);
+            }
+            match (store.last_scan_at, &store.last_error) {
+                (None, _) => text.push_str("    not scanned yet\n"),
+                (Some(at), Some(error)) => {
+                    let _ = writeln!(text, "    last scan at {at} failed: {error}");
+                }
+                (Some(at), None) => {
+                    let found = store
+                        .game_count
+                        .map_or("store unavailable".to_string(), |n| format!("{n} games"));
+                    let _ = writeln!(text, "    last scan at {at}: {found}");
+                }
+            }
+            if let Some(at) = store.last_sync_at {
+                let _ = writeln!(text, "    last library sync at {at}");
+            }
+        }
+
+        let _ = writeln!(text, "\nOverall: {}", self.overall().label());
+        text
+    }
+}
+
+/// Runs the checks against a set of store and system paths
+pub struct Doctor {
+    steam: SteamPaths,
+    epic: EpicPaths,
+    gog: GogPaths,
+    /// Where the autostart entry goes, on platforms that use a file
+    autostart_file: Option<PathBuf>,
+    /// The program protocol URLs (steam://, com.epicgames.launcher://) are
+    /// opened with, on platforms that need one
+    url_opener: Option<Result<PathBuf, String>>,
+}
+
+impl Doctor {
+    /// Check the paths detected on this machine
+    pub fn detect() -> Self {
+        Self {
+            steam: SteamPaths::detect(),
+            epic: EpicPaths::detect(),
+            gog: GogPaths::detect(),
+            autostart_file: autostart_file(),
+            url_opener: url_opener(),
+        }
+    }
+
+    /// Check custom paths (for testing)
+    pub fn with_paths(steam: SteamPaths, epic: EpicPaths, gog: GogPaths) -> Self {
+        Self {
+            steam,
+            epic,
+            gog,
+            autostart_file: None,
+            url_opener: None,
+        }
+    }
+
+    /// Run every check. `stores` is the current status of each store, which
+    /// is included as is.
+    pub fn run(&self, storage: &Storage, stores: Vec<StoreStatus>) -> DoctorReport {
+        let mut checks = Vec::new();
+        check_data_dir(storage.data_dir(), &mut checks);
+        check_storage(storage, &mut checks);
+        self.check_steam(&mut checks);
+        self.check_epic(&mut checks);
+        self.check_gog(&mut checks);
+        self.check_system(storage, &mut checks);
+
+        DoctorReport {
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            checks,
+            stores,
+        }
+    }
+
+    fn check_steam(&self, checks: &mut Vec<Check>) {
+        const AREA: &str = "Steam";
+        let Some(steam_path) = &self.steam.steam_path else {
+            checks.push(Check::new(
+                AREA,
+                "Installation",
+                CheckStatus::Skipped,
+                "Steam not found",
+            ));
+            return;
+        };
+        checks.push(Check::new(
+            AREA,
+            "Installation",
+            CheckStatus::Ok,
+            steam_path.display().to_string(),
+        ));
+
+        let vdf = steam_path.join("steamapps").join("libraryfolders.vdf");
+        if !vdf.exists() {
+            checks.push(Check::new(
+                AREA,
+                "libraryfolders.vdf",
+                CheckStatus::Warning,
+                format!(
+                    "{} is missing; only the main library is scanned",
+                    vdf.display()
+                ),
+            ));
+            return;
+        }
+        let folders = match listed_library_folders(&vdf) {
+            Ok(folders) => folders,
+            Err(e) => {
+                checks.push(Check::new(
+                    AREA,
+                    "libraryfolders.vdf",
+                    CheckStatus::Error,
+                    e.to_string(),
+                ));
+                return;
+            }
+        };
+        checks.push(Check::new(
+            AREA,
+            "libraryfolders.vdf",
+            CheckStatus::Ok,
+            format!("{} libraries listed", folders.len()),
+        ));
+
+        for folder in folders {
+            let name = format!("Library {}", folder.display());
+            let steamapps = folder.join("steamapps");
+            let check = if !folder.exists() {
+                Check::new(
+                    AREA,
+                    name,
+                    CheckStatus::Warning,
+                    "listed but not found (a removed drive or deleted library?)",
+                )
+            } else if !steamapps.is_dir() {
+                Check::new(AREA, name, CheckStatus::Warning, "has no steamapps folder")
+            } else {
+                let manifests = count_files(&steamapps, |file| {
+                    file.starts_with("appmanifest_") && file.ends_with(".acf")
+                });
+                Check::new(
+                    AREA,
+                    name,
+                    CheckStatus::Ok,
+                    format!("{manifests} app manifests"),
+                )
+            };
+            checks.push(check);
+        }
+    }
+
+    fn check_epic(&self, checks: &mut Vec<Check>) {
+        const AREA: &str = "Epic";
+        let Some(manifests) = &self.epic.manifests_path else {
+            checks.push(Check::new(
+                AREA,
+                "Manifests",
+                CheckStatus::Skipped,
+                "Epic Games Launcher not found",
+            ));
+            return;
+        };
+        let Ok(entries) = fs::read_dir(manifests) else {
+            checks.push(Check::new(
+                AREA,
+                "Manifests",
+                CheckStatus::Warning,
+                format!("{} can't be read", manifests.display()),
+            ));
+            return;
+        };
+
+        let mut parsed = 0;
+        let mut failures = Vec::new();
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "item"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            match parse_manifest_file(&path) {
+                Ok(_) => parsed += 1,
+                Err(e) => failures.push(Check::new(
+                    AREA,
+                    format!("Manifest {}", file_name(&path)),
+                    CheckStatus::Warning,
+                    format!("not parsed, the game won't show up: {e}"),
+                )),
+            }
+        }
+        checks.push(Check::new(
+            AREA,
+            "Manifests",
+            CheckStatus::Ok,
+            format!("{parsed} parsed in {}", manifests.display()),
+        ));
+        checks.extend(failures);
+    }
+
+    fn check_gog(&self, checks: &mut Vec<Check>) {
+        const AREA: &str = "GOG";
+        let Some(database) = &self.gog.database_path else {
+            checks.push(Check::new(
+                AREA,
+                "Galaxy database",
+                CheckStatus::Skipped,
+                "GOG Galaxy not found",
+            ));
+            return;
+        };
+        if !database.exists() {
+            checks.push(Check::new(
+                AREA,
+                "Galaxy database",
+                CheckStatus::Warning,
+                format!("{} doesn't exist yet", database.display()),
+            ));
+            return;
+        }
+
+        let check = match missing_tables(database) {
+            Err(e) => Check::new(AREA, "Galaxy database", CheckStatus::Error, e.to_string()),
+            Ok(missing) if missing.is_empty() => Check::new(
+                AREA,
+                "Galaxy database",
+                CheckStatus::Ok,
+                database.display().to_string(),
+            ),
+            Ok(missing) => {
+                // Either table lists installed games; without both none are found
+                let status = if missing.contains(&"InstalledBaseProducts")
+                    && missing.contains(&"LibraryReleases")
+                {
+                    CheckStatus::Error
+                } else {
+                    CheckStatus::Warning
+                };
+                Check::new(
+                    AREA,
+                    "Galaxy database",
+                    status,
+                    format!("missing tables: {}", missing.join(", ")),
+                )
+            }
+        };
+        checks.push(check);
+    }
+
+    fn check_system(&self, storage: &Storage, checks: &mut Vec<Check>) {
+        const AREA: &str = "System";
+        match &self.url_opener {
+            Some(Ok(path)) => checks.push(Check::new(
+                AREA,
+                "Protocol launches",
+                CheckStatus::Ok,
+                path.display().to_string(),
+            )),
+            Some(Err(e)) => checks.push(Check::new(
+                AREA,
+                "Protocol launches",
+                CheckStatus::Error,
+                format!("xdg-open not found, store games can't be launched: {e}"),
+            )),
+            None => {}
+        }
+
+        if let Some(file) = &self.autostart_file {
+            let enabled = storage
+                .load_settings()
+                .map(|s| s.launch_on_startup)
+                .unwrap_or(false);
+            checks.push(check_autostart(file, enabled));
+        }
+    }
+}
+
+/// The data dir is there, writable, and not open to other users
+fn check_data_dir(data_dir: &Path, checks: &mut Vec<Check>) {
+    const AREA: &str = "Data";
+    let name = "Data directory";
+    if !data_dir.is_dir() {
+        checks.push(Check::new(
+            AREA,
+            name,
+            CheckStatus::Error,
+            format!("{} doesn't exist", data_dir.display()),
+        ));
+        return;
+    }
+
+    let probe = data_dir.join(".doctor-probe");
+    let writable = files::write_atomic(&probe, b"probe").and_then(|_| fs::remove_file(&probe));
+    if let Err(e) = writable {
+        checks.push(Check::new(
+            AREA,
+            name,
+            CheckStatus::Error,
+            format!("{} isn't writable: {e}", data_dir.display()),
+        ));
+        return;
+    }
+
+    match permission_problem(data_dir, 0o002) {
+        Some(mode) => checks.push(Check::new(
+            AREA,
+            name,
+            CheckStatus::Warning,
+            format!(
+                "{} is writable by every user (mode {mode:o})",
+                data_dir.display()
+            ),
+        )),
+        None => checks.push(Check::new(
+            AREA,
+            name,
+            CheckStatus::Ok,
+            data_dir.display().to_string(),
+        )),
+    }
+
+    for file in [VAULT_FILE, MACHINE_KEY_FILE] {
+        let path = data_dir.join(file);
+        if let Some(mode) = permission_problem(&path, 0o077) {
+            checks.push(Check::new(
+                AREA,
+                file,
+                CheckStatus::Warning,
+                format!("readable by other users (mode {mode:o})"),
+            ));
+        }
+    }
+}
+
+/// The database and every JSON data file can be read
+fn check_storage(storage: &Storage, checks: &mut Vec<Check>) {
+    const AREA: &str = "Data";
+    let check = match (storage.quick_check(), storage.schema_version()) {
+        (Ok(result), Ok(version)) if result == "ok" => Check::new(
+            AREA,
+            "Database",
+            CheckStatus::Ok,
+            format!("schema version {version}"),
+        ),
+        (Err(e), _) | (_, Err(e)) => {
+            Check::new(AREA, "Database", CheckStatus::Error, e.to_string())
+        }
+        (Ok(damage), Ok(_)) => Check::new(AREA, "Database", CheckStatus::Error, damage),
+    };
+    checks.push(check);
+
+    for (what, path) in storage.json_files() {
+        let name = file_name(&path);
+        let check = match fs::read(&path) {
+            Err(_) if !path.exists() => {
+                Check::new(AREA, name, CheckStatus::Ok, format!("no {what} saved yet"))
+            }
+            Err(e) => Check::new(
+                AREA,
+                name,
+                CheckStatus::Error,
+                format!("can't be read: {e}"),
+            ),
+            Ok(content) => match serde_json::from_slice::<serde_json::Value>(&content) {
+                Ok(_) => Check::new(AREA, name, CheckStatus::Ok, ""),
+                Err(e) => Check::new(
+                    AREA,
+                    name,
+                    CheckStatus::Error,
+                    format!("doesn't parse ({e}); the last good backup will be restored"),
+                ),
+            },
+        };
+        checks.push(check);
+    }
+}
+
+/// The autostart entry matches the setting and points at an existing binary
+fn check_autostart(file: &Path, enabled: bool) -> Check {
+    const AREA: &str = "System";
+    let name = "Autostart";
+    let exec = fs::read_to_string(file).ok().map(|content| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("Exec="))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    });
+
+    match (enabled, exec) {
+        (false, None) => Check::new(AREA, name, CheckStatus::Ok, "off"),
+        (true, None) => Check::new(
+            AREA,
+            name,
+            CheckStatus::Warning,
+            format!("turned on in settings, but {} is missing", file.display()),
+        ),
+        (false, Some(_)) => Check::new(
+            AREA,
+            name,
+            CheckStatus::Warning,
+            format!(
+                "turned off in settings, but {} still exists",
+                file.display()
+            ),
+        ),
+        (true, Some(exec)) if !Path::new(&exec).exists() => Check::new(
+            AREA,
+            name,
+            CheckStatus::Error,
+            format!("{} starts {exec}, which doesn't exist", file.display()),
+        ),
+        (true, Some(exec)) => Check::new(AREA, name, CheckStatus::Ok, exec),
+    }
+}
+
+/// The autostart entry written by the launch-on-startup setting
+#[cfg(target_os = "linux")]
+pub fn autostart_file() -> Option<PathBuf> {
+    Some(
+        dirs::config_dir()?
+            .join("autostart")
+            .join("tenfoot.desktop"),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn autostart_file() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn url_opener() -> Option<Result<PathBuf, String>> {
+    Some(which::which("xdg-open").map_err(|e| e.to_string()))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn url_opener() -> Option<Result<PathBuf, String>> {
+    None
+}
+
+/// The permission bits of `mask` that are set on a file, if any
+#[cfg(unix)]
+fn permission_problem(path: &Path, mask: u32) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path).ok()?.permissions().mode() & 0o777;
+    (mode & mask != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+fn permission_problem(_path: &Path, _mask: u32) -> Option<u32> {
+    None
+}
+
+fn count_files(dir: &Path, matches: impl Fn(&str) -> bool) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_str().is_some_and(&matches))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn no_stores() -> Doctor {
+        Doctor::with_paths(
+            SteamPaths {
+                steam_path: None,
+                steam_exe: None,
+            },
+            EpicPaths {
+                launcher_path: None,
+                launcher_exe: None,
+                manifests_path: None,
+            },
+            GogPaths {
+                galaxy_path: None,
+                galaxy_exe: None,
+                database_path: None,
+            },
+        )
+    }
+
+    fn find<'a>(report: &'a DoctorReport, name: &str) -> &'a Check {
+        report
+            .checks
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("no check named {name}"))
+    }
+
+    #[test]
+    fn test_healthy_data_dir() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(temp.path()).unwrap();
+
+        let report = no_stores().run(&storage, Vec::new());
+
+        assert_eq!(find(&report, "Data directory").status, CheckStatus::Ok);
+        assert_eq!(find(&report, "Database").status, CheckStatus::Ok);
+        assert_eq!(find(&report, "collections.json").status, CheckStatus::Ok);
+        assert_eq!(find(&report, "Installation").status, CheckStatus::Skipped);
+        assert_eq!(report.overall(), CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_corrupt_json_file() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(temp.path()).unwrap();
+        fs::write(temp.path().join("game_links.json"), "{\"links\": [").unwrap();
+
+        let report = no_stores().run(&storage, Vec::new());
+
+        assert_eq!(find(&report, "game_links.json").status, CheckStatus::Error);
+        assert_eq!(report.overall(), CheckStatus::Error);
+        assert!(report
+            .to_text()
+            .contains("[FAIL] game_links.json: doesn't parse"));
+    }
+
+    #[test]
+    fn test_store_files() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(&temp.path().join("data")).unwrap();
+
+        // Steam: one library present, one on an unplugged drive
+        let steam = temp.path().join("Steam");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::write(steam.join("steamapps/appmanifest_440.acf"), "").unwrap();
+        fs::write(
+            steam.join("steamapps/libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\"\n{{\n\"path\" \"{}\"\n\"path\" \"/unplugged/Steam\"\n}}\n",
+                steam.display()
+            ),
+        )
+        .unwrap();
+
+        // Epic: a good and a broken manifest
+        let manifests = temp.path().join("Manifests");
+        fs::create_dir_all(&manifests).unwrap();
+        fs::write(
+            manifests.join("good.item"),
+            r#"{"AppName": "Fortnite", "DisplayName": "Fortnite", "InstallLocation": "/games/fn", "LaunchExecutable": "fn.exe"}"#,
+        )
+        .unwrap();
+        fs::write(manifests.join("broken.item"), "{").unwrap();
+
+        // GOG: a database from an unknown Galaxy version
+        let database = temp.path().join("galaxy-2.0.db");
+        rusqlite::Connection::open(&database)
+            .unwrap()
+            .execute("CREATE TABLE Other (id INTEGER)", [])
+            .unwrap();
+
+        let doctor = Doctor::with_paths(
+            SteamPaths {
+                steam_path: Some(steam.clone()),
+                steam_exe: None,
+            },
+            EpicPaths {
+                launcher_path: None,
+                launcher_exe: None,
+                manifests_path: Some(manifests),
+            },
+            GogPaths {
+                galaxy_path: None,
+                galaxy_exe: None,
+                database_path: Some(database),
+            },
+        );
+        let report = doctor.run(&storage, Vec::new());
+
+        let library = find(&report, &format!("Library {}", steam.display()));
+        assert_eq!(library.status, CheckStatus::Ok);
+        assert_eq!(library.detail, "1 app manifests");
+        assert_eq!(
+            find(&report, "Library /unplugged/Steam").status,
+            CheckStatus::Warning
+        );
+        assert_eq!(
+            find(&report, "Manifests").detail.split(' ').next(),
+            Some("1")
+        );
+        assert_eq!(
+            find(&report, "Manifest broken.item").status,
+            CheckStatus::Warning
+        );
+        let gog = find(&report, "Galaxy database");
+        assert_eq!(gog.status, CheckStatus::Error);
+        assert!(gog.detail.contains("InstalledBaseProducts"));
+    }
+
+    #[test]
+    fn test_autostart() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("tenfoot.desktop");
+
+        assert_eq!(check_autostart(&file, false).status, CheckStatus::Ok);
+        assert_eq!(check_autostart(&file, true).status, CheckStatus::Warning);
+
+        fs::write(&file, "[Desktop Entry]\nExec=/nowhere/tenfoot\n").unwrap();
+        assert_eq!(check_autostart(&file, true).status, CheckStatus::Error);
+        assert_eq!(check_autostart(&file, false).status, CheckStatus::Warning);
+
+        let exe = std::env::current_exe().unwrap();
+        fs::write(&file, format!("[Desktop Entry]\nExec={}\n", exe.display())).unwrap();
+        assert_eq!(check_autostart(&file, true).status, CheckStatus::Ok);
+    }
+}
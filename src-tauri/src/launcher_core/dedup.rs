@@ -0,0 +1,505 @@
+use crate::launcher_core::{Game, StoreType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Edition suffixes that don't distinguish one game from another
+const EDITION_SUFFIXES: &[&str] = &[
+    "game of the year edition",
+    "game of the year",
+    "goty edition",
+    "goty",
+    "definitive edition",
+    "deluxe edition",
+    "complete edition",
+    "gold edition",
+    "ultimate edition",
+    "enhanced edition",
+    "special edition",
+    "standard edition",
+    "digital edition",
+    "anniversary edition",
+    "directors cut",
+    "edition",
+];
+
+/// Roman numerals converted to digits (I is left alone, it's usually a word,
+/// and X is left alone because it's usually a name: "Mega Man X", "XCOM X")
+const ROMAN_NUMERALS: &[(&str, &str)] = &[
+    ("ii", "2"),
+    ("iii", "3"),
+    ("iv", "4"),
+    ("v", "5"),
+    ("vi", "6"),
+    ("vii", "7"),
+    ("viii", "8"),
+    ("ix", "9"),
+    ("xi", "11"),
+    ("xii", "12"),
+    ("xiii", "13"),
+    ("xiv", "14"),
+    ("xv", "15"),
+    ("xvi", "16"),
+    ("xvii", "17"),
+    ("xviii", "18"),
+    ("xix", "19"),
+    ("xx", "20"),
+];
+
+/// Normalize a game title so the same game matches across stores.
+///
+/// Lowercases, drops trademark symbols and punctuation, converts roman
+/// numerals to digits and strips trailing edition suffixes.
+pub fn normalize_title(title: &str) -> String {
+    let mut cleaned = String::with_capacity(title.len());
+    for c in title.chars() {
+        match c {
+            '™' | '®' | '©' | '\'' | '’' => {}
+            '&' => cleaned.push_str(" and "),
+            c if c.is_alphanumeric() => cleaned.extend(c.to_lowercase()),
+            _ => cleaned.push(' '),
+        }
+    }
+
+    let mut words: Vec<&str> = cleaned
+        .split_whitespace()
+        .map(|word| {
+            ROMAN_NUMERALS
+                .iter()
+                .find(|(roman, _)| *roman == word)
+                .map(|(_, digit)| *digit)
+                .unwrap_or(word)
+        })
+        .collect();
+
+    // Strip edition suffixes repeatedly ("Deluxe Edition - GOTY")
+    loop {
+        let before = words.len();
+        for suffix in EDITION_SUFFIXES {
+            let suffix_words: Vec<&str> = suffix.split(' ').collect();
+            if words.len() > suffix_words.len() && words.ends_with(&suffix_words) {
+                words.truncate(words.len() - suffix_words.len());
+                // "The Complete Edition"
+                if words.len() > 1 && words.last() == Some(&"the") {
+                    words.pop();
+                }
+                break;
+            }
+        }
+        if words.len() == before {
+            break;
+        }
+    }
+
+    words.join(" ")
+}
+
+/// Manual overrides for cross-store grouping, persisted in storage
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct GameLinks {
+    /// Groups of game keys the user linked together
+    #[serde(default)]
+    pub linked: Vec<Vec<String>>,
+    /// Game keys the user split out; they are never grouped automatically
+    #[serde(default)]
+    pub unlinked: Vec<String>,
+    /// Game keys chosen as the launch source within their group
+    #[serde(default)]
+    pub preferred: Vec<String>,
+}
+
+impl GameLinks {
+    /// Link game keys into one group, merging any existing groups they belong to
+    pub fn link(&mut self, keys: &[String]) {
+        let mut group: Vec<String> = Vec::new();
+        self.linked.retain(|existing| {
+            if existing.iter().any(|k| keys.contains(k)) {
+                group.extend(existing.iter().cloned());
+                false
+            } else {
+                true
+            }
+        });
+
+        for key in keys {
+            if !group.contains(key) {
+                group.push(key.clone());
+            }
+        }
+        self.unlinked.retain(|k| !keys.contains(k));
+
+        if group.len() > 1 {
+            self.linked.push(group);
+        }
+    }
+
+    /// Split a game out of whatever group it's in
+    pub fn unlink(&mut self, key: &str) {
+        for group in &mut self.linked {
+            group.retain(|k| k != key);
+        }
+        self.linked.retain(|group| group.len() > 1);
+        self.preferred.retain(|k| k != key);
+
+        if !self.unlinked.iter().any(|k| k == key) {
+            self.unlinked.push(key.to_string());
+        }
+    }
+
+    /// Pick which source to launch a group from
+    pub fn set_preferred(&mut self, key: &str, group_keys: &[String]) {
+        self.preferred.retain(|k| !group_keys.contains(k));
+        self.preferred.push(key.to_string());
+    }
+
+    fn is_unlinked(&self, key: &str) -> bool {
+        self.unlinked.iter().any(|k| k == key)
+    }
+}
+
+/// A logical game that may be owned on several stores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnifiedGame {
+    /// Unique key of the preferred source (used to launch)
+    pub key: String,
+    /// Display name (from the preferred source)
+    pub name: String,
+    /// Normalized title used for matching
+    pub normalized_title: String,
+    /// Whether any source is installed
+    pub installed: bool,
+    /// Store entries for this game, preferred source first
+    pub sources: Vec<Game>,
+}
+
+impl UnifiedGame {
+    /// The store entry launched by default
+    pub fn preferred_source(&self) -> &Game {
+        &self.sources[0]
+    }
+
+    /// Unique keys of all sources
+    pub fn source_keys(&self) -> Vec<String> {
+        self.sources.iter().map(|g| g.unique_key()).collect()
+    }
+
+    /// Check whether this game has an entry with the given key
+    pub fn has_source(&self, key: &str) -> bool {
+        self.sources.iter().any(|g| g.unique_key() == key)
+    }
+}
+
+/// Group store entries for the same game into unified entries, sorted by name
+pub fn group_games(games: Vec<Game>, links: &GameLinks) -> Vec<UnifiedGame> {
+    let keys: Vec<String> = games.iter().map(|g| g.unique_key()).collect();
+    let titles: Vec<String> = games.iter().map(|g| normalize_title(&g.name)).collect();
+    let index: HashMap<&str, usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (key.as_str(), i))
+        .collect();
+
+    let mut groups = DisjointSet::new(games.len());
+
+    // Automatic matches: same normalized title from different stores. Two
+    // entries from one store are different products (a demo and the full
+    // game, two local entries) so each store appears at most once per group.
+    let mut by_title: HashMap<&str, Vec<(usize, HashSet<&StoreType>)>> = HashMap::new();
+    for (i, title) in titles.iter().enumerate() {
+        if title.is_empty() || links.is_unlinked(&keys[i]) {
+            continue;
+        }
+        let store = &games[i].store;
+        let candidates = by_title.entry(title.as_str()).or_default();
+        match candidates
+            .iter_mut()
+            .find(|(_, stores)| !stores.contains(store))
+        {
+            Some((first, stores)) => {
+                stores.insert(store);
+                groups.union(*first, i);
+            }
+            None => candidates.push((i, HashSet::from([store]))),
+        }
+    }
+
+    // Manual links always apply
+    for group in &links.linked {
+        let members: Vec<usize> = group
+            .iter()
+            .filter_map(|key| index.get(key.as_str()).copied())
+            .collect();
+        for pair in members.windows(2) {
+            groups.union(pair[0], pair[1]);
+        }
+    }
+
+    let preferred: HashSet<&str> = links.preferred.iter().map(String::as_str).collect();
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..games.len() {
+        members.entry(groups.find(i)).or_default().push(i);
+    }
+
+    let mut slots: Vec<Option<Game>> = games.into_iter().map(Some).collect();
+    let mut unified: Vec<UnifiedGame> = members
+        .into_values()
+        .map(|mut indices| {
+            indices.sort_by(|&a, &b| {
+                let a_game = slots[a].as_ref().expect("game used once");
+                let b_game = slots[b].as_ref().expect("game used once");
+                preferred
+                    .contains(keys[b].as_str())
+                    .cmp(&preferred.contains(keys[a].as_str()))
+                    .then_with(|| source_rank(a_game).cmp(&source_rank(b_game)))
+                    .then_with(|| keys[a].cmp(&keys[b]))
+            });
+
+            let sources: Vec<Game> = indices.iter().filter_map(|&i| slots[i].take()).collect();
+            UnifiedGame {
+                key: sources[0].unique_key(),
+                name: sources[0].name.clone(),
+                normalized_title: titles[indices[0]].clone(),
+                installed: sources.iter().any(|g| g.installed),
+                sources,
+            }
+        })
+        .collect();
+
+    unified.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    unified
+}
+
+/// Default source ordering: installed first, then most recently played, then store
+fn source_rank(game: &Game) -> (bool, std::cmp::Reverse<u64>, u8) {
+    let store = match game.store {
+        StoreType::Steam => 0,
+        StoreType::Epic => 1,
+        StoreType::Gog => 2,
+        StoreType::Local => 3,
+    };
+    (
+        !game.installed,
+        std::cmp::Reverse(game.last_played.unwrap_or(0)),
+        store,
+    )
+}
+
+/// Minimal union-find over entry indices
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = i;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_b] = root_a;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(id: &str, name: &str, store: StoreType) -> Game {
+        Game::new(id, name, store)
+    }
+
+    #[test]
+    fn test_normalize_title_punctuation_and_symbols() {
+        assert_eq!(
+            normalize_title("Tom Clancy's Rainbow Six® Siege"),
+            "tom clancys rainbow six siege"
+        );
+        assert_eq!(normalize_title("DOOM™"), "doom");
+        assert_eq!(
+            normalize_title("Ratchet & Clank: Rift Apart"),
+            "ratchet and clank rift apart"
+        );
+        assert_eq!(normalize_title("  Half-Life  "), "half life");
+    }
+
+    #[test]
+    fn test_normalize_title_edition_suffixes() {
+        assert_eq!(
+            normalize_title("The Witcher 3: Wild Hunt - Game of the Year Edition"),
+            "the witcher 3 wild hunt"
+        );
+        assert_eq!(normalize_title("Control Ultimate Edition"), "control");
+        assert_eq!(
+            normalize_title("Borderlands GOTY Enhanced"),
+            "borderlands goty enhanced"
+        );
+        assert_eq!(normalize_title("Mafia: Definitive Edition"), "mafia");
+        // A title that is only a suffix is kept
+        assert_eq!(normalize_title("Edition"), "edition");
+    }
+
+    #[test]
+    fn test_normalize_title_roman_numerals() {
+        assert_eq!(normalize_title("Diablo II"), normalize_title("Diablo 2"));
+        assert_eq!(normalize_title("Final Fantasy VII"), "final fantasy 7");
+        assert_eq!(
+            normalize_title("Sid Meier's Civilization VI"),
+            "sid meiers civilization 6"
+        );
+        // X is a name far more often than a numeral
+        assert_eq!(normalize_title("Mega Man X"), "mega man x");
+        // Roman numerals inside words are untouched
+        assert_eq!(normalize_title("Vivid Knight"), "vivid knight");
+    }
+
+    #[test]
+    fn test_group_games_merges_across_stores() {
+        let games = vec![
+            game("8870", "BioShock Infinite", StoreType::Steam),
+            game(
+                "bio",
+                "BioShock Infinite: The Complete Edition",
+                StoreType::Epic,
+            ),
+            game("other", "Celeste", StoreType::Epic),
+        ];
+
+        let unified = group_games(games, &GameLinks::default());
+
+        assert_eq!(unified.len(), 2);
+        assert_eq!(unified[0].sources.len(), 2);
+        assert_eq!(unified[0].key, "steam:8870");
+        assert_eq!(unified[1].name, "Celeste");
+    }
+
+    #[test]
+    fn test_group_games_keeps_same_store_entries_apart() {
+        let games = vec![
+            game("1145360", "Hades", StoreType::Steam),
+            game("1145350", "Hades", StoreType::Steam),
+            game("fn", "Hades", StoreType::Epic),
+            game("a", "Emulator", StoreType::Local),
+            game("b", "Emulator", StoreType::Local),
+        ];
+
+        let unified = group_games(games, &GameLinks::default());
+
+        assert_eq!(unified.len(), 4);
+        let emulators: Vec<_> = unified.iter().filter(|u| u.name == "Emulator").collect();
+        assert_eq!(emulators.len(), 2);
+        assert!(emulators.iter().all(|u| u.sources.len() == 1));
+        let hades: Vec<_> = unified.iter().filter(|u| u.name == "Hades").collect();
+        assert_eq!(hades.len(), 2);
+        assert!(hades.iter().all(|u| u
+            .sources
+            .iter()
+            .filter(|g| g.store == StoreType::Steam)
+            .count()
+            == 1));
+    }
+
+    #[test]
+    fn test_group_games_prefers_installed_source() {
+        let mut epic = game("fn", "Hades", StoreType::Epic);
+        epic.installed = true;
+        let games = vec![game("1145360", "Hades", StoreType::Steam), epic];
+
+        let unified = group_games(games, &GameLinks::default());
+
+        assert_eq!(unified.len(), 1);
+        assert_eq!(unified[0].key, "epic:fn");
+        assert!(unified[0].installed);
+        assert_eq!(unified[0].preferred_source().store, StoreType::Epic);
+    }
+
+    #[test]
+    fn test_group_games_respects_preferred_source() {
+        let mut steam = game("1145360", "Hades", StoreType::Steam);
+        steam.installed = true;
+        let games = vec![steam, game("fn", "Hades", StoreType::Epic)];
+        let links = GameLinks {
+            preferred: vec!["epic:fn".to_string()],
+            ..Default::default()
+        };
+
+        let unified = group_games(games, &links);
+
+        assert_eq!(unified[0].key, "epic:fn");
+        assert_eq!(unified[0].source_keys(), vec!["epic:fn", "steam:1145360"]);
+    }
+
+    #[test]
+    fn test_manual_link_and_unlink() {
+        let games = vec![
+            game("1", "Shadow Warrior Classic", StoreType::Steam),
+            game("sw", "Shadow Warrior (1997)", StoreType::Gog),
+            game("2", "Hades", StoreType::Steam),
+            game("h", "Hades", StoreType::Epic),
+        ];
+        let mut links = GameLinks::default();
+        links.link(&["steam:1".to_string(), "gog:sw".to_string()]);
+        links.unlink("epic:h");
+
+        let unified = group_games(games, &links);
+
+        assert_eq!(unified.len(), 3);
+        let shadow = unified.iter().find(|u| u.has_source("gog:sw")).unwrap();
+        assert!(shadow.has_source("steam:1"));
+        let hades: Vec<_> = unified.iter().filter(|u| u.name == "Hades").collect();
+        assert_eq!(hades.len(), 2);
+    }
+
+    #[test]
+    fn test_links_link_merges_groups_and_clears_unlinked() {
+        let mut links = GameLinks::default();
+        links.unlink("epic:a");
+        links.link(&["steam:1".to_string(), "epic:a".to_string()]);
+        links.link(&["epic:a".to_string(), "gog:x".to_string()]);
+
+        assert!(links.unlinked.is_empty());
+        assert_eq!(links.linked.len(), 1);
+        assert_eq!(links.linked[0].len(), 3);
+
+        links.unlink("gog:x");
+        assert_eq!(links.linked[0], vec!["steam:1", "epic:a"]);
+        assert_eq!(links.unlinked, vec!["gog:x"]);
+    }
+
+    #[test]
+    fn test_links_set_preferred_replaces_group_choice() {
+        let group = vec!["steam:1".to_string(), "epic:a".to_string()];
+        let mut links = GameLinks::default();
+        links.set_preferred("steam:1", &group);
+        links.set_preferred("epic:a", &group);
+
+        assert_eq!(links.preferred, vec!["epic:a"]);
+    }
+
+    #[test]
+    fn test_game_links_serialization_defaults() {
+        let links: GameLinks = serde_json::from_str("{}").unwrap();
+        assert_eq!(links, GameLinks::default());
+    }
+}
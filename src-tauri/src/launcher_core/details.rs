@@ -0,0 +1,41 @@
+use crate::launcher_core::{LauncherError, StoreType};
+use serde::{Deserialize, Serialize};
+
+/// Descriptive details about a game, the same shape for every store
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDetails {
+    pub description: Option<String>,
+    pub developers: Option<Vec<String>>,
+    pub publishers: Option<Vec<String>>,
+    pub genres: Option<Vec<String>>,
+    pub platforms: Option<Vec<String>>,
+    pub release_date: Option<String>,
+}
+
+/// A source of game details for one store
+pub trait DetailsProvider: Send + Sync {
+    /// The store whose games this provider can describe
+    fn store(&self) -> StoreType;
+
+    /// Fetch details for a store-specific game ID (None if the store has none)
+    fn fetch_details(&self, game_id: &str) -> Result<Option<GameDetails>, LauncherError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_details_serialize_camel_case() {
+        let details = GameDetails {
+            release_date: Some("2020".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&details).unwrap();
+        assert!(json.contains("\"releaseDate\":\"2020\""));
+
+        let parsed: GameDetails = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, details);
+    }
+}
@@ -1,42 +1,240 @@
+use super::redact::redact;
+use super::StoreType;
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
 pub enum LauncherError {
-    #[error("Store not found: {0}")]
+    #[error("Store not found: {}", redact(.0))]
     StoreNotFound(String),
 
-    #[error("Game not found: {0}")]
+    #[error("Game not found: {}", redact(.0))]
     GameNotFound(String),
 
-    #[error("Failed to parse configuration: {0}")]
+    #[error("Failed to parse configuration: {}", redact(.0))]
     ParseError(String),
 
-    #[error("IO error: {0}")]
+    #[error("IO error: {}", redact(.0))]
     IoError(String),
 
-    #[error("HTTP request failed: {0}")]
+    #[error("HTTP request failed: {}", redact(.0))]
     HttpError(String),
 
-    #[error("JSON parsing failed: {0}")]
+    #[error("JSON parsing failed: {}", redact(.0))]
     JsonError(String),
 
-    #[error("Database error: {0}")]
+    #[error("Database error: {}", redact(.0))]
     DatabaseError(String),
 
-    #[error("Game launch failed: {0}")]
+    #[error("Game launch failed: {}", redact(.0))]
     LaunchError(String),
 
-    #[error("Authentication required for {0}")]
+    #[error("Authentication required for {}", redact(.0))]
     AuthRequired(String),
 
-    #[error("Platform not supported: {0}")]
+    #[error("Platform not supported: {}", redact(.0))]
     PlatformNotSupported(String),
 
-    #[error("Network error: {0}")]
+    #[error("Network error: {}", redact(.0))]
     NetworkError(String),
 
-    #[error("Configuration error: {0}")]
+    #[error("Configuration error: {}", redact(.0))]
     ConfigError(String),
+
+    #[error("Request timed out: {}", redact(.0))]
+    Timeout(String),
+
+    #[error("Too many requests to {}; try again later", redact(.0))]
+    RateLimited(String),
+
+    #[error("{} profile is private", redact(.0))]
+    PrivateProfile(String),
+
+    #[error("{} is not connected", redact(.0))]
+    NotConnected(String),
+}
+
+impl LauncherError {
+    /// A request that failed before a response came back, with timeouts told
+    /// apart from other network errors
+    pub fn from_request(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            LauncherError::Timeout(err.to_string())
+        } else {
+            LauncherError::NetworkError(err.to_string())
+        }
+    }
+
+    /// An unsuccessful HTTP status from a service
+    pub fn from_status(service: &str, status: reqwest::StatusCode, body: &str) -> Self {
+        match status.as_u16() {
+            401 | 403 => LauncherError::AuthRequired(service.to_string()),
+            429 => LauncherError::RateLimited(service.to_string()),
+            408 | 504 => LauncherError::Timeout(format!("{service} ({status})")),
+            _ if body.is_empty() => {
+                LauncherError::HttpError(format!("{service} returned {status}"))
+            }
+            _ => LauncherError::HttpError(format!("{service} returned {status}: {body}")),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LauncherError::StoreNotFound(_) => ErrorCode::StoreNotFound,
+            LauncherError::GameNotFound(_) => ErrorCode::GameNotFound,
+            LauncherError::ParseError(_) => ErrorCode::ParseError,
+            LauncherError::IoError(_) => ErrorCode::IoError,
+            LauncherError::HttpError(_) => ErrorCode::HttpError,
+            LauncherError::JsonError(_) => ErrorCode::JsonError,
+            LauncherError::DatabaseError(_) => ErrorCode::DatabaseError,
+            LauncherError::LaunchError(_) => ErrorCode::LaunchError,
+            LauncherError::AuthRequired(_) => ErrorCode::AuthRequired,
+            LauncherError::PlatformNotSupported(_) => ErrorCode::PlatformNotSupported,
+            LauncherError::NetworkError(_) => ErrorCode::NetworkError,
+            LauncherError::ConfigError(_) => ErrorCode::ConfigError,
+            LauncherError::Timeout(_) => ErrorCode::Timeout,
+            LauncherError::RateLimited(_) => ErrorCode::RateLimited,
+            LauncherError::PrivateProfile(_) => ErrorCode::PrivateProfile,
+            LauncherError::NotConnected(_) => ErrorCode::NotConnected,
+        }
+    }
+
+    /// Whether the same call could succeed if simply made again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            LauncherError::NetworkError(_)
+                | LauncherError::HttpError(_)
+                | LauncherError::Timeout(_)
+                | LauncherError::RateLimited(_)
+        )
+    }
+}
+
+/// Stable identifiers for what went wrong, for the frontend to match on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    StoreNotFound,
+    GameNotFound,
+    ParseError,
+    IoError,
+    HttpError,
+    JsonError,
+    DatabaseError,
+    LaunchError,
+    AuthRequired,
+    PlatformNotSupported,
+    NetworkError,
+    ConfigError,
+    Timeout,
+    RateLimited,
+    PrivateProfile,
+    NotConnected,
+    /// The command was called with arguments it can't act on
+    InvalidInput,
+    /// A failure inside the app itself (a poisoned lock, a lost task)
+    Internal,
+}
+
+/// The error every Tauri command returns: a code to branch on, the store it
+/// concerns (if any), whether retrying may help, a message to show, and
+/// technical details for logs and bug reports. Both texts are redacted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub store: Option<StoreType>,
+    pub retryable: bool,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            store: None,
+            retryable: false,
+            message: redact(&message.into()),
+            details: None,
+        }
+    }
+
+    /// Bad arguments from the caller
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn internal(details: impl std::fmt::Display) -> Self {
+        Self {
+            details: Some(redact(&details.to_string())),
+            ..Self::new(ErrorCode::Internal, "Something went wrong inside TenFoot")
+        }
+    }
+
+    /// Mark the error as concerning a store
+    pub fn for_store(mut self, store: StoreType) -> Self {
+        self.store = Some(store);
+        self
+    }
+}
+
+impl From<LauncherError> for CommandError {
+    fn from(err: LauncherError) -> Self {
+        // Low-level failures get a plain summary, with the specifics (paths,
+        // response bodies, driver messages) kept as details
+        let summary = match &err {
+            LauncherError::ParseError(_) | LauncherError::JsonError(_) => {
+                Some("Some data couldn't be read")
+            }
+            LauncherError::IoError(_) => Some("A file couldn't be read or written"),
+            LauncherError::HttpError(_) => Some("The server returned an error"),
+            LauncherError::DatabaseError(_) => Some("The library database couldn't be used"),
+            LauncherError::NetworkError(_) => Some("Couldn't connect; check your connection"),
+            _ => None,
+        };
+        let (message, details) = match summary {
+            Some(summary) => (summary.to_string(), Some(err.to_string())),
+            None => (err.to_string(), None),
+        };
+
+        Self {
+            code: err.code(),
+            store: None,
+            retryable: err.is_retryable(),
+            message,
+            details,
+        }
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for CommandError {
+    fn from(err: std::sync::PoisonError<T>) -> Self {
+        Self::internal(err)
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.details {
+            Some(details) => write!(f, "{} ({details})", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Attach the store a failure concerns while converting it for a command
+pub trait StoreResultExt<T> {
+    fn for_store(self, store: StoreType) -> Result<T, CommandError>;
+}
+
+impl<T, E: Into<CommandError>> StoreResultExt<T> for Result<T, E> {
+    fn for_store(self, store: StoreType) -> Result<T, CommandError> {
+        self.map_err(|e| e.into().for_store(store))
+    }
 }
 
 impl From<std::io::Error> for LauncherError {
@@ -47,7 +245,13 @@ impl From<std::io::Error> for LauncherError {
 
 impl From<reqwest::Error> for LauncherError {
     fn from(err: reqwest::Error) -> Self {
-        LauncherError::HttpError(err.to_string())
+        // Bad statuses and bodies came back from the service; anything else
+        // never got a response
+        if err.is_status() || err.is_decode() {
+            LauncherError::HttpError(err.to_string())
+        } else {
+            LauncherError::from_request(err)
+        }
     }
 }
 
@@ -63,12 +267,13 @@ impl From<rusqlite::Error> for LauncherError {
     }
 }
 
-impl serde::Serialize for LauncherError {
+/// Serialized the way commands report it, as a `CommandError`
+impl Serialize for LauncherError {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        CommandError::from(self.clone()).serialize(serializer)
     }
 }
 
@@ -156,8 +361,107 @@ mod tests {
     #[test]
     fn test_error_serialize() {
         let err = LauncherError::LaunchError("failed".to_string());
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "launch_error",
+                "store": null,
+                "retryable": false,
+                "message": "Game launch failed: failed",
+                "details": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_command_error_from_launcher_error() {
+        let err = CommandError::from(LauncherError::RateLimited("Steam".to_string()));
+        assert_eq!(err.code, ErrorCode::RateLimited);
+        assert!(err.retryable);
+        assert_eq!(err.message, "Too many requests to Steam; try again later");
+        assert_eq!(err.details, None);
+
+        // Low-level errors keep their specifics out of the message
+        let err = CommandError::from(LauncherError::NetworkError(
+            "dns error for https://api.example.com/?key=12345678".to_string(),
+        ));
+        assert_eq!(err.code, ErrorCode::NetworkError);
+        assert!(err.retryable);
+        assert_eq!(err.message, "Couldn't connect; check your connection");
+        let details = err.details.unwrap();
+        assert!(details.contains("dns error"));
+        assert!(!details.contains("12345678"));
+
+        let err = CommandError::from(LauncherError::GameNotFound("steam:1".to_string()));
+        assert!(!err.retryable);
+    }
+
+    #[test]
+    fn test_command_error_store() {
+        let result: Result<(), LauncherError> =
+            Err(LauncherError::PrivateProfile("Steam".to_string()));
+        let err = result.for_store(StoreType::Steam).unwrap_err();
+        assert_eq!(err.code, ErrorCode::PrivateProfile);
+        assert_eq!(err.store, Some(StoreType::Steam));
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["store"],
+            serde_json::json!("steam")
+        );
+    }
+
+    #[test]
+    fn test_from_status() {
+        use reqwest::StatusCode;
+
+        assert!(matches!(
+            LauncherError::from_status("Epic", StatusCode::UNAUTHORIZED, ""),
+            LauncherError::AuthRequired(_)
+        ));
+        assert!(matches!(
+            LauncherError::from_status("Epic", StatusCode::TOO_MANY_REQUESTS, ""),
+            LauncherError::RateLimited(_)
+        ));
+        assert!(matches!(
+            LauncherError::from_status("Epic", StatusCode::GATEWAY_TIMEOUT, ""),
+            LauncherError::Timeout(_)
+        ));
+        assert_eq!(
+            LauncherError::from_status("Epic", StatusCode::BAD_GATEWAY, "oops").to_string(),
+            "HTTP request failed: Epic returned 502 Bad Gateway: oops"
+        );
+    }
+
+    #[test]
+    fn test_from_reqwest_connect_failure_is_network_error() {
+        // Nothing listens on the port once the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let err = reqwest::blocking::get(format!("http://127.0.0.1:{port}/")).unwrap_err();
+
+        assert!(matches!(
+            LauncherError::from(err),
+            LauncherError::NetworkError(_)
+        ));
+    }
+
+    #[test]
+    fn test_messages_are_redacted() {
+        let err = LauncherError::NetworkError(
+            "error sending request for url (https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/?key=ABCDEF0123456789&steamids=7)".to_string(),
+        );
+        let message = err.to_string();
+        assert!(!message.contains("ABCDEF0123456789"));
+        assert!(message.contains("?key=[REDACTED]&steamids=7"));
+
+        let err = LauncherError::AuthRequired(
+            r#"Token refresh failed (400 Bad Request): {"refresh_token":"eg1~secret"}"#.to_string(),
+        );
         let json = serde_json::to_string(&err).unwrap();
-        assert_eq!(json, "\"Game launch failed: failed\"");
+        assert!(!json.contains("eg1~secret"));
     }
 
     #[test]
@@ -177,6 +481,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
     fn test_result_type_ok() {
         let result: std::result::Result<i32, LauncherError> = Ok(42);
         assert!(result.is_ok());
@@ -1,3 +1,4 @@
+use crate::launcher_core::store::ArtworkType;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -8,6 +9,8 @@ pub enum StoreType {
     Steam,
     Epic,
     Gog,
+    /// Games added manually by the user (DRM-free, emulators, itch.io, ...)
+    Local,
 }
 
 impl std::fmt::Display for StoreType {
@@ -16,12 +19,13 @@ impl std::fmt::Display for StoreType {
             StoreType::Steam => write!(f, "Steam"),
             StoreType::Epic => write!(f, "Epic"),
             StoreType::Gog => write!(f, "GOG"),
+            StoreType::Local => write!(f, "Local"),
         }
     }
 }
 
 /// Unified game representation across all stores
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Game {
     /// Unique identifier within the store (AppID for Steam, AppName for Epic, etc.)
     pub id: String,
@@ -63,6 +67,10 @@ pub struct Game {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
 
+    /// URL or path to the game's logo (transparent title art)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+
     /// Size on disk in bytes (if installed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size_bytes: Option<u64>,
@@ -91,6 +99,7 @@ impl Game {
             cover_url: None,
             hero_url: None,
             icon_url: None,
+            logo_url: None,
             size_bytes: None,
             version: None,
             installed_at: None,
@@ -102,6 +111,27 @@ impl Game {
         format!("{}:{}", self.store.to_string().to_lowercase(), self.id)
     }
 
+    /// Remote URL for a kind of artwork, if the store provides one
+    pub fn artwork_url(&self, art_type: ArtworkType) -> Option<&str> {
+        match art_type {
+            ArtworkType::Cover => self.cover_url.as_deref(),
+            ArtworkType::Hero => self.hero_url.as_deref(),
+            ArtworkType::Icon => self.icon_url.as_deref(),
+            ArtworkType::Logo => self.logo_url.as_deref(),
+        }
+    }
+
+    /// Replace the URL for a kind of artwork
+    pub fn set_artwork_url(&mut self, art_type: ArtworkType, url: impl Into<String>) {
+        let url = Some(url.into());
+        match art_type {
+            ArtworkType::Cover => self.cover_url = url,
+            ArtworkType::Hero => self.hero_url = url,
+            ArtworkType::Icon => self.icon_url = url,
+            ArtworkType::Logo => self.logo_url = url,
+        }
+    }
+
     /// Check if this game can be launched
     pub fn can_launch(&self) -> bool {
         self.installed
@@ -173,6 +203,16 @@ impl GameArtwork {
     pub fn has_any(&self) -> bool {
         self.cover.is_some() || self.hero.is_some() || self.logo.is_some() || self.icon.is_some()
     }
+
+    /// URL for a kind of artwork
+    pub fn get(&self, art_type: ArtworkType) -> Option<&str> {
+        match art_type {
+            ArtworkType::Cover => self.cover.as_deref(),
+            ArtworkType::Hero => self.hero.as_deref(),
+            ArtworkType::Logo => self.logo.as_deref(),
+            ArtworkType::Icon => self.icon.as_deref(),
+        }
+    }
 }
 
 impl Default for GameArtwork {
@@ -190,6 +230,7 @@ mod tests {
         assert_eq!(format!("{}", StoreType::Steam), "Steam");
         assert_eq!(format!("{}", StoreType::Epic), "Epic");
         assert_eq!(format!("{}", StoreType::Gog), "GOG");
+        assert_eq!(format!("{}", StoreType::Local), "Local");
     }
 
     #[test]
@@ -1,11 +1,57 @@
+use crate::launcher_core::dedup::{self, GameLinks, UnifiedGame};
+use crate::launcher_core::status::StoreStatus;
+use crate::launcher_core::supervisor::ProcessInfo;
 use crate::launcher_core::{Game, GameStore, LauncherError};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
 use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default time a single store gets to return its installed games
+pub const DEFAULT_STORE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shortest scan timeout a user can configure; anything less times out every store
+pub const MIN_STORE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How a single store's scan finished
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum StoreScanOutcome {
+    Ok {
+        game_count: usize,
+    },
+    Unavailable,
+    Failed {
+        error: String,
+    },
+    TimedOut,
+    /// The scan from an earlier refresh hasn't finished, so no new one was started
+    StillRunning,
+}
+
+/// Result of scanning one store during a refresh
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StoreScanResult {
+    pub store_id: String,
+    #[serde(flatten)]
+    pub outcome: StoreScanOutcome,
+    pub duration_ms: u64,
+    /// Unix time the refresh started
+    pub scanned_at: u64,
+}
 
 /// Central game library that aggregates games from all stores
 pub struct GameLibrary {
     stores: HashMap<&'static str, Arc<RwLock<Box<dyn GameStore>>>>,
+    /// Per-store flag set while a scan thread is running
+    scanning: HashMap<&'static str, Arc<AtomicBool>>,
     games: RwLock<Vec<Game>>,
+    store_timeout: Duration,
+    store_timeouts: HashMap<&'static str, Duration>,
+    last_scan: RwLock<Vec<StoreScanResult>>,
 }
 
 impl GameLibrary {
@@ -13,7 +59,11 @@ impl GameLibrary {
     pub fn new() -> Self {
         Self {
             stores: HashMap::new(),
+            scanning: HashMap::new(),
             games: RwLock::new(Vec::new()),
+            store_timeout: DEFAULT_STORE_TIMEOUT,
+            store_timeouts: HashMap::new(),
+            last_scan: RwLock::new(Vec::new()),
         }
     }
 
@@ -21,45 +71,201 @@ impl GameLibrary {
     pub fn register_store(&mut self, store: Box<dyn GameStore>) {
         let store_id = store.store_id();
         self.stores.insert(store_id, Arc::new(RwLock::new(store)));
+        self.scanning
+            .insert(store_id, Arc::new(AtomicBool::new(false)));
+    }
+
+    /// Set the default time each store gets to scan during a refresh
+    pub fn set_store_timeout(&mut self, timeout: Duration) {
+        self.store_timeout = timeout;
+    }
+
+    /// Override the scan timeout for a single store
+    pub fn set_store_timeout_for(&mut self, store_id: &'static str, timeout: Duration) {
+        self.store_timeouts.insert(store_id, timeout);
+    }
+
+    /// Scan timeout that applies to a store
+    pub fn store_timeout(&self, store_id: &str) -> Duration {
+        self.store_timeouts
+            .get(store_id)
+            .copied()
+            .unwrap_or(self.store_timeout)
     }
 
-    /// Refresh games from all registered stores
+    /// Refresh games from all registered stores.
+    ///
+    /// Stores are scanned concurrently on worker threads. A store that doesn't
+    /// finish within its timeout is reported as timed out and its games are
+    /// left out of the results; the scan thread is abandoned and its result
+    /// discarded. Until that thread finishes, later refreshes report the store
+    /// as still running instead of starting another scan. The cache keeps the
+    /// games of any store whose scan didn't finish from the previous refresh.
     pub fn refresh_all(&self) -> Result<Vec<Game>, LauncherError> {
+        let started = Instant::now();
+        let scanned_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let (tx, rx) = mpsc::channel();
+        let mut deadlines: HashMap<&'static str, Instant> = HashMap::new();
+        let mut results = Vec::new();
+
+        for (&store_id, store) in &self.stores {
+            let scanning = Arc::clone(&self.scanning[store_id]);
+            if scanning.swap(true, Ordering::SeqCst) {
+                log::warn!("Previous scan of {store_id} is still running");
+                results.push(StoreScanResult {
+                    store_id: store_id.to_string(),
+                    outcome: StoreScanOutcome::StillRunning,
+                    duration_ms: 0,
+                    scanned_at,
+                });
+                continue;
+            }
+
+            let store = Arc::clone(store);
+            let tx = tx.clone();
+            let guard = ScanGuard(Arc::clone(&scanning));
+            let spawned = thread::Builder::new()
+                .name(format!("scan-{store_id}"))
+                .spawn(move || {
+                    let _guard = guard;
+                    let result = scan_store(store_id, &store);
+                    // The receiver is gone if this store already timed out
+                    let _ = tx.send((store_id, result));
+                });
+
+            match spawned {
+                Ok(_) => {
+                    deadlines.insert(store_id, started + self.store_timeout(store_id));
+                }
+                Err(e) => {
+                    // The closure (and its guard) was dropped, clearing the flag
+                    log::warn!("Failed to start scan for {store_id}: {e}");
+                }
+            }
+        }
+        drop(tx);
+
         let mut all_games = Vec::new();
+        // Stores whose scan finished, so their cached games are replaced
+        let mut scanned: HashSet<&'static str> = HashSet::new();
 
-        for (store_id, store) in &self.stores {
-            let store_guard = store.read().map_err(|_| {
-                LauncherError::StoreNotFound(format!("Failed to lock store: {store_id}"))
-            })?;
+        while !deadlines.is_empty() {
+            let next_deadline = deadlines.values().min().copied().unwrap_or(started);
+            let wait = next_deadline.saturating_duration_since(Instant::now());
 
-            if store_guard.is_available() {
-                match store_guard.get_installed_games() {
-                    Ok(games) => all_games.extend(games),
-                    Err(e) => {
-                        log::warn!("Failed to get games from {store_id}: {e}");
+            match rx.recv_timeout(wait) {
+                Ok((store_id, scan)) => {
+                    if deadlines.remove(store_id).is_none() {
+                        continue;
                     }
+                    let elapsed = started.elapsed();
+                    let outcome = match scan {
+                        Ok(Some(games)) => {
+                            scanned.insert(store_id);
+                            let game_count = games.len();
+                            all_games.extend(games);
+                            StoreScanOutcome::Ok { game_count }
+                        }
+                        Ok(None) => {
+                            scanned.insert(store_id);
+                            StoreScanOutcome::Unavailable
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to get games from {store_id}: {e}");
+                            StoreScanOutcome::Failed {
+                                error: e.to_string(),
+                            }
+                        }
+                    };
+                    results.push(StoreScanResult {
+                        store_id: store_id.to_string(),
+                        outcome,
+                        duration_ms: elapsed.as_millis() as u64,
+                        scanned_at,
+                    });
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let now = Instant::now();
+                    deadlines.retain(|store_id, deadline| {
+                        if *deadline > now {
+                            return true;
+                        }
+                        log::warn!("Timed out scanning {store_id}");
+                        results.push(StoreScanResult {
+                            store_id: store_id.to_string(),
+                            outcome: StoreScanOutcome::TimedOut,
+                            duration_ms: started.elapsed().as_millis() as u64,
+                            scanned_at,
+                        });
+                        false
+                    });
                 }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
             }
         }
 
-        // Sort by name
-        all_games.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        results.sort_by(|a, b| a.store_id.cmp(&b.store_id));
+        if let Ok(mut last_scan) = self.last_scan.write() {
+            *last_scan = results;
+        }
 
-        // Update cached games
+        // Update cached games, keeping what we knew about stores that didn't
+        // finish (timed out, still running or failed)
         let mut games_guard = self
             .games
             .write()
             .map_err(|_| LauncherError::ParseError("Failed to acquire write lock".to_string()))?;
-        *games_guard = all_games.clone();
+        let mut cached_games = all_games.clone();
+        cached_games.extend(
+            games_guard
+                .iter()
+                .filter(|g| !scanned.contains(g.store.to_string().to_lowercase().as_str()))
+                .cloned(),
+        );
+        cached_games.sort_by_key(|g| g.name.to_lowercase());
+        *games_guard = cached_games;
 
+        // Sort by name
+        all_games.sort_by_key(|g| g.name.to_lowercase());
         Ok(all_games)
     }
 
+    /// Per-store results of the most recent refresh
+    pub fn last_scan_results(&self) -> Vec<StoreScanResult> {
+        self.last_scan.read().map(|r| r.clone()).unwrap_or_default()
+    }
+
+    /// Status of every registered store, with the results of its last scan.
+    /// `processes` is a snapshot of the process table, to tell whether each
+    /// store client is running.
+    pub fn store_statuses(&self, processes: &[ProcessInfo]) -> Vec<StoreStatus> {
+        let last_scan = self.last_scan_results();
+        let mut statuses: Vec<StoreStatus> = self
+            .stores
+            .iter()
+            .filter_map(|(&store_id, store)| {
+                let store = store.read().ok()?;
+                let scan = last_scan.iter().find(|r| r.store_id == store_id);
+                Some(StoreStatus::inspect(store.as_ref(), scan, processes))
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.store_id.cmp(&b.store_id));
+        statuses
+    }
+
     /// Get all cached games
     pub fn get_games(&self) -> Vec<Game> {
         self.games.read().map(|g| g.clone()).unwrap_or_default()
     }
 
+    /// Get cached games with cross-store duplicates grouped together
+    pub fn get_unified_games(&self, links: &GameLinks) -> Vec<UnifiedGame> {
+        dedup::group_games(self.get_games(), links)
+    }
+
     /// Get only installed games
     pub fn get_installed_games(&self) -> Vec<Game> {
         self.games
@@ -78,6 +284,27 @@ impl GameLibrary {
             .cloned()
     }
 
+    /// Add or replace a single cached game (e.g. after a manifest changed)
+    pub fn upsert_game(&self, game: Game) {
+        if let Ok(mut games) = self.games.write() {
+            let key = game.unique_key();
+            match games.iter_mut().find(|g| g.unique_key() == key) {
+                Some(existing) => *existing = game,
+                None => {
+                    games.push(game);
+                    games.sort_by_key(|g| g.name.to_lowercase());
+                }
+            }
+        }
+    }
+
+    /// Remove a single cached game by its unique key
+    pub fn remove_game(&self, unique_key: &str) {
+        if let Ok(mut games) = self.games.write() {
+            games.retain(|g| g.unique_key() != unique_key);
+        }
+    }
+
     /// Launch a game by its unique key
     pub fn launch_game(&self, unique_key: &str) -> Result<(), LauncherError> {
         let parts: Vec<&str> = unique_key.splitn(2, ':').collect();
@@ -119,6 +346,30 @@ impl GameLibrary {
     }
 }
 
+/// Clears a store's in-flight flag when its scan thread finishes (or panics)
+struct ScanGuard(Arc<AtomicBool>);
+
+impl Drop for ScanGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Scan one store, returning None if it isn't available
+fn scan_store(
+    store_id: &str,
+    store: &RwLock<Box<dyn GameStore>>,
+) -> Result<Option<Vec<Game>>, LauncherError> {
+    let store_guard = store
+        .read()
+        .map_err(|_| LauncherError::StoreNotFound(format!("Failed to lock store: {store_id}")))?;
+
+    if !store_guard.is_available() {
+        return Ok(None);
+    }
+    store_guard.get_installed_games().map(Some)
+}
+
 impl Default for GameLibrary {
     fn default() -> Self {
         Self::new()
@@ -133,17 +384,23 @@ mod tests {
 
     // Mock store for testing
     struct MockStore {
+        id: &'static str,
         available: bool,
         games: Vec<Game>,
         launch_result: Result<(), LauncherError>,
+        scan_delay: Duration,
+        scan_error: Option<LauncherError>,
     }
 
     impl MockStore {
         fn new(available: bool) -> Self {
             Self {
+                id: "mock",
                 available,
                 games: Vec::new(),
                 launch_result: Ok(()),
+                scan_delay: Duration::ZERO,
+                scan_error: None,
             }
         }
 
@@ -156,11 +413,26 @@ mod tests {
             self.launch_result = Err(err);
             self
         }
+
+        fn with_id(mut self, id: &'static str) -> Self {
+            self.id = id;
+            self
+        }
+
+        fn with_scan_delay(mut self, delay: Duration) -> Self {
+            self.scan_delay = delay;
+            self
+        }
+
+        fn with_scan_error(mut self, err: LauncherError) -> Self {
+            self.scan_error = Some(err);
+            self
+        }
     }
 
     impl GameStore for MockStore {
         fn store_id(&self) -> &'static str {
-            "mock"
+            self.id
         }
 
         fn display_name(&self) -> &'static str {
@@ -180,7 +452,11 @@ mod tests {
         }
 
         fn get_installed_games(&self) -> Result<Vec<Game>, LauncherError> {
-            Ok(self.games.clone())
+            thread::sleep(self.scan_delay);
+            match &self.scan_error {
+                Some(err) => Err(err.clone()),
+                None => Ok(self.games.clone()),
+            }
         }
 
         fn launch_game(&self, _game_id: &str) -> Result<(), LauncherError> {
@@ -276,6 +552,21 @@ mod tests {
         assert_eq!(games.len(), 1);
     }
 
+    #[test]
+    fn test_get_unified_games_groups_duplicates() {
+        let mut lib = GameLibrary::new();
+        let game1 = Game::new("1", "Hades", StoreType::Steam);
+        let game2 = Game::new("2", "Hades™", StoreType::Epic);
+        lib.register_store(Box::new(
+            MockStore::new(true).with_games(vec![game1, game2]),
+        ));
+        lib.refresh_all().unwrap();
+
+        let unified = lib.get_unified_games(&GameLinks::default());
+        assert_eq!(unified.len(), 1);
+        assert_eq!(unified[0].sources.len(), 2);
+    }
+
     #[test]
     fn test_get_installed_games() {
         let mut lib = GameLibrary::new();
@@ -307,6 +598,28 @@ mod tests {
         assert_eq!(found.unwrap().name, "Test Game");
     }
 
+    #[test]
+    fn test_upsert_and_remove_game() {
+        let mut lib = GameLibrary::new();
+        let game = Game::new("2", "Zelda", StoreType::Steam);
+        lib.register_store(Box::new(MockStore::new(true).with_games(vec![game])));
+        lib.refresh_all().unwrap();
+
+        lib.upsert_game(Game::new("1", "Apex", StoreType::Steam));
+        let mut renamed = Game::new("2", "Zelda II", StoreType::Steam);
+        renamed.installed = true;
+        lib.upsert_game(renamed);
+
+        let games = lib.get_games();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].name, "Apex");
+        assert!(games[1].installed);
+
+        lib.remove_game("steam:1");
+        assert!(lib.find_game("steam:1").is_none());
+        assert_eq!(lib.get_games().len(), 1);
+    }
+
     #[test]
     fn test_find_game_not_found() {
         let lib = GameLibrary::new();
@@ -357,4 +670,205 @@ mod tests {
         let games = lib.refresh_all().unwrap();
         assert!(games.is_empty());
     }
+
+    #[test]
+    fn test_refresh_all_scans_stores_concurrently() {
+        let mut lib = GameLibrary::new();
+        for (id, name) in [("a", "Alpha"), ("b", "Beta"), ("c", "Gamma")] {
+            let game = Game::new(id, name, StoreType::Steam);
+            lib.register_store(Box::new(
+                MockStore::new(true)
+                    .with_id(id)
+                    .with_games(vec![game])
+                    .with_scan_delay(Duration::from_millis(300)),
+            ));
+        }
+
+        let started = Instant::now();
+        let games = lib.refresh_all().unwrap();
+
+        assert_eq!(games.len(), 3);
+        assert!(started.elapsed() < Duration::from_millis(850));
+    }
+
+    #[test]
+    fn test_refresh_all_times_out_stuck_store() {
+        let mut lib = GameLibrary::new();
+        lib.set_store_timeout(Duration::from_millis(200));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("fast")
+                .with_games(vec![Game::new("1", "Fast Game", StoreType::Steam)]),
+        ));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("stuck")
+                .with_games(vec![Game::new("2", "Stuck Game", StoreType::Gog)])
+                .with_scan_delay(Duration::from_secs(3)),
+        ));
+
+        let started = Instant::now();
+        let games = lib.refresh_all().unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Fast Game");
+
+        let results = lib.last_scan_results();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].store_id, "fast");
+        assert_eq!(results[0].outcome, StoreScanOutcome::Ok { game_count: 1 });
+        assert_eq!(results[1].outcome, StoreScanOutcome::TimedOut);
+    }
+
+    #[test]
+    fn test_refresh_all_skips_store_still_scanning() {
+        let mut lib = GameLibrary::new();
+        lib.set_store_timeout(Duration::from_millis(100));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("stuck")
+                .with_games(vec![Game::new("1", "Stuck Game", StoreType::Gog)])
+                .with_scan_delay(Duration::from_millis(600)),
+        ));
+
+        lib.refresh_all().unwrap();
+        assert_eq!(
+            lib.last_scan_results()[0].outcome,
+            StoreScanOutcome::TimedOut
+        );
+
+        // The first scan is still going, so no second thread is started
+        let started = Instant::now();
+        lib.refresh_all().unwrap();
+        assert!(started.elapsed() < Duration::from_millis(50));
+        assert_eq!(
+            lib.last_scan_results()[0].outcome,
+            StoreScanOutcome::StillRunning
+        );
+
+        // Once it finishes, the store is scanned again
+        thread::sleep(Duration::from_millis(700));
+        lib.set_store_timeout(Duration::from_secs(5));
+        let games = lib.refresh_all().unwrap();
+        assert_eq!(games.len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_all_keeps_cached_games_of_timed_out_store() {
+        let mut lib = GameLibrary::new();
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("steam")
+                .with_games(vec![Game::new("1", "Fast Game", StoreType::Steam)]),
+        ));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("gog")
+                .with_games(vec![Game::new("2", "Slow Game", StoreType::Gog)])
+                .with_scan_delay(Duration::from_millis(300)),
+        ));
+
+        assert_eq!(lib.refresh_all().unwrap().len(), 2);
+
+        lib.set_store_timeout(Duration::from_millis(100));
+        let games = lib.refresh_all().unwrap();
+
+        // Left out of this scan's results, but still in the cache
+        assert_eq!(games.len(), 1);
+        let cached: Vec<String> = lib.get_games().into_iter().map(|g| g.name).collect();
+        assert_eq!(cached, vec!["Fast Game", "Slow Game"]);
+        assert!(lib.find_game("gog:2").is_some());
+    }
+
+    #[test]
+    fn test_refresh_all_per_store_timeout_override() {
+        let mut lib = GameLibrary::new();
+        lib.set_store_timeout(Duration::from_millis(100));
+        lib.set_store_timeout_for("slow", Duration::from_secs(5));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("slow")
+                .with_games(vec![Game::new("1", "Slow Game", StoreType::Gog)])
+                .with_scan_delay(Duration::from_millis(300)),
+        ));
+
+        let games = lib.refresh_all().unwrap();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(lib.store_timeout("slow"), Duration::from_secs(5));
+        assert_eq!(lib.store_timeout("other"), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_refresh_all_records_failures_and_unavailable() {
+        let mut lib = GameLibrary::new();
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("broken")
+                .with_scan_error(LauncherError::DatabaseError("locked".into())),
+        ));
+        lib.register_store(Box::new(MockStore::new(false).with_id("missing")));
+
+        let games = lib.refresh_all().unwrap();
+        assert!(games.is_empty());
+
+        let results = lib.last_scan_results();
+        assert!(matches!(
+            &results[0].outcome,
+            StoreScanOutcome::Failed { error } if error.contains("locked")
+        ));
+        assert_eq!(results[1].outcome, StoreScanOutcome::Unavailable);
+    }
+
+    #[test]
+    fn test_store_scan_result_serialization() {
+        let result = StoreScanResult {
+            store_id: "gog".to_string(),
+            outcome: StoreScanOutcome::TimedOut,
+            duration_ms: 10_000,
+            scanned_at: 1_700_000_000,
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["store_id"], "gog");
+        assert_eq!(json["status"], "timed_out");
+    }
+
+    #[test]
+    fn test_store_statuses_report_last_scan() {
+        let mut lib = GameLibrary::new();
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("good")
+                .with_games(vec![Game::new("1", "Game", StoreType::Steam)]),
+        ));
+        lib.register_store(Box::new(
+            MockStore::new(true)
+                .with_id("broken")
+                .with_scan_error(LauncherError::DatabaseError("locked".into())),
+        ));
+
+        // Nothing scanned yet
+        let before = lib.store_statuses(&[]);
+        assert_eq!(before.len(), 2);
+        assert!(before.iter().all(|s| s.last_scan_at.is_none()));
+
+        lib.refresh_all().unwrap();
+        let statuses = lib.store_statuses(&[]);
+
+        assert_eq!(statuses[0].store_id, "broken");
+        assert!(statuses[0]
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("locked"));
+        assert_eq!(statuses[0].game_count, None);
+        assert_eq!(statuses[1].store_id, "good");
+        assert_eq!(statuses[1].game_count, Some(1));
+        assert_eq!(statuses[1].last_error, None);
+        assert!(statuses[1].last_scan_at.is_some());
+        assert_eq!(statuses[1].client_path, Some(PathBuf::from("/mock/path")));
+        assert!(!statuses[1].client_installed);
+        assert!(!statuses[1].client_running);
+    }
 }
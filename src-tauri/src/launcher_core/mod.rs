@@ -1,9 +1,18 @@
+pub mod dedup;
+pub mod details;
 pub mod error;
 pub mod game;
 pub mod library;
+pub mod redact;
+pub mod status;
 pub mod store;
+pub mod supervisor;
 
-pub use error::LauncherError;
+pub use dedup::{GameLinks, UnifiedGame};
+pub use details::{DetailsProvider, GameDetails};
+pub use error::{CommandError, ErrorCode, LauncherError, StoreResultExt};
 pub use game::{Game, StoreType};
-pub use library::GameLibrary;
+pub use library::{GameLibrary, StoreScanOutcome, StoreScanResult};
+pub use status::{CredentialStatus, StoreStatus};
 pub use store::GameStore;
+pub use supervisor::{GameSession, ProcessSupervisor, SessionEvent, SessionExitStatus};
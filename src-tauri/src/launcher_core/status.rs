@@ -0,0 +1,131 @@
+use crate::launcher_core::library::{StoreScanOutcome, StoreScanResult};
+use crate::launcher_core::supervisor::ProcessInfo;
+use crate::launcher_core::GameStore;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Whether the credentials a store needs for syncing are there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialStatus {
+    /// The store is only scanned locally
+    NotNeeded,
+    Missing,
+    Present,
+    /// Can't tell until the credential vault is unlocked
+    Locked,
+}
+
+/// A path a store looks for, and whether it is there
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DetectedPath {
+    pub name: &'static str,
+    pub path: Option<PathBuf>,
+    pub exists: bool,
+}
+
+/// Health of one registered store: what was found on this machine and how
+/// its last scan went
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStatus {
+    pub store_id: String,
+    pub display_name: String,
+    pub available: bool,
+    pub paths: Vec<DetectedPath>,
+    pub client_path: Option<PathBuf>,
+    /// The client binary exists on disk
+    pub client_installed: bool,
+    pub client_running: bool,
+    pub credentials: CredentialStatus,
+    /// Unix time of the last scan, if there was one
+    pub last_scan_at: Option<u64>,
+    pub last_scan_duration_ms: Option<u64>,
+    /// Games found by the last scan
+    pub game_count: Option<usize>,
+    /// Why the last scan failed, if it did
+    pub last_error: Option<String>,
+    /// Unix time of the last online library sync (Steam, Epic)
+    pub last_sync_at: Option<u64>,
+}
+
+impl StoreStatus {
+    /// Inspect a store. Credentials and sync times live outside the library,
+    /// so they start out empty for the caller to fill in.
+    pub fn inspect(
+        store: &dyn GameStore,
+        scan: Option<&StoreScanResult>,
+        processes: &[ProcessInfo],
+    ) -> Self {
+        let paths = store
+            .detected_paths()
+            .into_iter()
+            .map(|(name, path)| DetectedPath {
+                name,
+                exists: path.as_ref().is_some_and(|p| p.exists()),
+                path,
+            })
+            .collect();
+        let client_path = store.get_client_path();
+
+        let (game_count, last_error) = match scan.map(|s| &s.outcome) {
+            Some(StoreScanOutcome::Ok { game_count }) => (Some(*game_count), None),
+            Some(StoreScanOutcome::Failed { error }) => (None, Some(error.clone())),
+            Some(StoreScanOutcome::TimedOut) => (None, Some("The scan timed out".to_string())),
+            Some(StoreScanOutcome::StillRunning) => {
+                (None, Some("An earlier scan is still running".to_string()))
+            }
+            Some(StoreScanOutcome::Unavailable) | None => (None, None),
+        };
+
+        Self {
+            store_id: store.store_id().to_string(),
+            display_name: store.display_name().to_string(),
+            available: store.is_available(),
+            paths,
+            client_installed: client_path.as_ref().is_some_and(|p| p.exists()),
+            client_path,
+            client_running: is_client_running(store.client_process_names(), processes),
+            credentials: CredentialStatus::NotNeeded,
+            last_scan_at: scan.map(|s| s.scanned_at),
+            last_scan_duration_ms: scan.map(|s| s.duration_ms),
+            game_count,
+            last_error,
+            last_sync_at: None,
+        }
+    }
+}
+
+/// Whether any process runs an executable with one of the client's names
+fn is_client_running(names: &[&str], processes: &[ProcessInfo]) -> bool {
+    processes
+        .iter()
+        .filter_map(|p| p.exe.as_ref()?.file_name())
+        .any(|file_name| names.iter().any(|name| file_name == *name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(exe: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid: 1,
+            exe: Some(PathBuf::from(exe)),
+        }
+    }
+
+    #[test]
+    fn test_is_client_running() {
+        let names = &["steam", "steam.exe"];
+        assert!(is_client_running(
+            names,
+            &[
+                process("/usr/bin/bash"),
+                process("/home/me/.steam/ubuntu12_32/steam")
+            ]
+        ));
+        assert!(!is_client_running(names, &[process("/usr/bin/steamcmd")]));
+        assert!(!is_client_running(&[], &[process("/usr/bin/steam")]));
+    }
+}
@@ -23,6 +23,16 @@ pub trait GameStore: Send + Sync {
 
     /// Get artwork URLs for a game
     fn get_artwork_url(&self, game_id: &str, art_type: ArtworkType) -> Option<String>;
+
+    /// Paths the store looks for on this machine, by name (for status reports)
+    fn detected_paths(&self) -> Vec<(&'static str, Option<PathBuf>)> {
+        Vec::new()
+    }
+
+    /// File names of the store client's executables, to tell if it is running
+    fn client_process_names(&self) -> &'static [&'static str] {
+        &[]
+    }
 }
 
 /// Types of artwork available for games
@@ -38,6 +48,31 @@ pub enum ArtworkType {
     Icon,
 }
 
+impl ArtworkType {
+    /// Every artwork type
+    pub const ALL: [ArtworkType; 4] = [
+        ArtworkType::Cover,
+        ArtworkType::Hero,
+        ArtworkType::Logo,
+        ArtworkType::Icon,
+    ];
+
+    /// Lowercase name used in URLs and file names
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArtworkType::Cover => "cover",
+            ArtworkType::Hero => "hero",
+            ArtworkType::Logo => "logo",
+            ArtworkType::Icon => "icon",
+        }
+    }
+
+    /// Parse a name produced by `as_str`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,12 +84,21 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_artwork_type_clone() {
         let art = ArtworkType::Hero;
         let cloned = art.clone();
         assert_eq!(art, cloned);
     }
 
+    #[test]
+    fn test_artwork_type_names_round_trip() {
+        for art in ArtworkType::ALL {
+            assert_eq!(ArtworkType::from_name(art.as_str()), Some(art));
+        }
+        assert_eq!(ArtworkType::from_name("banner"), None);
+    }
+
     #[test]
     fn test_artwork_type_debug() {
         let art = ArtworkType::Icon;
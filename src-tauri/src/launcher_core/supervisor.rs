@@ -0,0 +1,683 @@
+use crate::launcher_core::Game;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// How often the process table is polled
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait for the game process to appear after a launch.
+/// Store clients may need to update or sync saves before the game starts.
+const DEFAULT_DETECT_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Consecutive polls without a game process before the session is considered over.
+/// Covers launchers that exit and re-exec the real game binary.
+const DEFAULT_EXIT_GRACE_POLLS: u32 = 3;
+
+/// How a tracked game session ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionExitStatus {
+    /// All game processes exited
+    Exited,
+    /// No game process was detected after launch
+    NotDetected,
+    /// Tracking was stopped before the game exited (e.g. another game was launched)
+    Stopped,
+}
+
+/// A game session tracked by the supervisor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameSession {
+    /// Unique key of the game (store:id)
+    pub game_key: String,
+
+    /// Display name of the game
+    pub game_name: String,
+
+    /// When TenFoot launched the game (Unix epoch)
+    pub launched_at: u64,
+
+    /// When the game process was first detected (Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+
+    /// When the game process exited (Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<u64>,
+
+    /// Process ID of the detected game process
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+
+    /// How the session ended (None while the session is active)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_status: Option<SessionExitStatus>,
+}
+
+impl GameSession {
+    fn new(game: &Game) -> Self {
+        Self {
+            game_key: game.unique_key(),
+            game_name: game.name.clone(),
+            launched_at: now_secs(),
+            started_at: None,
+            ended_at: None,
+            pid: None,
+            exit_status: None,
+        }
+    }
+
+    /// Check if the game process is currently running
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some() && self.exit_status.is_none()
+    }
+
+    /// Length of the session in seconds (None if the game never started or is still running)
+    pub fn duration_secs(&self) -> Option<u64> {
+        Some(self.ended_at?.saturating_sub(self.started_at?))
+    }
+}
+
+/// Events reported while a session is tracked
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    /// The game process was detected
+    Started(GameSession),
+    /// The session is over (see `GameSession::exit_status`)
+    Ended(GameSession),
+}
+
+/// A running process as seen by the supervisor
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub exe: Option<PathBuf>,
+}
+
+/// Source of process table snapshots
+pub trait ProcessSource: Send {
+    /// List currently running processes
+    fn processes(&mut self) -> Vec<ProcessInfo>;
+}
+
+/// Process source backed by the operating system's process table
+pub struct SystemProcesses {
+    system: System,
+    own_pid: u32,
+}
+
+impl SystemProcesses {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            own_pid: std::process::id(),
+        }
+    }
+}
+
+impl Default for SystemProcesses {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessSource for SystemProcesses {
+    fn processes(&mut self) -> Vec<ProcessInfo> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_exe(UpdateKind::OnlyIfNotSet),
+        );
+
+        self.system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessInfo {
+                pid: pid.as_u32(),
+                exe: process.exe().map(Path::to_path_buf),
+            })
+            .filter(|p| p.pid != self.own_pid)
+            .collect()
+    }
+}
+
+/// Timing configuration for the supervisor
+#[derive(Debug, Clone, Copy)]
+pub struct SupervisorConfig {
+    /// How often the process table is polled
+    pub poll_interval: Duration,
+    /// How long to wait for the game process to appear after launch
+    pub detect_timeout: Duration,
+    /// Consecutive polls without a game process before the session ends
+    pub exit_grace_polls: u32,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            detect_timeout: DEFAULT_DETECT_TIMEOUT,
+            exit_grace_polls: DEFAULT_EXIT_GRACE_POLLS,
+        }
+    }
+}
+
+/// Identifies the real game process after a launch and tracks it until exit.
+///
+/// Steam, Epic and GOG launches go through the store client, so the spawned
+/// process is not the game. Instead, any process running the game's own
+/// executable, or an executable under its install path, is treated as the
+/// game. Only one session is tracked at a time.
+pub struct ProcessSupervisor {
+    config: SupervisorConfig,
+    current: Arc<Mutex<Option<GameSession>>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl ProcessSupervisor {
+    /// Create a supervisor with default timings
+    pub fn new() -> Self {
+        Self::with_config(SupervisorConfig::default())
+    }
+
+    /// Create a supervisor with custom timings
+    pub fn with_config(config: SupervisorConfig) -> Self {
+        Self {
+            config,
+            current: Arc::new(Mutex::new(None)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Get the currently active session (waiting for or running the game)
+    pub fn current_session(&self) -> Option<GameSession> {
+        self.current.lock().ok().and_then(|s| s.clone())
+    }
+
+    /// Start tracking a freshly launched game using the system process table
+    pub fn track<F>(&self, game: &Game, on_event: F)
+    where
+        F: Fn(SessionEvent) + Send + 'static,
+    {
+        self.track_with_source(game, SystemProcesses::new(), on_event);
+    }
+
+    /// Start tracking a freshly launched game using a custom process source.
+    /// Any session already being tracked is stopped.
+    pub fn track_with_source<S, F>(&self, game: &Game, source: S, on_event: F)
+    where
+        S: ProcessSource + 'static,
+        F: Fn(SessionEvent) + Send + 'static,
+    {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let session = GameSession::new(game);
+        let target = ProcessTarget::for_game(game);
+
+        if target.is_empty() {
+            log::info!(
+                "No install path for {}, cannot track its process",
+                session.game_key
+            );
+            let mut session = session;
+            session.exit_status = Some(SessionExitStatus::NotDetected);
+            if let Ok(mut current) = self.current.lock() {
+                *current = None;
+            }
+            on_event(SessionEvent::Ended(session));
+            return;
+        }
+
+        if let Ok(mut current) = self.current.lock() {
+            *current = Some(session.clone());
+        }
+
+        let tracker = SessionTracker {
+            session,
+            target,
+            config: self.config,
+            current: Arc::clone(&self.current),
+            generation: Arc::clone(&self.generation),
+            own_generation: generation,
+        };
+
+        thread::spawn(move || tracker.run(source, on_event));
+    }
+
+    /// Stop tracking the current session, if any
+    pub fn stop(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut current) = self.current.lock() {
+            *current = None;
+        }
+    }
+}
+
+impl Default for ProcessSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State owned by a tracking thread
+struct SessionTracker {
+    session: GameSession,
+    target: ProcessTarget,
+    config: SupervisorConfig,
+    current: Arc<Mutex<Option<GameSession>>>,
+    generation: Arc<AtomicU64>,
+    own_generation: u64,
+}
+
+impl SessionTracker {
+    fn run<S, F>(mut self, mut source: S, on_event: F)
+    where
+        S: ProcessSource,
+        F: Fn(SessionEvent),
+    {
+        let launched = Instant::now();
+        let mut missed_polls = 0;
+        let mut last_seen = 0;
+
+        loop {
+            thread::sleep(self.config.poll_interval);
+
+            if !self.is_current() {
+                self.finish(SessionExitStatus::Stopped, now_secs(), &on_event);
+                return;
+            }
+
+            let pids = self.target.matching_pids(&source.processes());
+
+            if self.session.started_at.is_none() {
+                if let Some(&pid) = pids.first() {
+                    last_seen = now_secs();
+                    self.session.started_at = Some(last_seen);
+                    self.session.pid = Some(pid);
+                    self.publish();
+                    on_event(SessionEvent::Started(self.session.clone()));
+                } else if launched.elapsed() >= self.config.detect_timeout {
+                    self.finish(SessionExitStatus::NotDetected, now_secs(), &on_event);
+                    return;
+                }
+                continue;
+            }
+
+            if pids.is_empty() {
+                missed_polls += 1;
+                if missed_polls >= self.config.exit_grace_polls {
+                    self.finish(SessionExitStatus::Exited, last_seen, &on_event);
+                    return;
+                }
+            } else {
+                missed_polls = 0;
+                last_seen = now_secs();
+                if !self.session.pid.is_some_and(|pid| pids.contains(&pid)) {
+                    self.session.pid = Some(pids[0]);
+                    self.publish();
+                }
+            }
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        self.generation.load(Ordering::SeqCst) == self.own_generation
+    }
+
+    /// Update the shared session if this tracker still owns it
+    fn publish(&self) {
+        if let Ok(mut current) = self.current.lock() {
+            if self.is_current() {
+                *current = Some(self.session.clone());
+            }
+        }
+    }
+
+    fn finish<F>(mut self, status: SessionExitStatus, ended_at: u64, on_event: &F)
+    where
+        F: Fn(SessionEvent),
+    {
+        if self.session.started_at.is_some() {
+            self.session.ended_at = Some(ended_at);
+        }
+        self.session.exit_status = Some(status);
+
+        if let Ok(mut current) = self.current.lock() {
+            if self.is_current() {
+                *current = None;
+            }
+        }
+
+        on_event(SessionEvent::Ended(self.session));
+    }
+}
+
+/// Which processes belong to a game
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessTarget {
+    /// The game's own executable, matched exactly
+    executable: Option<PathBuf>,
+    /// Game folder; any executable inside it counts as the game
+    install_path: Option<PathBuf>,
+}
+
+impl ProcessTarget {
+    fn for_game(game: &Game) -> Self {
+        Self {
+            executable: game.executable.clone(),
+            // A local game pointed at /usr/bin would make every tool in it "the game"
+            install_path: game
+                .install_path
+                .clone()
+                .filter(|path| !is_system_dir(path)),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.executable.is_none() && self.install_path.is_none()
+    }
+
+    /// Find processes running the executable or an executable inside the install path
+    fn matching_pids(&self, processes: &[ProcessInfo]) -> Vec<u32> {
+        processes
+            .iter()
+            .filter(|p| {
+                p.exe.as_ref().is_some_and(|exe| {
+                    self.executable.as_ref() == Some(exe)
+                        || self
+                            .install_path
+                            .as_ref()
+                            .is_some_and(|root| exe.starts_with(root))
+                })
+            })
+            .map(|p| p.pid)
+            .collect()
+    }
+}
+
+/// Directories shared by many programs, which can never be a single game's folder
+fn is_system_dir(path: &Path) -> bool {
+    const SYSTEM_DIRS: &[&str] = &[
+        "/bin",
+        "/sbin",
+        "/usr",
+        "/usr/bin",
+        "/usr/sbin",
+        "/usr/games",
+        "/usr/local",
+        "/usr/local/bin",
+        "/usr/local/games",
+        "/opt",
+        "/Applications",
+        "/System/Applications",
+        "C:\\Windows",
+        "C:\\Windows\\System32",
+        "C:\\Program Files",
+        "C:\\Program Files (x86)",
+    ];
+    const HOME_DIRS: &[&str] = &["Applications", "Desktop", "Downloads", "bin", ".local/bin"];
+
+    // The filesystem root, or a top-level directory such as /home
+    if path.components().count() <= 2 {
+        return true;
+    }
+    if SYSTEM_DIRS.iter().any(|dir| path == Path::new(dir)) {
+        return true;
+    }
+    dirs::home_dir().is_some_and(|home| {
+        home.starts_with(path) || HOME_DIRS.iter().any(|dir| path == home.join(dir))
+    })
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+    use std::collections::VecDeque;
+    use std::sync::mpsc;
+
+    /// Process source that replays scripted snapshots, repeating the last one
+    struct ScriptedProcesses {
+        snapshots: VecDeque<Vec<ProcessInfo>>,
+    }
+
+    impl ScriptedProcesses {
+        fn new(snapshots: Vec<Vec<ProcessInfo>>) -> Self {
+            Self {
+                snapshots: snapshots.into(),
+            }
+        }
+    }
+
+    impl ProcessSource for ScriptedProcesses {
+        fn processes(&mut self) -> Vec<ProcessInfo> {
+            if self.snapshots.len() > 1 {
+                self.snapshots.pop_front().unwrap_or_default()
+            } else {
+                self.snapshots.front().cloned().unwrap_or_default()
+            }
+        }
+    }
+
+    fn test_config() -> SupervisorConfig {
+        SupervisorConfig {
+            poll_interval: Duration::from_millis(5),
+            detect_timeout: Duration::from_millis(100),
+            exit_grace_polls: 2,
+        }
+    }
+
+    fn installed_game() -> Game {
+        let mut game = Game::new("440", "Team Fortress 2", StoreType::Steam);
+        game.set_installed(PathBuf::from("/games/tf2"));
+        game
+    }
+
+    fn process(pid: u32, exe: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            exe: Some(PathBuf::from(exe)),
+        }
+    }
+
+    fn recv(rx: &mpsc::Receiver<SessionEvent>) -> SessionEvent {
+        rx.recv_timeout(Duration::from_secs(5)).unwrap()
+    }
+
+    #[test]
+    fn test_matching_pids_under_install_path() {
+        let target = ProcessTarget::for_game(&installed_game());
+        let processes = vec![
+            process(1, "/games/tf2/hl2_linux"),
+            process(2, "/usr/bin/steam"),
+        ];
+        assert_eq!(target.matching_pids(&processes), vec![1]);
+    }
+
+    #[test]
+    fn test_matching_pids_ignores_sibling_prefix() {
+        let target = ProcessTarget::for_game(&installed_game());
+        let processes = vec![process(1, "/games/tf2-beta/hl2_linux")];
+        assert!(target.matching_pids(&processes).is_empty());
+    }
+
+    #[test]
+    fn test_matching_pids_exact_executable_outside_install() {
+        let mut game = installed_game();
+        game.set_executable(PathBuf::from("/opt/runtime/run.sh"));
+        let target = ProcessTarget::for_game(&game);
+        let processes = vec![
+            process(1, "/opt/runtime/run.sh"),
+            process(2, "/opt/runtime/helper"),
+        ];
+        assert_eq!(target.matching_pids(&processes), vec![1]);
+    }
+
+    #[test]
+    fn test_matching_pids_ignores_system_install_path() {
+        let mut game = Game::new("retroarch", "RetroArch", StoreType::Local);
+        game.set_installed(PathBuf::from("/usr/bin"));
+        game.set_executable(PathBuf::from("/usr/bin/retroarch"));
+        let target = ProcessTarget::for_game(&game);
+        let processes = vec![
+            process(1, "/usr/bin/retroarch"),
+            process(2, "/usr/bin/bash"),
+        ];
+        assert_eq!(target.install_path, None);
+        assert_eq!(target.matching_pids(&processes), vec![1]);
+    }
+
+    #[test]
+    fn test_is_system_dir() {
+        assert!(is_system_dir(Path::new("/")));
+        assert!(is_system_dir(Path::new("/home")));
+        assert!(is_system_dir(Path::new("/usr/bin")));
+        assert!(!is_system_dir(Path::new("/games/tf2")));
+        assert!(!is_system_dir(Path::new("/usr/share/games/quake")));
+    }
+
+    #[test]
+    fn test_session_duration() {
+        let mut session = GameSession::new(&installed_game());
+        assert_eq!(session.duration_secs(), None);
+        session.started_at = Some(100);
+        session.ended_at = Some(160);
+        assert_eq!(session.duration_secs(), Some(60));
+    }
+
+    #[test]
+    fn test_track_detects_start_and_exit() {
+        let supervisor = ProcessSupervisor::with_config(test_config());
+        let source = ScriptedProcesses::new(vec![
+            vec![],
+            vec![process(42, "/games/tf2/hl2_linux")],
+            vec![process(42, "/games/tf2/hl2_linux")],
+            vec![],
+        ]);
+        let (tx, rx) = mpsc::channel();
+
+        supervisor.track_with_source(&installed_game(), source, move |e| {
+            let _ = tx.send(e);
+        });
+
+        let SessionEvent::Started(started) = recv(&rx) else {
+            panic!("expected Started event");
+        };
+        assert_eq!(started.game_key, "steam:440");
+        assert_eq!(started.pid, Some(42));
+        assert!(started.is_running());
+
+        let SessionEvent::Ended(ended) = recv(&rx) else {
+            panic!("expected Ended event");
+        };
+        assert_eq!(ended.exit_status, Some(SessionExitStatus::Exited));
+        assert!(ended.ended_at.is_some());
+        assert!(supervisor.current_session().is_none());
+    }
+
+    #[test]
+    fn test_track_not_detected_after_timeout() {
+        let supervisor = ProcessSupervisor::with_config(test_config());
+        let source = ScriptedProcesses::new(vec![vec![process(1, "/usr/bin/steam")]]);
+        let (tx, rx) = mpsc::channel();
+
+        supervisor.track_with_source(&installed_game(), source, move |e| {
+            let _ = tx.send(e);
+        });
+
+        let SessionEvent::Ended(ended) = recv(&rx) else {
+            panic!("expected Ended event");
+        };
+        assert_eq!(ended.exit_status, Some(SessionExitStatus::NotDetected));
+        assert!(ended.started_at.is_none());
+        assert!(ended.ended_at.is_none());
+    }
+
+    #[test]
+    fn test_track_without_install_path_ends_immediately() {
+        let supervisor = ProcessSupervisor::with_config(test_config());
+        let game = Game::new("1", "Not Installed", StoreType::Gog);
+        let (tx, rx) = mpsc::channel();
+
+        supervisor.track_with_source(&game, ScriptedProcesses::new(vec![]), move |e| {
+            let _ = tx.send(e);
+        });
+
+        let SessionEvent::Ended(ended) = recv(&rx) else {
+            panic!("expected Ended event");
+        };
+        assert_eq!(ended.exit_status, Some(SessionExitStatus::NotDetected));
+        assert!(supervisor.current_session().is_none());
+    }
+
+    #[test]
+    fn test_current_session_while_running() {
+        let supervisor = ProcessSupervisor::with_config(test_config());
+        let source = ScriptedProcesses::new(vec![vec![process(42, "/games/tf2/hl2_linux")]]);
+        let (tx, rx) = mpsc::channel();
+
+        supervisor.track_with_source(&installed_game(), source, move |e| {
+            let _ = tx.send(e);
+        });
+
+        assert!(matches!(recv(&rx), SessionEvent::Started(_)));
+        let current = supervisor.current_session().unwrap();
+        assert_eq!(current.game_key, "steam:440");
+        assert_eq!(current.pid, Some(42));
+
+        supervisor.stop();
+        let SessionEvent::Ended(ended) = recv(&rx) else {
+            panic!("expected Ended event");
+        };
+        assert_eq!(ended.exit_status, Some(SessionExitStatus::Stopped));
+    }
+
+    #[test]
+    fn test_new_launch_stops_previous_session() {
+        let supervisor = ProcessSupervisor::with_config(SupervisorConfig {
+            detect_timeout: Duration::from_secs(5),
+            ..test_config()
+        });
+        let (tx, rx) = mpsc::channel();
+
+        let first_tx = tx.clone();
+        supervisor.track_with_source(
+            &installed_game(),
+            ScriptedProcesses::new(vec![vec![process(42, "/games/tf2/hl2_linux")]]),
+            move |e| {
+                let _ = first_tx.send(e);
+            },
+        );
+        assert!(matches!(recv(&rx), SessionEvent::Started(_)));
+
+        let mut other = Game::new("570", "Dota 2", StoreType::Steam);
+        other.set_installed(PathBuf::from("/games/dota"));
+        supervisor.track_with_source(&other, ScriptedProcesses::new(vec![vec![]]), move |e| {
+            let _ = tx.send(e);
+        });
+
+        let SessionEvent::Ended(ended) = recv(&rx) else {
+            panic!("expected Ended event");
+        };
+        assert_eq!(ended.game_key, "steam:440");
+        assert_eq!(ended.exit_status, Some(SessionExitStatus::Stopped));
+        assert_eq!(supervisor.current_session().unwrap().game_key, "steam:570");
+    }
+
+    #[test]
+    fn test_exit_status_serialization() {
+        let json = serde_json::to_string(&SessionExitStatus::NotDetected).unwrap();
+        assert_eq!(json, "\"not_detected\"");
+    }
+}
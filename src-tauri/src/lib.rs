@@ -1,16 +1,59 @@
+pub mod artwork;
+pub mod cli;
+pub mod collections;
+pub mod diagnostics;
 pub mod launcher_core;
+pub mod logging;
+pub mod metadata;
+pub mod query;
+pub mod search;
+pub mod stats;
 pub mod storage;
 pub mod stores;
-
-use crate::launcher_core::{Game, GameLibrary, GameStore};
-use crate::storage::{AppSettings, Storage};
-use crate::stores::epic::{EpicApi, EpicCredentials, EpicGameDetails};
-use crate::stores::steam::{GameDetails, SteamApi, SteamCredentials};
-use crate::stores::{EpicStore, GogStore, SteamStore};
+pub mod sync;
+pub mod vault;
+pub mod watcher;
+
+use crate::artwork::{
+    ArtworkCache, ArtworkCandidate, ArtworkResolver, CachedArtwork, GameArtworkInfo,
+    SteamGridDbProvider, ART_PROTOCOL, DEFAULT_MAX_CACHE_BYTES,
+};
+use crate::collections::{Collection, SmartCollection, UserCollections};
+use crate::diagnostics::{Doctor, DoctorReport};
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::supervisor::{ProcessSource, SystemProcesses};
+use crate::launcher_core::{
+    dedup, CommandError, Game, GameDetails, GameLibrary, GameSession, GameStore, LauncherError,
+    ProcessSupervisor, SessionEvent, StoreResultExt, StoreScanResult, StoreStatus, StoreType,
+    UnifiedGame,
+};
+use crate::logging::{LogEntry, LogLevel};
+use crate::metadata::{DetailsUpdate, MetadataService};
+use crate::query::{GameMetadata, Query, QueryContext};
+use crate::search::{LibrarySearch, SearchCache, SearchIndex, SearchResult, DEFAULT_SEARCH_LIMIT};
+use crate::stats::{
+    GamePlaytime, PeriodPlaytime, PlaytimeStats, PlaytimeSummary, StatsPeriod, StorePlaytime,
+};
+use crate::storage::{AppSettings, CustomArtwork, GamesCache, LocalGame, PlaySession, Storage};
+use crate::stores::epic::{EpicApi, EpicCredentials, EpicDetailsProvider};
+use crate::stores::gog::GogApi;
+use crate::stores::steam::{SteamApi, SteamCredentials};
+use crate::stores::{local, SteamStore};
+use crate::sync::{combine_games, merge_cached_games, merge_owned_games};
+use crate::vault::{CredentialVault, VaultStatus};
+use crate::watcher::{LibraryChange, LibraryWatcher};
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{Manager, State};
+use tauri::http::{header, Response, StatusCode};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// A background task that panicked or was cancelled
+impl From<tauri::Error> for CommandError {
+    fn from(err: tauri::Error) -> Self {
+        CommandError::internal(err)
+    }
+}
 
 /// Type alias for play history data: (last_played, installed_at)
 type PlayHistoryMap = HashMap<String, (Option<u64>, Option<u64>)>;
@@ -19,8 +62,16 @@ type PlayHistoryMap = HashMap<String, (Option<u64>, Option<u64>)>;
 pub struct AppState {
     pub library: Mutex<GameLibrary>,
     pub storage: Mutex<Storage>,
+    pub vault: CredentialVault,
     pub steam_api: Mutex<SteamApi>,
     pub epic_api: Mutex<EpicApi>,
+    pub supervisor: ProcessSupervisor,
+    pub watcher: Mutex<Option<LibraryWatcher>>,
+    pub metadata: Arc<MetadataService>,
+    pub artwork: Arc<ArtworkCache>,
+    pub resolver: Arc<ArtworkResolver>,
+    pub steamgriddb: Arc<SteamGridDbProvider>,
+    pub search: SearchCache,
 }
 
 impl AppState {
@@ -29,62 +80,254 @@ impl AppState {
         let mut library = GameLibrary::new();
 
         // Register available stores
-        library.register_store(Box::new(SteamStore::new()));
-        library.register_store(Box::new(EpicStore::new()));
-        library.register_store(Box::new(GogStore::new()));
+        stores::register_default_stores(&mut library).expect("Failed to initialize storage");
 
         let storage = Storage::new().expect("Failed to initialize storage");
+        let artwork = ArtworkCache::new(storage.data_dir().join("artwork"))
+            .expect("Failed to initialize artwork cache");
+        let vault = CredentialVault::open(storage.data_dir());
+        if let Err(e) = vault.import_plaintext(&storage) {
+            log::warn!("Credentials not moved into the vault yet: {e}");
+        }
+        let steamgriddb = Arc::new(SteamGridDbProvider::new());
+        if let Ok(creds) = vault.load() {
+            steamgriddb.set_api_key(creds.steamgriddb_api_key);
+        }
+        let mut resolver =
+            ArtworkResolver::with_default_providers(artwork.dir().join("probes.json"));
+        resolver.add_provider(steamgriddb.clone());
+        if let Ok(settings) = storage.load_settings() {
+            apply_library_settings(&mut library, &settings);
+            apply_artwork_settings(&artwork, &settings);
+            logging::set_level(settings.log_level.unwrap_or_default());
+        }
+
+        let mut metadata =
+            MetadataService::new(Storage::new().expect("Failed to initialize storage"));
+        metadata.register_provider(Arc::new(SteamApi::new()));
+        metadata.register_provider(Arc::new(
+            EpicDetailsProvider::new().expect("Failed to initialize storage"),
+        ));
+        metadata.register_provider(Arc::new(GogApi::new()));
 
         Self {
             library: Mutex::new(library),
             storage: Mutex::new(storage),
+            vault,
             steam_api: Mutex::new(SteamApi::new()),
             epic_api: Mutex::new(EpicApi::new()),
+            supervisor: ProcessSupervisor::new(),
+            watcher: Mutex::new(None),
+            metadata: Arc::new(metadata),
+            artwork: Arc::new(artwork),
+            resolver: Arc::new(resolver),
+            steamgriddb,
+            search: SearchCache::new(),
         }
     }
 }
 
+/// Apply user settings that affect how the library scans stores
+fn apply_library_settings(library: &mut GameLibrary, settings: &AppSettings) {
+    library.set_store_timeout(settings.store_timeout());
+}
+
+/// Apply artwork cache settings (size limit)
+fn apply_artwork_settings(artwork: &ArtworkCache, settings: &AppSettings) {
+    let max_bytes = settings
+        .artwork_cache_mb
+        .map(|mb| mb * 1024 * 1024)
+        .unwrap_or(DEFAULT_MAX_CACHE_BYTES);
+    if let Err(e) = artwork.set_max_bytes(max_bytes) {
+        log::warn!("Failed to apply artwork cache limit: {e}");
+    }
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Start watching store manifests and push install changes to the frontend
+fn start_library_watcher(app: &AppHandle) {
+    let handle = app.clone();
+    let watcher = LibraryWatcher::start(
+        watcher::detect_targets(),
+        watcher::DEFAULT_DEBOUNCE,
+        move |change| handle_library_change(&handle, change),
+    );
+
+    match watcher {
+        Ok(watcher) => {
+            let state = app.state::<AppState>();
+            if let Ok(mut slot) = state.watcher.lock() {
+                *slot = Some(watcher);
+            };
+        }
+        Err(e) => log::warn!("Failed to start library watcher: {e}"),
+    }
+}
+
+/// Update the cached library and notify the frontend about a changed game
+fn handle_library_change(app: &AppHandle, change: LibraryChange) {
+    let state = app.state::<AppState>();
+    if let Ok(library) = state.library.lock() {
+        match &change {
+            LibraryChange::Added(game) | LibraryChange::Updated(game) => {
+                library.upsert_game(game.clone())
+            }
+            LibraryChange::Removed(game) => library.remove_game(&game.unique_key()),
+        }
+    }
+    state.search.invalidate();
+
+    if let LibraryChange::Added(game) = &change {
+        if let Ok(storage) = state.storage.lock() {
+            if let Err(e) = storage.record_game_installed(&game.unique_key()) {
+                log::warn!("Failed to record install of {}: {e}", game.unique_key());
+            }
+        }
+    }
+
+    if let Err(e) = app.emit(change.event_name(), change.game()) {
+        log::warn!("Failed to emit {}: {e}", change.event_name());
+    }
+}
+
+/// Rescan the stores for installed games
+fn refresh_library(state: &AppState) -> Result<Vec<Game>, CommandError> {
+    let games = {
+        let library = state.library.lock()?;
+        library.refresh_all()?
+    };
+    state.search.invalidate();
+    Ok(games)
+}
+
 /// Get all installed games from all registered stores
 #[tauri::command]
-fn get_installed_games(state: State<AppState>) -> Result<Vec<Game>, String> {
-    let library = state.library.lock().map_err(|e| e.to_string())?;
-    library.refresh_all().map_err(|e| e.to_string())
+fn get_installed_games(state: State<AppState>) -> Result<Vec<Game>, CommandError> {
+    let games = refresh_library(&state)?;
+    with_artwork_overrides(&state, games)
 }
 
-/// Get cached games (without refreshing)
+/// Get per-store results (games found, failure, timeout) of the last refresh
 #[tauri::command]
-fn get_games(state: State<AppState>) -> Result<Vec<Game>, String> {
-    let library = state.library.lock().map_err(|e| e.to_string())?;
-    Ok(library.get_games())
+fn get_store_scan_results(state: State<AppState>) -> Result<Vec<StoreScanResult>, CommandError> {
+    let library = state.library.lock()?;
+    Ok(library.last_scan_results())
+}
+
+/// Get the health of every registered store: detected paths, whether the
+/// client is installed and running, credentials, and how the last scan went
+#[tauri::command]
+fn get_store_status(state: State<AppState>) -> Result<Vec<StoreStatus>, CommandError> {
+    load_store_statuses(&state)
+}
+
+fn load_store_statuses(state: &State<AppState>) -> Result<Vec<StoreStatus>, CommandError> {
+    let processes = SystemProcesses::new().processes();
+    let mut statuses = {
+        let library = state.library.lock()?;
+        library.store_statuses(&processes)
+    };
+
+    let credentials = state.vault.load().ok();
+    let storage = state.storage.lock()?;
+    sync::apply_account_status(&mut statuses, credentials.as_ref(), &storage)?;
+    Ok(statuses)
+}
+
+/// Get cached games (without refreshing). Hidden games are left out unless requested.
+#[tauri::command]
+fn get_games(
+    state: State<AppState>,
+    include_hidden: Option<bool>,
+) -> Result<Vec<Game>, CommandError> {
+    let games = {
+        let library = state.library.lock()?;
+        library.get_games()
+    };
+
+    let games = if include_hidden.unwrap_or(false) {
+        games
+    } else {
+        let storage = state.storage.lock()?;
+        let collections = storage.load_collections()?;
+        games
+            .into_iter()
+            .filter(|g| !collections.is_hidden(&g.unique_key()))
+            .collect()
+    };
+    with_artwork_overrides(&state, games)
 }
 
 /// Launch a game by its unique key (store:id)
 /// Returns the timestamp when the game was launched (for immediate UI update)
 #[tauri::command]
-fn launch_game(state: State<AppState>, game_key: String) -> Result<u64, String> {
-    let library = state.library.lock().map_err(|e| e.to_string())?;
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
+fn launch_game(
+    app: AppHandle,
+    state: State<AppState>,
+    game_key: String,
+) -> Result<u64, CommandError> {
+    let library = state.library.lock()?;
 
     // Record the launch time before launching and get the timestamp
-    let timestamp = storage
-        .record_game_launch_with_timestamp(&game_key)
-        .map_err(|e| e.to_string())?;
+    let timestamp = {
+        let storage = state.storage.lock()?;
+        storage.record_game_launch_with_timestamp(&game_key)?
+    };
 
-    library.launch_game(&game_key).map_err(|e| e.to_string())?;
+    library.launch_game(&game_key)?;
+
+    // Track the game process so the frontend knows when it is running
+    match library.find_game(&game_key) {
+        Some(game) => state
+            .supervisor
+            .track(&game, move |event| handle_session_event(&app, event)),
+        None => log::info!("{game_key} is not in the library cache, not tracking its process"),
+    }
 
     Ok(timestamp)
 }
 
+/// Forward supervisor events to the frontend and record finished sessions
+fn handle_session_event(app: &AppHandle, event: SessionEvent) {
+    match event {
+        SessionEvent::Started(session) => {
+            let _ = app.emit("game-session-started", &session);
+        }
+        SessionEvent::Ended(session) => {
+            if let Some(play) = PlaySession::from_session(&session) {
+                let state = app.state::<AppState>();
+                let result = state
+                    .storage
+                    .lock()
+                    .map_err(CommandError::from)
+                    .and_then(|s| s.record_play_session(play).map_err(CommandError::from));
+                if let Err(e) = result {
+                    log::warn!(
+                        "Failed to record play session for {}: {e}",
+                        session.game_key
+                    );
+                }
+            }
+            let _ = app.emit("game-session-ended", &session);
+        }
+    }
+}
+
+/// Get the game session currently tracked by the supervisor (if any)
+#[tauri::command]
+fn get_running_game(state: State<AppState>) -> Result<Option<GameSession>, CommandError> {
+    Ok(state.supervisor.current_session())
+}
+
 /// Get list of available stores
 #[tauri::command]
-fn get_available_stores(state: State<AppState>) -> Result<Vec<String>, String> {
-    let library = state.library.lock().map_err(|e| e.to_string())?;
+fn get_available_stores(state: State<AppState>) -> Result<Vec<String>, CommandError> {
+    let library = state.library.lock()?;
     Ok(library
         .get_available_stores()
         .into_iter()
@@ -94,9 +337,13 @@ fn get_available_stores(state: State<AppState>) -> Result<Vec<String>, String> {
 
 /// Find a specific game by its unique key
 #[tauri::command]
-fn find_game(state: State<AppState>, game_key: String) -> Result<Option<Game>, String> {
-    let library = state.library.lock().map_err(|e| e.to_string())?;
-    Ok(library.find_game(&game_key))
+fn find_game(state: State<AppState>, game_key: String) -> Result<Option<Game>, CommandError> {
+    let game = {
+        let library = state.library.lock()?;
+        library.find_game(&game_key)
+    };
+    let games = with_artwork_overrides(&state, game.into_iter().collect())?;
+    Ok(games.into_iter().next())
 }
 
 /// Save Steam credentials
@@ -105,37 +352,37 @@ fn save_steam_credentials(
     state: State<AppState>,
     api_key: String,
     steam_id: String,
-) -> Result<(), String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let mut creds = storage.load_credentials().map_err(|e| e.to_string())?;
-    creds.steam = Some(SteamCredentials { api_key, steam_id });
-    storage.save_credentials(&creds).map_err(|e| e.to_string())
+) -> Result<(), CommandError> {
+    state
+        .vault
+        .update(|creds| creds.steam = Some(SteamCredentials { api_key, steam_id }))
+        .map_err(CommandError::from)
 }
 
 /// Get Steam credentials (returns None if not set)
 #[tauri::command]
-fn get_steam_credentials(state: State<AppState>) -> Result<Option<SteamCredentials>, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let creds = storage.load_credentials().map_err(|e| e.to_string())?;
+fn get_steam_credentials(state: State<AppState>) -> Result<Option<SteamCredentials>, CommandError> {
+    let creds = state.vault.load()?;
     Ok(creds.steam)
 }
 
 /// Check if Steam is connected (has credentials)
 #[tauri::command]
-fn is_steam_connected(state: State<AppState>) -> Result<bool, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let creds = storage.load_credentials().map_err(|e| e.to_string())?;
+fn is_steam_connected(state: State<AppState>) -> Result<bool, CommandError> {
+    let creds = state.vault.load()?;
     Ok(creds.steam.is_some())
 }
 
 /// Sync Steam library (fetch owned games from API and merge with installed)
 #[tauri::command]
-async fn sync_steam_library(state: State<'_, AppState>) -> Result<Vec<Game>, String> {
-    // Step 1: Load credentials (release lock immediately)
+async fn sync_steam_library(state: State<'_, AppState>) -> Result<Vec<Game>, CommandError> {
+    // Step 1: Load credentials
     let steam_creds = {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let creds = storage.load_credentials().map_err(|e| e.to_string())?;
-        creds.steam.ok_or("Steam credentials not set")?
+        let creds = state.vault.load()?;
+        creds
+            .steam
+            .ok_or_else(|| LauncherError::NotConnected("Steam".to_string()))
+            .for_store(StoreType::Steam)?
     };
 
     // Step 2: Make network call on blocking thread pool (no lock needed - create fresh API instance)
@@ -143,40 +390,13 @@ async fn sync_steam_library(state: State<'_, AppState>) -> Result<Vec<Game>, Str
         let api = SteamApi::new();
         api.get_owned_games(&steam_creds)
     })
-    .await
-    .map_err(|e| e.to_string())?
-    .map_err(|e| e.to_string())?;
+    .await?
+    .for_store(StoreType::Steam)?;
 
     // Step 3: Get installed games
-    let installed_games = {
-        let library = state.library.lock().map_err(|e| e.to_string())?;
-        library.refresh_all().map_err(|e| e.to_string())?
-    };
-
-    // Create a map of installed Steam games by ID
-    let installed_map: HashMap<String, Game> = installed_games
-        .into_iter()
-        .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Steam))
-        .map(|g| (g.id.clone(), g))
-        .collect();
+    let installed_games = refresh_library(&state)?;
 
-    // Merge: mark owned games as installed if they are
-    let merged_games: Vec<Game> = owned_games
-        .into_iter()
-        .map(|mut game| {
-            if let Some(installed) = installed_map.get(&game.id) {
-                game.installed = true;
-                game.install_path = installed.install_path.clone();
-                game.executable = installed.executable.clone();
-                game.size_bytes = installed.size_bytes;
-                // Use name from installed game if API didn't provide one
-                if game.name.starts_with("App ") {
-                    game.name = installed.name.clone();
-                }
-            }
-            game
-        })
-        .collect();
+    let merged_games = merge_owned_games(owned_games, &installed_games, StoreType::Steam);
 
     // Step 4: Cache the results
     let now = SystemTime::now()
@@ -185,90 +405,107 @@ async fn sync_steam_library(state: State<'_, AppState>) -> Result<Vec<Game>, Str
         .unwrap_or(0);
 
     {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let mut cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-        cache.steam_owned = merged_games.clone();
-        cache.last_sync = Some(now);
-        storage
-            .save_games_cache(&cache)
-            .map_err(|e| e.to_string())?;
+        let storage = state.storage.lock()?;
+        storage.save_owned_games(StoreType::Steam, &merged_games, now)?;
     }
+    state.search.invalidate();
 
-    Ok(merged_games)
+    warm_artwork(&state, merged_games.clone());
+    with_artwork_overrides(&state, merged_games)
 }
 
 /// Get cached Steam games (without making API call)
 #[tauri::command]
-fn get_steam_games_cached(state: State<AppState>) -> Result<Vec<Game>, String> {
+fn get_steam_games_cached(state: State<AppState>) -> Result<Vec<Game>, CommandError> {
     // Load cache first
-    let cache = {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        storage.load_games_cache().map_err(|e| e.to_string())?
+    let owned = {
+        let storage = state.storage.lock()?;
+        storage.load_owned_games(StoreType::Steam)?
     };
 
     // Get installed games
-    let installed_games = {
-        let library = state.library.lock().map_err(|e| e.to_string())?;
-        library.refresh_all().map_err(|e| e.to_string())?
-    };
-
-    if cache.steam_owned.is_empty() {
-        // No cache, return installed games only
-        return Ok(installed_games
-            .into_iter()
-            .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Steam))
-            .collect());
-    }
-
-    // Create a map of installed Steam games
-    let installed_map: HashMap<String, Game> = installed_games
-        .into_iter()
-        .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Steam))
-        .map(|g| (g.id.clone(), g))
-        .collect();
+    let installed_games = refresh_library(&state)?;
 
-    let updated_games: Vec<Game> = cache
-        .steam_owned
-        .into_iter()
-        .map(|mut game| {
-            if let Some(installed) = installed_map.get(&game.id) {
-                game.installed = true;
-                game.install_path = installed.install_path.clone();
-                game.size_bytes = installed.size_bytes;
-                // Use name from installed game if API didn't provide one
-                if game.name.starts_with("App ") {
-                    game.name = installed.name.clone();
-                }
-            } else {
-                game.installed = false;
-            }
-            game
-        })
-        .collect();
-
-    Ok(updated_games)
+    let games = merge_cached_games(owned, &installed_games, StoreType::Steam);
+    with_artwork_overrides(&state, games)
 }
 
 /// Get last sync timestamp
 #[tauri::command]
-fn get_last_sync_time(state: State<AppState>) -> Result<Option<u64>, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-    Ok(cache.last_sync)
+fn get_last_sync_time(state: State<AppState>) -> Result<Option<u64>, CommandError> {
+    let storage = state.storage.lock()?;
+    storage
+        .last_sync(StoreType::Steam)
+        .map_err(CommandError::from)
 }
 
 /// Install a Steam game (opens Steam to download)
 #[tauri::command]
-fn install_steam_game(game_id: String) -> Result<(), String> {
+fn install_steam_game(game_id: String) -> Result<(), CommandError> {
     let store = SteamStore::new();
-    store.install_game(&game_id).map_err(|e| e.to_string())
+    store.install_game(&game_id).for_store(StoreType::Steam)
 }
 
 /// Disconnect Steam (clear credentials and cache)
 #[tauri::command]
-fn disconnect_steam(state: State<AppState>) -> Result<(), String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    storage.clear_steam_data().map_err(|e| e.to_string())
+fn disconnect_steam(state: State<AppState>) -> Result<(), CommandError> {
+    state.vault.update(|creds| creds.steam = None)?;
+    let storage = state.storage.lock()?;
+    storage.clear_steam_data()?;
+    state.search.invalidate();
+    Ok(())
+}
+
+// ============================================================================
+// Credential Vault Commands
+// ============================================================================
+
+/// Where credentials are kept, and whether the vault is locked
+#[tauri::command]
+fn get_vault_status(state: State<AppState>) -> VaultStatus {
+    state.vault.status()
+}
+
+/// Forget the credentials until the vault is unlocked again
+#[tauri::command]
+fn lock_vault(state: State<AppState>) {
+    state.vault.lock();
+    state.steamgriddb.set_api_key(None);
+}
+
+/// Unlock the vault (the passphrase is only needed if it has one)
+#[tauri::command]
+fn unlock_vault(state: State<AppState>, passphrase: Option<String>) -> Result<(), CommandError> {
+    state.vault.unlock(passphrase.as_deref())?;
+    {
+        let storage = state.storage.lock()?;
+        if let Err(e) = state.vault.import_plaintext(&storage) {
+            log::warn!("Credentials not moved into the vault yet: {e}");
+        }
+    }
+    let creds = state.vault.load()?;
+    state.steamgriddb.set_api_key(creds.steamgriddb_api_key);
+    Ok(())
+}
+
+/// Protect the vault with a passphrase, or with None stop asking for one
+#[tauri::command]
+fn set_vault_passphrase(
+    state: State<AppState>,
+    passphrase: Option<String>,
+) -> Result<(), CommandError> {
+    state
+        .vault
+        .set_passphrase(passphrase.as_deref())
+        .map_err(CommandError::from)
+}
+
+/// Erase all stored credentials (e.g. after forgetting the passphrase)
+#[tauri::command]
+fn reset_vault(state: State<AppState>) -> Result<(), CommandError> {
+    state.vault.reset()?;
+    state.steamgriddb.set_api_key(None);
+    Ok(())
 }
 
 // ============================================================================
@@ -286,55 +523,47 @@ fn get_epic_login_url() -> String {
 async fn exchange_epic_code(
     state: State<'_, AppState>,
     auth_code: String,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     // Step 1: Exchange code for tokens on blocking thread pool
     let credentials = tauri::async_runtime::spawn_blocking(move || {
         let api = EpicApi::new();
         api.exchange_code(&auth_code)
     })
-    .await
-    .map_err(|e| e.to_string())?
-    .map_err(|e| e.to_string())?;
+    .await?
+    .for_store(StoreType::Epic)?;
 
     let display_name = credentials.display_name.clone();
 
-    // Step 2: Save credentials (separate lock scope)
-    {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let mut stored_creds = storage.load_credentials().map_err(|e| e.to_string())?;
-        stored_creds.epic = Some(credentials);
-        storage
-            .save_credentials(&stored_creds)
-            .map_err(|e| e.to_string())?;
-    }
+    // Step 2: Save credentials
+    state.vault.update(|creds| creds.epic = Some(credentials))?;
 
     Ok(display_name)
 }
 
 /// Get Epic credentials (for checking connection status)
 #[tauri::command]
-fn get_epic_credentials(state: State<AppState>) -> Result<Option<EpicCredentials>, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let creds = storage.load_credentials().map_err(|e| e.to_string())?;
+fn get_epic_credentials(state: State<AppState>) -> Result<Option<EpicCredentials>, CommandError> {
+    let creds = state.vault.load()?;
     Ok(creds.epic)
 }
 
 /// Check if Epic is connected (has credentials)
 #[tauri::command]
-fn is_epic_connected(state: State<AppState>) -> Result<bool, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let creds = storage.load_credentials().map_err(|e| e.to_string())?;
+fn is_epic_connected(state: State<AppState>) -> Result<bool, CommandError> {
+    let creds = state.vault.load()?;
     Ok(creds.epic.is_some())
 }
 
 /// Sync Epic library (fetch owned games from API and merge with installed)
 #[tauri::command]
-async fn sync_epic_library(state: State<'_, AppState>) -> Result<Vec<Game>, String> {
-    // Load credentials (release lock immediately)
+async fn sync_epic_library(state: State<'_, AppState>) -> Result<Vec<Game>, CommandError> {
+    // Load credentials
     let epic_creds = {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let creds = storage.load_credentials().map_err(|e| e.to_string())?;
-        creds.epic.ok_or("Epic credentials not set")?
+        let creds = state.vault.load()?;
+        creds
+            .epic
+            .ok_or_else(|| LauncherError::NotConnected("Epic".to_string()))
+            .for_store(StoreType::Epic)?
     };
 
     // Check and refresh token if needed (on blocking thread pool)
@@ -343,18 +572,14 @@ async fn sync_epic_library(state: State<'_, AppState>) -> Result<Vec<Game>, Stri
         let api = EpicApi::new();
         api.ensure_valid_token(&creds_for_refresh)
     })
-    .await
-    .map_err(|e| e.to_string())?
-    .map_err(|e| e.to_string())?;
+    .await?
+    .for_store(StoreType::Epic)?;
 
     // Save refreshed credentials if they changed
     if valid_creds.access_token != epic_creds.access_token {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let mut stored_creds = storage.load_credentials().map_err(|e| e.to_string())?;
-        stored_creds.epic = Some(valid_creds.clone());
-        storage
-            .save_credentials(&stored_creds)
-            .map_err(|e| e.to_string())?;
+        state
+            .vault
+            .update(|creds| creds.epic = Some(valid_creds.clone()))?;
     }
 
     // Fetch owned games from Epic API (on blocking thread pool)
@@ -363,36 +588,13 @@ async fn sync_epic_library(state: State<'_, AppState>) -> Result<Vec<Game>, Stri
         let api = EpicApi::new();
         api.get_library(&creds_for_library)
     })
-    .await
-    .map_err(|e| e.to_string())?
-    .map_err(|e| e.to_string())?;
+    .await?
+    .for_store(StoreType::Epic)?;
 
     // Get installed games
-    let installed_games = {
-        let library = state.library.lock().map_err(|e| e.to_string())?;
-        library.refresh_all().map_err(|e| e.to_string())?
-    };
+    let installed_games = refresh_library(&state)?;
 
-    // Create a map of installed Epic games by ID
-    let installed_map: HashMap<String, Game> = installed_games
-        .into_iter()
-        .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Epic))
-        .map(|g| (g.id.clone(), g))
-        .collect();
-
-    // Merge: mark owned games as installed if they are
-    let merged_games: Vec<Game> = owned_games
-        .into_iter()
-        .map(|mut game| {
-            if let Some(installed) = installed_map.get(&game.id) {
-                game.installed = true;
-                game.install_path = installed.install_path.clone();
-                game.executable = installed.executable.clone();
-                game.size_bytes = installed.size_bytes;
-            }
-            game
-        })
-        .collect();
+    let merged_games = merge_owned_games(owned_games, &installed_games, StoreType::Epic);
 
     // Cache the results including metadata
     let now = SystemTime::now()
@@ -400,142 +602,85 @@ async fn sync_epic_library(state: State<'_, AppState>) -> Result<Vec<Game>, Stri
         .map(|d| d.as_secs())
         .unwrap_or(0);
 
-    // Convert EpicGameDetails to EpicGameMetadata for storage
-    let storage_metadata: HashMap<String, crate::storage::EpicGameMetadata> = metadata
-        .into_iter()
-        .map(|(id, details)| {
-            (
-                id,
-                crate::storage::EpicGameMetadata {
-                    description: details.description,
-                    developers: details.developers,
-                    publishers: details.publishers,
-                    genres: details.genres,
-                    platforms: details.platforms,
-                    release_date: details.release_date,
-                },
-            )
-        })
-        .collect();
+    let details = {
+        let storage = state.storage.lock()?;
+        storage.save_owned_games(StoreType::Epic, &merged_games, now)?;
+        sync::save_epic_metadata(&storage, metadata)?
+    };
 
-    {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        let mut cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-        cache.epic_owned = merged_games.clone();
-        cache.epic_last_sync = Some(now);
-        cache.epic_metadata = storage_metadata;
-        storage
-            .save_games_cache(&cache)
-            .map_err(|e| e.to_string())?;
+    if let Err(e) = state.metadata.store(details, now) {
+        log::warn!("Failed to cache Epic game details: {e}");
     }
+    state.search.invalidate();
 
-    Ok(merged_games)
+    warm_artwork(&state, merged_games.clone());
+    with_artwork_overrides(&state, merged_games)
 }
 
 /// Get cached Epic games (without making API call)
 #[tauri::command]
-fn get_epic_games_cached(state: State<AppState>) -> Result<Vec<Game>, String> {
+fn get_epic_games_cached(state: State<AppState>) -> Result<Vec<Game>, CommandError> {
     // Load cache first
-    let cache = {
-        let storage = state.storage.lock().map_err(|e| e.to_string())?;
-        storage.load_games_cache().map_err(|e| e.to_string())?
+    let owned = {
+        let storage = state.storage.lock()?;
+        storage.load_owned_games(StoreType::Epic)?
     };
 
     // Get installed games
-    let installed_games = {
-        let library = state.library.lock().map_err(|e| e.to_string())?;
-        library.refresh_all().map_err(|e| e.to_string())?
-    };
-
-    if cache.epic_owned.is_empty() {
-        // No cache, return installed games only
-        return Ok(installed_games
-            .into_iter()
-            .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Epic))
-            .collect());
-    }
-
-    // Create a map of installed Epic games
-    let installed_map: HashMap<String, Game> = installed_games
-        .into_iter()
-        .filter(|g| matches!(g.store, crate::launcher_core::StoreType::Epic))
-        .map(|g| (g.id.clone(), g))
-        .collect();
-
-    let updated_games: Vec<Game> = cache
-        .epic_owned
-        .into_iter()
-        .map(|mut game| {
-            if let Some(installed) = installed_map.get(&game.id) {
-                game.installed = true;
-                game.install_path = installed.install_path.clone();
-                game.size_bytes = installed.size_bytes;
-            } else {
-                game.installed = false;
-            }
-            game
-        })
-        .collect();
+    let installed_games = refresh_library(&state)?;
 
-    Ok(updated_games)
+    let games = merge_cached_games(owned, &installed_games, StoreType::Epic);
+    with_artwork_overrides(&state, games)
 }
 
 /// Get Epic last sync timestamp
 #[tauri::command]
-fn get_epic_last_sync_time(state: State<AppState>) -> Result<Option<u64>, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-    Ok(cache.epic_last_sync)
+fn get_epic_last_sync_time(state: State<AppState>) -> Result<Option<u64>, CommandError> {
+    let storage = state.storage.lock()?;
+    storage
+        .last_sync(StoreType::Epic)
+        .map_err(CommandError::from)
 }
 
 /// Disconnect Epic (clear credentials and cache)
 #[tauri::command]
-fn disconnect_epic(state: State<AppState>) -> Result<(), String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    storage.clear_epic_data().map_err(|e| e.to_string())
+fn disconnect_epic(state: State<AppState>) -> Result<(), CommandError> {
+    state.vault.update(|creds| creds.epic = None)?;
+    let storage = state.storage.lock()?;
+    storage.clear_epic_data()?;
+    state.search.invalidate();
+    Ok(())
 }
 
-/// Get Epic game details from cache
+/// Get Epic game details (kept for older frontends; see `get_details`)
 #[tauri::command]
-fn get_epic_game_details(
-    state: State<AppState>,
+async fn get_epic_game_details(
+    app: AppHandle,
+    state: State<'_, AppState>,
     game_id: String,
-) -> Result<Option<EpicGameDetails>, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-
-    // Look up metadata from cache
-    let metadata = cache.epic_metadata.get(&game_id);
-
-    Ok(metadata.map(|m| EpicGameDetails {
-        description: m.description.clone(),
-        developers: m.developers.clone(),
-        publishers: m.publishers.clone(),
-        genres: m.genres.clone(),
-        platforms: m.platforms.clone(),
-        release_date: m.release_date.clone(),
-    }))
+) -> Result<Option<GameDetails>, CommandError> {
+    load_details(app, &state, format!("epic:{game_id}")).await
 }
 
 /// Auto-detect Steam ID from local Steam installation
 #[tauri::command]
-fn detect_steam_id() -> Result<Option<String>, String> {
+fn detect_steam_id() -> Result<Option<String>, CommandError> {
     let paths = crate::stores::steam::SteamPaths::detect();
     Ok(paths.detect_steam_id())
 }
 
 /// Check if Steam is installed locally
 #[tauri::command]
-fn is_steam_installed() -> Result<bool, String> {
+fn is_steam_installed() -> Result<bool, CommandError> {
     let store = SteamStore::new();
     Ok(store.is_available())
 }
 
 /// Get play history for enriching game data on frontend
 #[tauri::command]
-fn get_play_history(state: State<AppState>) -> Result<PlayHistoryMap, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let history = storage.load_play_history().map_err(|e| e.to_string())?;
+fn get_play_history(state: State<AppState>) -> Result<PlayHistoryMap, CommandError> {
+    let storage = state.storage.lock()?;
+    let history = storage.load_play_history()?;
 
     // Convert to a simpler format: game_key -> (last_played, installed_at)
     Ok(history
@@ -545,52 +690,896 @@ fn get_play_history(state: State<AppState>) -> Result<PlayHistoryMap, String> {
         .collect())
 }
 
-/// Check if there's any sync data (to decide initial screen)
+// ============================================================================
+// Local Game Commands
+// ============================================================================
+
+/// Get all manually added games
 #[tauri::command]
-fn has_synced_library(state: State<AppState>) -> Result<bool, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    let cache = storage.load_games_cache().map_err(|e| e.to_string())?;
-    Ok(cache.last_sync.is_some()
-        || cache.epic_last_sync.is_some()
-        || !cache.steam_owned.is_empty()
-        || !cache.epic_owned.is_empty())
+fn get_local_games(state: State<AppState>) -> Result<Vec<LocalGame>, CommandError> {
+    let storage = state.storage.lock()?;
+    let local = storage.load_local_games()?;
+    Ok(local.games)
 }
 
-/// Get application settings
+/// Add a game that isn't from any store (its id is generated from the name)
 #[tauri::command]
-fn get_app_settings(state: State<AppState>) -> Result<AppSettings, String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    storage.load_settings().map_err(|e| e.to_string())
+fn add_local_game(state: State<AppState>, game: LocalGame) -> Result<LocalGame, CommandError> {
+    let added = {
+        let storage = state.storage.lock()?;
+        storage.add_local_game(game)?
+    };
+
+    let library = state.library.lock()?;
+    library.upsert_game(local::to_game(&added));
+    state.search.invalidate();
+    Ok(added)
 }
 
-/// Save application settings
+/// Edit a manually added game
+#[tauri::command]
+fn update_local_game(state: State<AppState>, game: LocalGame) -> Result<LocalGame, CommandError> {
+    let updated = {
+        let storage = state.storage.lock()?;
+        storage.update_local_game(game)?
+    };
+
+    let library = state.library.lock()?;
+    library.upsert_game(local::to_game(&updated));
+    state.search.invalidate();
+    Ok(updated)
+}
+
+/// Remove a manually added game
+#[tauri::command]
+fn remove_local_game(state: State<AppState>, game_id: String) -> Result<(), CommandError> {
+    let removed = {
+        let storage = state.storage.lock()?;
+        storage.remove_local_game(&game_id)?
+    };
+
+    let library = state.library.lock()?;
+    library.remove_game(&local::to_game(&removed).unique_key());
+    state.search.invalidate();
+    Ok(())
+}
+
+// ============================================================================
+// Collection Commands
+// ============================================================================
+
+/// Load collections, apply a change and save them
+fn update_collections<T>(
+    state: &State<AppState>,
+    change: impl FnOnce(&mut UserCollections) -> Result<T, LauncherError>,
+) -> Result<T, CommandError> {
+    let storage = state.storage.lock()?;
+    let mut collections = storage.load_collections()?;
+    let result = change(&mut collections)?;
+    storage.save_collections(&collections)?;
+    // Hidden games are left out of search
+    state.search.invalidate();
+    Ok(result)
+}
+
+/// Get favorites, hidden games and all user collections
+#[tauri::command]
+fn get_collections(state: State<AppState>) -> Result<UserCollections, CommandError> {
+    let storage = state.storage.lock()?;
+    storage.load_collections().map_err(CommandError::from)
+}
+
+/// Create a new empty collection
+#[tauri::command]
+fn create_collection(state: State<AppState>, name: String) -> Result<Collection, CommandError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    update_collections(&state, |c| c.create(&name, now))
+}
+
+/// Rename a collection
+#[tauri::command]
+fn rename_collection(
+    state: State<AppState>,
+    collection_id: String,
+    name: String,
+) -> Result<Collection, CommandError> {
+    update_collections(&state, |c| c.rename(&collection_id, &name))
+}
+
+/// Delete a collection
+#[tauri::command]
+fn delete_collection(state: State<AppState>, collection_id: String) -> Result<(), CommandError> {
+    update_collections(&state, |c| c.delete(&collection_id))
+}
+
+/// Add games (by unique key) to a collection
+#[tauri::command]
+fn add_games_to_collection(
+    state: State<AppState>,
+    collection_id: String,
+    game_keys: Vec<String>,
+) -> Result<Collection, CommandError> {
+    update_collections(&state, |c| c.add_games(&collection_id, &game_keys))
+}
+
+/// Remove games (by unique key) from a collection
+#[tauri::command]
+fn remove_games_from_collection(
+    state: State<AppState>,
+    collection_id: String,
+    game_keys: Vec<String>,
+) -> Result<Collection, CommandError> {
+    update_collections(&state, |c| c.remove_games(&collection_id, &game_keys))
+}
+
+/// Mark or unmark games as favorites
+#[tauri::command]
+fn set_favorite(
+    state: State<AppState>,
+    game_keys: Vec<String>,
+    favorite: bool,
+) -> Result<(), CommandError> {
+    update_collections(&state, |c| {
+        c.set_favorite(&game_keys, favorite);
+        Ok(())
+    })
+}
+
+/// Hide games from the library or show them again
+#[tauri::command]
+fn set_hidden(
+    state: State<AppState>,
+    game_keys: Vec<String>,
+    hidden: bool,
+) -> Result<(), CommandError> {
+    update_collections(&state, |c| {
+        c.set_hidden(&game_keys, hidden);
+        Ok(())
+    })
+}
+
+// ============================================================================
+// Smart Collection Commands
+// ============================================================================
+
+/// Cached store details keyed by unique key, in the shape queries and search use
+fn cached_game_metadata(
+    state: &State<AppState>,
+    cache: &GamesCache,
+) -> HashMap<String, GameMetadata> {
+    let to_metadata = |d: GameDetails| GameMetadata {
+        genres: d.genres.unwrap_or_default(),
+        developers: d.developers.unwrap_or_default(),
+        publishers: d.publishers.unwrap_or_default(),
+        description: d.description,
+    };
+
+    let mut metadata: HashMap<String, GameMetadata> = state
+        .metadata
+        .all_cached()
+        .into_iter()
+        .map(|(key, d)| (key, to_metadata(d)))
+        .collect();
+
+    // Epic metadata arrives with every sync, so it's never older than the details cache
+    metadata.extend(
+        cache
+            .epic_metadata
+            .iter()
+            .map(|(id, m)| (format!("epic:{id}"), to_metadata(m.clone().into()))),
+    );
+    metadata
+}
+
+/// Gather the play data, metadata and collections a query can filter on
+fn build_query_context(state: &State<AppState>) -> Result<QueryContext, CommandError> {
+    let storage = state.storage.lock()?;
+    let history = storage.load_play_history()?;
+    let sessions = storage.load_play_sessions()?;
+    let cache = storage.load_games_cache()?;
+    let collections = storage.load_collections()?;
+
+    let last_played = history
+        .games
+        .into_iter()
+        .filter_map(|(key, entry)| Some((key, entry.last_played?)))
+        .collect();
+
+    let mut playtime_secs: HashMap<String, u64> = HashMap::new();
+    for session in &sessions.sessions {
+        *playtime_secs.entry(session.game_key.clone()).or_default() += session.duration_secs();
+    }
+    let playtime_minutes = playtime_secs
+        .into_iter()
+        .map(|(key, secs)| (key, secs / 60))
+        .collect();
+
+    Ok(QueryContext {
+        now: now_secs() as u64,
+        metadata: cached_game_metadata(state, &cache),
+        last_played,
+        playtime_minutes,
+        collections,
+    })
+}
+
+/// Run a parsed query over owned and installed games. Hidden games only show
+/// up when asked for, either via `include_hidden` or a `hidden:` term in the query.
+fn run_query(
+    state: &State<AppState>,
+    query: &Query,
+    include_hidden: bool,
+) -> Result<Vec<Game>, CommandError> {
+    let cache = {
+        let storage = state.storage.lock()?;
+        storage.load_games_cache()?
+    };
+    let installed_games = {
+        let library = state.library.lock()?;
+        library.get_games()
+    };
+    let games = combine_games(cache, installed_games);
+    let ctx = build_query_context(state)?;
+
+    let show_hidden = include_hidden || query.mentions_hidden();
+    let games = query
+        .filter(games, &ctx)
+        .into_iter()
+        .filter(|g| show_hidden || !ctx.collections.is_hidden(&g.unique_key()))
+        .collect();
+    with_artwork_overrides(state, games)
+}
+
+/// Check that a query parses; the error describes the first problem found
+#[tauri::command]
+fn validate_query(query: String) -> Result<(), CommandError> {
+    Query::parse(&query).map(|_| ()).map_err(CommandError::from)
+}
+
+/// Get the games matching a query like `store:epic installed:true playtime:<60`
+#[tauri::command]
+fn query_games(
+    state: State<AppState>,
+    query: String,
+    include_hidden: Option<bool>,
+) -> Result<Vec<Game>, CommandError> {
+    let query = Query::parse(&query)?;
+    run_query(&state, &query, include_hidden.unwrap_or(false))
+}
+
+/// Get the built-in smart collections followed by the user's own
+#[tauri::command]
+fn get_smart_collections(state: State<AppState>) -> Result<Vec<SmartCollection>, CommandError> {
+    let storage = state.storage.lock()?;
+    let collections = storage.load_collections()?;
+    Ok(collections.smart_collections())
+}
+
+/// Save a query as a smart collection
 #[tauri::command]
-fn save_app_settings(state: State<AppState>, settings: AppSettings) -> Result<(), String> {
-    let storage = state.storage.lock().map_err(|e| e.to_string())?;
-    storage.save_settings(&settings).map_err(|e| e.to_string())
+fn create_smart_collection(
+    state: State<AppState>,
+    name: String,
+    query: String,
+) -> Result<SmartCollection, CommandError> {
+    let now = now_secs() as u64;
+    update_collections(&state, |c| c.create_smart(&name, &query, now))
+}
+
+/// Change a smart collection's name and query
+#[tauri::command]
+fn update_smart_collection(
+    state: State<AppState>,
+    collection_id: String,
+    name: String,
+    query: String,
+) -> Result<SmartCollection, CommandError> {
+    update_collections(&state, |c| c.update_smart(&collection_id, &name, &query))
 }
 
-/// Get detailed game information from Steam Store API
+/// Delete a user-defined smart collection
 #[tauri::command]
-fn get_game_details(
+fn delete_smart_collection(
     state: State<AppState>,
+    collection_id: String,
+) -> Result<(), CommandError> {
+    update_collections(&state, |c| c.delete_smart(&collection_id))
+}
+
+/// Get the games currently matching a smart collection
+#[tauri::command]
+fn get_smart_collection_games(
+    state: State<AppState>,
+    collection_id: String,
+) -> Result<Vec<Game>, CommandError> {
+    let collection = {
+        let storage = state.storage.lock()?;
+        let collections = storage.load_collections()?;
+        collections.get_smart(&collection_id).ok_or_else(|| {
+            CommandError::invalid_input(format!("Collection not found: {collection_id}"))
+        })?
+    };
+
+    let query = Query::parse(&collection.query)?;
+    run_query(&state, &query, false)
+}
+
+// ============================================================================
+// Game Details Commands
+// ============================================================================
+
+/// Serve details from the cache, announcing background refreshes with a
+/// "game-details-updated" event
+async fn load_details(
+    app: AppHandle,
+    state: &State<'_, AppState>,
+    game_key: String,
+) -> Result<Option<GameDetails>, CommandError> {
+    // A cache miss fetches from the store; don't block the main thread
+    let metadata = Arc::clone(&state.metadata);
+    let details = tauri::async_runtime::spawn_blocking(move || {
+        let key = game_key.clone();
+        metadata
+            .get_details(&game_key, now_secs() as u64, move |details| {
+                // Developers and genres are searchable
+                app.state::<AppState>().search.invalidate();
+                let update = DetailsUpdate {
+                    game_key: key,
+                    details,
+                };
+                if let Err(e) = app.emit("game-details-updated", &update) {
+                    log::warn!("Failed to emit game-details-updated: {e}");
+                }
+            })
+            .map_err(CommandError::from)
+    })
+    .await??;
+    // The details may have just been fetched
+    state.search.invalidate();
+    Ok(details)
+}
+
+/// Get details (description, developers, genres, ...) for any game by unique key
+#[tauri::command]
+async fn get_details(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    game_key: String,
+) -> Result<Option<GameDetails>, CommandError> {
+    load_details(app, &state, game_key).await
+}
+
+/// Get Steam game details (kept for older frontends; see `get_details`)
+#[tauri::command]
+async fn get_game_details(
+    app: AppHandle,
+    state: State<'_, AppState>,
     game_id: String,
-) -> Result<Option<GameDetails>, String> {
-    let steam_api = state.steam_api.lock().map_err(|e| e.to_string())?;
-    steam_api
-        .get_game_details(&game_id)
-        .map_err(|e| e.to_string())
+) -> Result<Option<GameDetails>, CommandError> {
+    load_details(app, &state, format!("steam:{game_id}")).await
+}
+
+// ============================================================================
+// Artwork Commands
+// ============================================================================
+
+/// Download artwork for the given games in the background
+fn warm_artwork(state: &State<AppState>, games: Vec<Game>) {
+    let artwork = Arc::clone(&state.artwork);
+    let resolver = Arc::clone(&state.resolver);
+    std::thread::spawn(move || {
+        let now = now_secs();
+        let summary = artwork.warm(&games, |game, art| resolver.resolve(game, art, now));
+        if let Err(e) = resolver.flush() {
+            log::warn!("Failed to save artwork probes: {e}");
+        }
+        log::info!(
+            "Artwork cache warmed: {} downloaded, {} already cached, {} generated, {} failed",
+            summary.downloaded,
+            summary.already_cached,
+            summary.generated,
+            summary.failed
+        );
+    });
+}
+
+/// Find a game in the library or the sync cache
+fn find_artwork_game(state: &AppState, game_key: &str) -> Option<Game> {
+    let installed = state
+        .library
+        .lock()
+        .ok()
+        .and_then(|library| library.find_game(game_key));
+
+    installed.or_else(|| {
+        let storage = state.storage.lock().ok()?;
+        storage.find_owned_game(game_key).ok()?
+    })
+}
+
+/// Handle a `tenfoot-art://` request: serve the cached image, or download it
+/// on a miss. Artwork with no working URL gets a generated placeholder.
+fn serve_artwork(app: &AppHandle, uri: &str) -> Response<Vec<u8>> {
+    let respond = |status: StatusCode| {
+        Response::builder()
+            .status(status)
+            .body(Vec::new())
+            .unwrap_or_default()
+    };
+
+    let Some(request) = artwork::parse_art_uri(uri) else {
+        return respond(StatusCode::BAD_REQUEST);
+    };
+    let (game_key, art_type) = (request.game_key, request.art_type);
+    let state = app.state::<AppState>();
+
+    // Custom artwork wins over everything; small images have no thumbnail,
+    // so the full image is served instead
+    let cached = find_custom_artwork(&state, &game_key, art_type)
+        .or_else(|| {
+            request
+                .thumbnail
+                .then(|| state.artwork.get_thumbnail(&game_key, art_type))
+                .flatten()
+        })
+        .or_else(|| state.artwork.get(&game_key, art_type))
+        .or_else(|| {
+            let game = find_artwork_game(&state, &game_key)?;
+            let now = now_secs();
+
+            let fetched = state
+                .resolver
+                .resolve(&game, art_type, now)
+                .and_then(|url| match state.artwork.fetch(&game_key, art_type, &url) {
+                    Ok(artwork) => Some(artwork),
+                    Err(e) => {
+                        log::debug!("Failed to download artwork for {game_key}: {e}");
+                        // A network error may be temporary; anything else means
+                        // the URL doesn't serve a usable image
+                        if !matches!(e, LauncherError::NetworkError(_)) {
+                            state.resolver.reject(&game_key, art_type, &url, now);
+                        }
+                        None
+                    }
+                });
+            if let Err(e) = state.resolver.flush() {
+                log::warn!("Failed to save artwork probes: {e}");
+            }
+
+            fetched.or_else(|| match state.artwork.store_placeholder(&game, art_type) {
+                Ok(artwork) => Some(artwork),
+                Err(e) => {
+                    log::warn!("Failed to cache placeholder artwork for {game_key}: {e}");
+                    Some(artwork::render_placeholder(&game, art_type))
+                }
+            })
+        });
+
+    match cached {
+        Some(artwork) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, artwork.content_type)
+            .header(header::CACHE_CONTROL, "max-age=3600")
+            .body(artwork.bytes)
+            .unwrap_or_default(),
+        None => respond(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Read the custom image the user picked for a game's artwork, if any
+fn find_custom_artwork(
+    state: &AppState,
+    game_key: &str,
+    art_type: ArtworkType,
+) -> Option<CachedArtwork> {
+    let storage = state.storage.lock().ok()?;
+    let overrides = storage.load_artwork_overrides().ok()?;
+    let custom = overrides.get(game_key, art_type)?;
+    match artwork::read_custom_artwork(storage.data_dir(), custom) {
+        Ok(artwork) => Some(artwork),
+        Err(e) => {
+            log::warn!("Failed to serve custom artwork for {game_key}: {e}");
+            None
+        }
+    }
+}
+
+/// Point the games' artwork at any custom images the user picked
+fn with_artwork_overrides(
+    state: &AppState,
+    mut games: Vec<Game>,
+) -> Result<Vec<Game>, CommandError> {
+    let storage = state.storage.lock()?;
+    let overrides = storage.load_artwork_overrides()?;
+    artwork::apply_artwork_overrides(&mut games, &overrides);
+    Ok(games)
+}
+
+/// Replace a game's artwork with a local image file or an image URL. The
+/// image is copied into the data dir.
+#[tauri::command]
+async fn set_custom_artwork(
+    state: State<'_, AppState>,
+    game_key: String,
+    art_type: String,
+    source: String,
+) -> Result<CustomArtwork, CommandError> {
+    let art_type = ArtworkType::from_name(&art_type)
+        .ok_or_else(|| CommandError::invalid_input(format!("Unknown artwork type: {art_type}")))?;
+
+    // Reading a URL is a network call; don't block the main thread
+    let cache = Arc::clone(&state.artwork);
+    let from = source.clone();
+    let bytes =
+        tauri::async_runtime::spawn_blocking(move || artwork::load_custom_source(&cache, &from))
+            .await??;
+
+    let storage = state.storage.lock()?;
+    let mut overrides = storage.load_artwork_overrides()?;
+    let custom = artwork::import_custom_artwork(
+        storage.data_dir(),
+        &game_key,
+        art_type,
+        &source,
+        &bytes,
+        now_secs(),
+    )?;
+
+    if let Some(old) = overrides.set(&game_key, art_type, custom.clone()) {
+        if old.file != custom.file {
+            artwork::remove_custom_artwork(storage.data_dir(), &old);
+        }
+    }
+    storage.save_artwork_overrides(&overrides)?;
+    Ok(custom)
+}
+
+/// Go back to the store's artwork for a game
+#[tauri::command]
+fn clear_custom_artwork(
+    state: State<AppState>,
+    game_key: String,
+    art_type: String,
+) -> Result<(), CommandError> {
+    let art_type = ArtworkType::from_name(&art_type)
+        .ok_or_else(|| CommandError::invalid_input(format!("Unknown artwork type: {art_type}")))?;
+
+    let storage = state.storage.lock()?;
+    let mut overrides = storage.load_artwork_overrides()?;
+    if let Some(old) = overrides.remove(&game_key, art_type) {
+        artwork::remove_custom_artwork(storage.data_dir(), &old);
+        storage.save_artwork_overrides(&overrides)?;
+    }
+    Ok(())
+}
+
+/// Get a game's custom artwork, keyed by artwork type
+#[tauri::command]
+fn get_custom_artwork(
+    state: State<AppState>,
+    game_key: String,
+) -> Result<HashMap<String, CustomArtwork>, CommandError> {
+    let storage = state.storage.lock()?;
+    let mut overrides = storage.load_artwork_overrides()?;
+    Ok(overrides.games.remove(&game_key).unwrap_or_default())
+}
+
+/// List the alternative artwork the providers have for a game. Picking one
+/// goes through `set_custom_artwork` with the candidate's URL.
+#[tauri::command]
+async fn get_artwork_candidates(
+    state: State<'_, AppState>,
+    game_key: String,
+    art_type: String,
+) -> Result<Vec<ArtworkCandidate>, CommandError> {
+    let art_type = ArtworkType::from_name(&art_type)
+        .ok_or_else(|| CommandError::invalid_input(format!("Unknown artwork type: {art_type}")))?;
+    let game = find_artwork_game(&state, &game_key)
+        .ok_or_else(|| LauncherError::GameNotFound(game_key.clone()))?;
+
+    let resolver = Arc::clone(&state.resolver);
+    tauri::async_runtime::spawn_blocking(move || resolver.browse(&game, art_type))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Save (or with None, remove) the SteamGridDB API key
+#[tauri::command]
+fn set_steamgriddb_api_key(
+    state: State<AppState>,
+    api_key: Option<String>,
+) -> Result<(), CommandError> {
+    let api_key = api_key.filter(|k| !k.trim().is_empty());
+    state
+        .vault
+        .update(|creds| creds.steamgriddb_api_key = api_key.clone())?;
+    state.steamgriddb.set_api_key(api_key);
+    Ok(())
+}
+
+/// Check if a SteamGridDB API key is set
+#[tauri::command]
+fn has_steamgriddb_api_key(state: State<AppState>) -> bool {
+    state.steamgriddb.has_api_key()
+}
+
+/// Get the colors, blurhash and thumbnail info of the games' cached cover and hero
+#[tauri::command]
+fn get_artwork_info(
+    state: State<AppState>,
+    game_keys: Vec<String>,
+) -> HashMap<String, GameArtworkInfo> {
+    game_keys
+        .into_iter()
+        .map(|key| {
+            let info = state.artwork.game_info(&key);
+            (key, info)
+        })
+        .collect()
+}
+
+/// Get the total size of the artwork cache in bytes
+#[tauri::command]
+fn get_artwork_cache_size(state: State<AppState>) -> u64 {
+    state.artwork.total_bytes()
+}
+
+/// Delete all cached artwork (it will be downloaded again as needed)
+#[tauri::command]
+fn clear_artwork_cache(state: State<AppState>) -> Result<(), CommandError> {
+    state.artwork.clear()?;
+    state.resolver.clear().map_err(CommandError::from)
+}
+
+// ============================================================================
+// Search Commands
+// ============================================================================
+
+/// Index the owned and installed games from the last scan, with their metadata
+fn build_library_search(state: &State<AppState>) -> Result<LibrarySearch, CommandError> {
+    let (cache, collections) = {
+        let storage = state.storage.lock()?;
+        (storage.load_games_cache()?, storage.load_collections()?)
+    };
+    let installed_games = {
+        let library = state.library.lock()?;
+        library.get_games()
+    };
+
+    let metadata = cached_game_metadata(state, &cache);
+    let games = combine_games(cache, installed_games);
+    let index = SearchIndex::build(&games, &metadata);
+    Ok(LibrarySearch::new(
+        index,
+        collections.hidden.into_iter().collect(),
+    ))
+}
+
+/// Search owned and installed games by title, developer, genre and description.
+/// The index is kept until the library changes, so it's cheap to call while typing.
+#[tauri::command]
+fn search_games(
+    state: State<AppState>,
+    query: String,
+    limit: Option<usize>,
+    include_hidden: Option<bool>,
+) -> Result<Vec<SearchResult>, CommandError> {
+    let search = state.search.get_or_build(|| build_library_search(&state))?;
+    Ok(search.search(
+        &query,
+        limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+        include_hidden.unwrap_or(false),
+    ))
+}
+
+// ============================================================================
+// Unified Library Commands
+// ============================================================================
+
+/// Collect every known game: cached owned games merged with locally installed ones.
+/// Uses the last scan rather than rescanning the stores.
+fn collect_all_games(state: &State<AppState>) -> Result<Vec<Game>, CommandError> {
+    let cache = {
+        let storage = state.storage.lock()?;
+        storage.load_games_cache()?
+    };
+
+    let installed_games = {
+        let library = state.library.lock()?;
+        library.get_games()
+    };
+
+    Ok(combine_games(cache, installed_games))
+}
+
+/// Load all games grouped across stores using the saved links
+fn load_unified_games(state: &State<AppState>) -> Result<Vec<UnifiedGame>, CommandError> {
+    let games = with_artwork_overrides(state, collect_all_games(state)?)?;
+    let storage = state.storage.lock()?;
+    let links = storage.load_game_links()?;
+    Ok(dedup::group_games(games, &links))
+}
+
+/// Get all games with entries for the same title on different stores merged
+#[tauri::command]
+fn get_unified_games(state: State<AppState>) -> Result<Vec<UnifiedGame>, CommandError> {
+    load_unified_games(&state)
+}
+
+/// Manually link game entries (store:id keys) as the same game
+#[tauri::command]
+fn link_games(state: State<AppState>, game_keys: Vec<String>) -> Result<(), CommandError> {
+    if game_keys.len() < 2 {
+        return Err(CommandError::invalid_input(
+            "At least two games are required to link",
+        ));
+    }
+
+    let storage = state.storage.lock()?;
+    let mut links = storage.load_game_links()?;
+    links.link(&game_keys);
+    storage.save_game_links(&links).map_err(CommandError::from)
+}
+
+/// Split a game entry out of its unified group
+#[tauri::command]
+fn unlink_game(state: State<AppState>, game_key: String) -> Result<(), CommandError> {
+    let storage = state.storage.lock()?;
+    let mut links = storage.load_game_links()?;
+    links.unlink(&game_key);
+    storage.save_game_links(&links).map_err(CommandError::from)
+}
+
+/// Choose which store entry a unified game launches from
+#[tauri::command]
+fn set_preferred_source(state: State<AppState>, game_key: String) -> Result<(), CommandError> {
+    let unified = load_unified_games(&state)?;
+    let group = unified
+        .iter()
+        .find(|g| g.has_source(&game_key))
+        .ok_or_else(|| LauncherError::GameNotFound(game_key.clone()))?;
+
+    let storage = state.storage.lock()?;
+    let mut links = storage.load_game_links()?;
+    links.set_preferred(&game_key, &group.source_keys());
+    storage.save_game_links(&links).map_err(CommandError::from)
+}
+
+// ============================================================================
+// Playtime Statistics Commands
+// ============================================================================
+
+/// Load the locally recorded play sessions
+fn load_play_sessions(state: &State<AppState>) -> Result<Vec<PlaySession>, CommandError> {
+    let storage = state.storage.lock()?;
+    let sessions = storage.load_play_sessions()?;
+    Ok(sessions.sessions)
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Get playtime for today, this week and this month plus longest sessions and streaks.
+/// `utc_offset_minutes` is the local offset from UTC (e.g. 120 for UTC+2).
+#[tauri::command]
+fn get_playtime_summary(
+    state: State<AppState>,
+    game_key: Option<String>,
+    utc_offset_minutes: Option<i64>,
+) -> Result<PlaytimeSummary, CommandError> {
+    let sessions = load_play_sessions(&state)?;
+    let mut stats = PlaytimeStats::new(&sessions, utc_offset_minutes.unwrap_or(0) * 60);
+    if let Some(game_key) = &game_key {
+        stats = stats.for_game(game_key);
+    }
+    Ok(stats.summary(now_secs()))
+}
+
+/// Get playtime per game, optionally only counting time played since a timestamp
+#[tauri::command]
+fn get_playtime_by_game(
+    state: State<AppState>,
+    since: Option<u64>,
+) -> Result<Vec<GamePlaytime>, CommandError> {
+    let mut sessions = load_play_sessions(&state)?;
+    if let Some(since) = since {
+        sessions = stats::sessions_since(&sessions, since);
+    }
+    Ok(PlaytimeStats::new(&sessions, 0).per_game())
+}
+
+/// Get playtime per store, optionally only counting time played since a timestamp
+#[tauri::command]
+fn get_playtime_by_store(
+    state: State<AppState>,
+    since: Option<u64>,
+) -> Result<Vec<StorePlaytime>, CommandError> {
+    let mut sessions = load_play_sessions(&state)?;
+    if let Some(since) = since {
+        sessions = stats::sessions_since(&sessions, since);
+    }
+    Ok(PlaytimeStats::new(&sessions, 0).per_store())
+}
+
+/// Get playtime bucketed by day, week or month, optionally for a single game
+#[tauri::command]
+fn get_playtime_history(
+    state: State<AppState>,
+    period: StatsPeriod,
+    game_key: Option<String>,
+    utc_offset_minutes: Option<i64>,
+) -> Result<Vec<PeriodPlaytime>, CommandError> {
+    let sessions = load_play_sessions(&state)?;
+    let mut stats = PlaytimeStats::new(&sessions, utc_offset_minutes.unwrap_or(0) * 60);
+    if let Some(game_key) = &game_key {
+        stats = stats.for_game(game_key);
+    }
+    Ok(stats.by_period(period))
+}
+
+/// Get the longest recorded play sessions, optionally for a single game
+#[tauri::command]
+fn get_longest_sessions(
+    state: State<AppState>,
+    game_key: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<PlaySession>, CommandError> {
+    let sessions = load_play_sessions(&state)?;
+    let mut stats = PlaytimeStats::new(&sessions, 0);
+    if let Some(game_key) = &game_key {
+        stats = stats.for_game(game_key);
+    }
+    Ok(stats.longest_sessions(limit.unwrap_or(10)))
+}
+
+/// Check if there's any sync data (to decide initial screen)
+#[tauri::command]
+fn has_synced_library(state: State<AppState>) -> Result<bool, CommandError> {
+    let storage = state.storage.lock()?;
+    storage.has_synced_library().map_err(CommandError::from)
+}
+
+/// Get application settings
+#[tauri::command]
+fn get_app_settings(state: State<AppState>) -> Result<AppSettings, CommandError> {
+    let storage = state.storage.lock()?;
+    storage.load_settings().map_err(CommandError::from)
+}
+
+/// Save application settings
+#[tauri::command]
+fn save_app_settings(state: State<AppState>, settings: AppSettings) -> Result<(), CommandError> {
+    if settings.store_timeout_secs == Some(0) {
+        return Err(CommandError::invalid_input(
+            "The store scan timeout must be at least one second",
+        ));
+    }
+    {
+        let mut library = state.library.lock()?;
+        apply_library_settings(&mut library, &settings);
+    }
+    apply_artwork_settings(&state.artwork, &settings);
+    logging::set_level(settings.log_level.unwrap_or_default());
+    let storage = state.storage.lock()?;
+    storage.save_settings(&settings).map_err(CommandError::from)
 }
 
 /// Set auto-launch on startup
 #[tauri::command]
-fn set_autolaunch(enabled: bool) -> Result<(), String> {
+fn set_autolaunch(enabled: bool) -> Result<(), CommandError> {
     #[cfg(target_os = "macos")]
     {
         use std::process::Command;
 
-        let app_path =
-            std::env::current_exe().map_err(|e| format!("Failed to get app path: {e}"))?;
+        let app_path = std::env::current_exe()
+            .map_err(|e| LauncherError::IoError(format!("Failed to get app path: {e}")))?;
 
         if enabled {
             // Add to login items using osascript
@@ -601,7 +1590,7 @@ fn set_autolaunch(enabled: bool) -> Result<(), String> {
             Command::new("osascript")
                 .args(["-e", &script])
                 .output()
-                .map_err(|e| format!("Failed to add login item: {e}"))?;
+                .map_err(|e| LauncherError::IoError(format!("Failed to add login item: {e}")))?;
         } else {
             // Remove from login items
             let app_name = app_path
@@ -620,8 +1609,8 @@ fn set_autolaunch(enabled: bool) -> Result<(), String> {
     {
         use std::process::Command;
 
-        let app_path =
-            std::env::current_exe().map_err(|e| format!("Failed to get app path: {e}"))?;
+        let app_path = std::env::current_exe()
+            .map_err(|e| LauncherError::IoError(format!("Failed to get app path: {e}")))?;
 
         let reg_path = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
         let app_name = "GameLauncher";
@@ -640,7 +1629,7 @@ fn set_autolaunch(enabled: bool) -> Result<(), String> {
                     "/f",
                 ])
                 .output()
-                .map_err(|e| format!("Failed to add registry key: {e}"))?;
+                .map_err(|e| LauncherError::IoError(format!("Failed to add registry key: {e}")))?;
         } else {
             let _ = Command::new("reg")
                 .args(["delete", reg_path, "/v", app_name, "/f"])
@@ -653,26 +1642,27 @@ fn set_autolaunch(enabled: bool) -> Result<(), String> {
     {
         use std::fs;
 
-        let autostart_dir = dirs::config_dir()
-            .ok_or("Could not find config directory")?
-            .join("autostart");
-
-        let desktop_file = autostart_dir.join("tenfoot.desktop");
+        let desktop_file = diagnostics::autostart_file().ok_or_else(|| {
+            LauncherError::ConfigError("Could not find config directory".to_string())
+        })?;
+        let autostart_dir = desktop_file.parent().unwrap_or(&desktop_file).to_path_buf();
 
         if enabled {
-            let app_path =
-                std::env::current_exe().map_err(|e| format!("Failed to get app path: {e}"))?;
+            let app_path = std::env::current_exe()
+                .map_err(|e| LauncherError::IoError(format!("Failed to get app path: {e}")))?;
 
-            fs::create_dir_all(&autostart_dir)
-                .map_err(|e| format!("Failed to create autostart dir: {e}"))?;
+            fs::create_dir_all(&autostart_dir).map_err(|e| {
+                LauncherError::IoError(format!("Failed to create autostart dir: {e}"))
+            })?;
 
             let app_display = app_path.display();
             let content = format!(
                 "[Desktop Entry]\nType=Application\nName=TenFoot\nExec={app_display}\nX-GNOME-Autostart-enabled=true"
             );
 
-            fs::write(&desktop_file, content)
-                .map_err(|e| format!("Failed to write desktop file: {e}"))?;
+            fs::write(&desktop_file, content).map_err(|e| {
+                LauncherError::IoError(format!("Failed to write desktop file: {e}"))
+            })?;
         } else {
             let _ = fs::remove_file(&desktop_file);
         }
@@ -680,11 +1670,75 @@ fn set_autolaunch(enabled: bool) -> Result<(), String> {
     }
 }
 
+// ============================================================================
+// Diagnostics Commands
+// ============================================================================
+
+/// Check everything TenFoot depends on: data files, store installs and
+/// libraries, launch tools and autostart
+#[tauri::command]
+fn run_diagnostics(state: State<AppState>) -> Result<DoctorReport, CommandError> {
+    let stores = load_store_statuses(&state)?;
+    let storage = state.storage.lock()?;
+    Ok(Doctor::detect().run(&storage, stores))
+}
+
+/// Run the diagnostics and zip the report with settings and data files
+/// (secrets redacted) for a bug report. Returns where the bundle was saved.
+#[tauri::command]
+fn create_support_bundle(state: State<AppState>) -> Result<String, CommandError> {
+    let stores = load_store_statuses(&state)?;
+    let storage = state.storage.lock()?;
+    let report = Doctor::detect().run(&storage, stores);
+
+    let dir = dirs::download_dir().unwrap_or_else(|| storage.data_dir().to_path_buf());
+    let path = dir.join(format!("tenfoot-support-{}.zip", report.generated_at));
+    diagnostics::write_support_bundle(&path, &report, &storage)?;
+    log::info!("Wrote support bundle to {}", path.display());
+    Ok(path.display().to_string())
+}
+
+/// Number of log entries the log viewer gets unless it asks for a limit
+const DEFAULT_LOG_ENTRY_LIMIT: usize = 500;
+
+/// The most recent log entries, optionally only those at or above a level
+/// and from modules whose path contains `module`
+#[tauri::command]
+fn get_log_entries(
+    state: State<AppState>,
+    level: Option<LogLevel>,
+    module: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<LogEntry>, CommandError> {
+    let data_dir = state.storage.lock()?.data_dir().to_path_buf();
+    let module = module.as_deref().filter(|m| !m.is_empty());
+    Ok(logging::read_entries(
+        &data_dir,
+        level,
+        module,
+        limit.unwrap_or(DEFAULT_LOG_ENTRY_LIMIT),
+    ))
+}
+
+/// Log to stderr and the data dir's log files, at info until the settings
+/// are loaded
+fn init_logging() {
+    let data_dir = storage::default_data_dir().ok();
+    logging::init(data_dir.as_deref(), LogLevel::default());
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    init_logging();
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(AppState::new())
+        .register_asynchronous_uri_scheme_protocol(ART_PROTOCOL, |ctx, request, responder| {
+            // Downloads can take a while, so stay off the webview thread
+            let app = ctx.app_handle().clone();
+            let uri = request.uri().to_string();
+            std::thread::spawn(move || responder.respond(serve_artwork(&app, &uri)));
+        })
         .setup(|app| {
             // Load settings and apply fullscreen if enabled
             if let Ok(storage) = Storage::new() {
@@ -696,12 +1750,16 @@ pub fn run() {
                     }
                 }
             }
+            start_library_watcher(app.handle());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_installed_games,
             get_games,
+            get_store_scan_results,
+            get_store_status,
             launch_game,
+            get_running_game,
             get_available_stores,
             find_game,
             // Steam commands
@@ -715,6 +1773,12 @@ pub fn run() {
             disconnect_steam,
             detect_steam_id,
             is_steam_installed,
+            // Credential vault commands
+            get_vault_status,
+            lock_vault,
+            unlock_vault,
+            set_vault_passphrase,
+            reset_vault,
             // Epic commands
             get_epic_login_url,
             exchange_epic_code,
@@ -725,13 +1789,63 @@ pub fn run() {
             get_epic_last_sync_time,
             disconnect_epic,
             get_epic_game_details,
+            // Local game commands
+            get_local_games,
+            add_local_game,
+            update_local_game,
+            remove_local_game,
+            // Collection commands
+            get_collections,
+            create_collection,
+            rename_collection,
+            delete_collection,
+            add_games_to_collection,
+            remove_games_from_collection,
+            set_favorite,
+            set_hidden,
+            // Smart collection commands
+            validate_query,
+            query_games,
+            get_smart_collections,
+            create_smart_collection,
+            update_smart_collection,
+            delete_smart_collection,
+            get_smart_collection_games,
+            // Game details commands
+            get_details,
+            // Artwork commands
+            get_artwork_info,
+            set_custom_artwork,
+            clear_custom_artwork,
+            get_custom_artwork,
+            get_artwork_candidates,
+            set_steamgriddb_api_key,
+            has_steamgriddb_api_key,
+            get_artwork_cache_size,
+            clear_artwork_cache,
+            // Search commands
+            search_games,
+            // Unified library commands
+            get_unified_games,
+            link_games,
+            unlink_game,
+            set_preferred_source,
             // General commands
             get_play_history,
+            get_playtime_summary,
+            get_playtime_by_game,
+            get_playtime_by_store,
+            get_playtime_history,
+            get_longest_sessions,
             has_synced_library,
             get_app_settings,
             save_app_settings,
             set_autolaunch,
             get_game_details,
+            // Diagnostics commands
+            run_diagnostics,
+            create_support_bundle,
+            get_log_entries,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -759,7 +1873,7 @@ mod tests {
     fn test_app_state_has_stores() {
         let state = AppState::new();
         let library = state.library.lock().unwrap();
-        // Should have 3 stores registered (Steam, Epic, GOG)
-        assert_eq!(library.store_count(), 3);
+        // Should have 4 stores registered (Steam, Epic, GOG, Local)
+        assert_eq!(library.store_count(), 4);
     }
 }
@@ -0,0 +1,420 @@
+//! Log output: every record goes to stderr and to rotating files in the data
+//! dir (`logs/tenfoot.log`, then `.1`, `.2`, ... oldest last), with
+//! credentials scrubbed. The files are read back for the in-app log viewer
+//! and support bundles.
+
+use crate::launcher_core::redact::RedactingLogger;
+use crate::stats::civil_from_days;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory in the data dir the log files go in
+pub const LOG_DIR: &str = "logs";
+const LOG_FILE: &str = "tenfoot.log";
+
+/// Size a log file grows to before it is rotated
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept besides the current one
+const KEEP_FILES: usize = 4;
+
+/// How much gets logged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ERROR" => Some(LogLevel::Error),
+            "WARN" => Some(LogLevel::Warn),
+            "INFO" => Some(LogLevel::Info),
+            "DEBUG" => Some(LogLevel::Debug),
+            "TRACE" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+}
+
+/// One record read back from the log files
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LogEntry {
+    /// UTC, as RFC 3339
+    pub timestamp: String,
+    pub level: LogLevel,
+    /// Module the record came from (`tenfoot_lib::launcher_core::library`)
+    pub module: String,
+    pub message: String,
+}
+
+/// Start logging to stderr and the data dir's log files. Without a data dir
+/// or a usable log file, logging goes to stderr only.
+pub fn init(data_dir: Option<&Path>, level: LogLevel) {
+    let file = data_dir.and_then(|data_dir| {
+        RotatingFile::open(&data_dir.join(LOG_DIR), MAX_FILE_BYTES, KEEP_FILES)
+            .map_err(|e| eprintln!("Failed to open the log file, logging to stderr only: {e}"))
+            .ok()
+    });
+    let logger = RedactingLogger::new(AppLogger {
+        file: file.map(Mutex::new),
+    });
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        set_level(level);
+    }
+}
+
+/// Change how much gets logged from now on
+pub fn set_level(level: LogLevel) {
+    log::set_max_level(level.filter());
+}
+
+/// The log files in a data dir, newest first
+pub fn log_files(data_dir: &Path) -> Vec<PathBuf> {
+    let dir = data_dir.join(LOG_DIR);
+    (0..=KEEP_FILES)
+        .map(|n| rotated_path(&dir, n))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// The last `limit` entries (oldest first) at or above `level` whose module
+/// contains `module`
+pub fn read_entries(
+    data_dir: &Path,
+    level: Option<LogLevel>,
+    module: Option<&str>,
+    limit: usize,
+) -> Vec<LogEntry> {
+    let matches = |entry: &LogEntry| {
+        level.is_none_or(|level| entry.level <= level)
+            && module.is_none_or(|module| entry.module.contains(module))
+    };
+
+    // Newest file first, so older files are only read when needed
+    let mut entries = VecDeque::new();
+    for path in log_files(data_dir) {
+        let Ok(content) = fs::read(&path) else {
+            continue;
+        };
+        let found: Vec<LogEntry> = parse_entries(&String::from_utf8_lossy(&content))
+            .into_iter()
+            .filter(|entry| matches(entry))
+            .collect();
+        for entry in found.into_iter().rev() {
+            entries.push_front(entry);
+        }
+        if entries.len() >= limit {
+            break;
+        }
+    }
+    let excess = entries.len().saturating_sub(limit);
+    entries.drain(..excess);
+    entries.into()
+}
+
+/// Writes records to stderr and the log file
+struct AppLogger {
+    file: Option<Mutex<RotatingFile>>,
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Debug and trace are for TenFoot's own modules; dependencies (HTTP,
+        // the webview) are far too chatty at those levels
+        metadata.level() <= log::max_level()
+            && (metadata.level() <= log::Level::Info
+                || metadata.target().starts_with(env!("CARGO_CRATE_NAME")))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format_line(
+            SystemTime::now(),
+            record.level(),
+            record.target(),
+            &record.args().to_string(),
+        );
+        let _ = io::stderr().write_all(line.as_bytes());
+        if let Some(file) = &self.file {
+            let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = file.write(line.as_bytes()) {
+                eprintln!("Failed to write to the log file: {e}");
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+/// `2026-10-16T12:34:56.789Z WARN  tenfoot_lib::module: message`
+fn format_line(time: SystemTime, level: log::Level, target: &str, message: &str) -> String {
+    format!(
+        "{} {level:<5} {target}: {message}\n",
+        format_timestamp(time)
+    )
+}
+
+pub(crate) fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let secs_of_day = secs.rem_euclid(86_400);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Split log text into entries. Lines that don't start a record (a message
+/// spanning several lines) belong to the record before them.
+fn parse_entries(text: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in text.lines() {
+        match parse_line(line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+        }
+    }
+    entries
+}
+
+fn parse_line(line: &str) -> Option<LogEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    if timestamp.len() != 24 || !timestamp.ends_with('Z') {
+        return None;
+    }
+    let (level, rest) = rest.split_once(' ')?;
+    let level = LogLevel::from_name(level)?;
+    let (module, message) = rest.trim_start().split_once(": ")?;
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level,
+        module: module.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// A log file that is moved aside once it gets too big. The app and
+/// tenfoot-cli write to the same file, so each checks before writing whether
+/// the other has appended to it or rotated it away.
+struct RotatingFile {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    fn open(dir: &Path, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file = append(&rotated_path(dir, 0))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file,
+            size,
+            max_bytes,
+            keep,
+        })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.follow()?;
+        if self.size > 0 && self.size + bytes.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(bytes)?;
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Pick up what another process did to the file: count its writes, and
+    /// reopen the current file if ours was rotated away (the file at the
+    /// path no longer matches the one we hold)
+    fn follow(&mut self) -> io::Result<()> {
+        let path = rotated_path(&self.dir, 0);
+        let ours = self.file.metadata()?.len();
+        if fs::metadata(&path).map(|m| m.len()).ok() == Some(ours) {
+            self.size = ours;
+        } else {
+            self.file = append(&path)?;
+            self.size = self.file.metadata()?.len();
+        }
+        Ok(())
+    }
+
+    /// Shift `.1`.. up a slot (dropping the oldest) and start a new file
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let oldest = rotated_path(&self.dir, self.keep);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for n in (0..self.keep).rev() {
+            let from = rotated_path(&self.dir, n);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.dir, n + 1))?;
+            }
+        }
+        self.file = append(&rotated_path(&self.dir, 0))?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// `tenfoot.log` for 0, `tenfoot.log.{n}` for older files
+fn rotated_path(dir: &Path, n: usize) -> PathBuf {
+    match n {
+        0 => dir.join(LOG_FILE),
+        n => dir.join(format!("{LOG_FILE}.{n}")),
+    }
+}
+
+fn append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn line(level: log::Level, target: &str, message: &str) -> String {
+        format_line(
+            UNIX_EPOCH + Duration::from_millis(1_792_022_400_250),
+            level,
+            target,
+            message,
+        )
+    }
+
+    #[test]
+    fn test_format_and_parse() {
+        let text = line(log::Level::Warn, "tenfoot_lib::library", "Failed: a\nb");
+        assert_eq!(
+            text,
+            "2026-10-15T00:00:00.250Z WARN  tenfoot_lib::library: Failed: a\nb\n"
+        );
+
+        assert_eq!(
+            parse_entries(&text),
+            vec![LogEntry {
+                timestamp: "2026-10-15T00:00:00.250Z".to_string(),
+                level: LogLevel::Warn,
+                module: "tenfoot_lib::library".to_string(),
+                message: "Failed: a\nb".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rotation() {
+        let temp = TempDir::new().unwrap();
+        let mut file = RotatingFile::open(temp.path(), 10, 2).unwrap();
+
+        for n in 0..5 {
+            file.write(format!("line {n}\n").as_bytes()).unwrap();
+        }
+
+        let read = |n| fs::read_to_string(rotated_path(temp.path(), n)).unwrap();
+        assert_eq!(read(0), "line 4\n");
+        assert_eq!(read(1), "line 3\n");
+        assert_eq!(read(2), "line 2\n");
+        assert!(!rotated_path(temp.path(), 3).exists());
+    }
+
+    #[test]
+    fn test_rotation_shared_between_processes() {
+        let temp = TempDir::new().unwrap();
+        let mut app = RotatingFile::open(temp.path(), 10, 2).unwrap();
+        let mut cli = RotatingFile::open(temp.path(), 10, 2).unwrap();
+
+        app.write(b"a 0\n").unwrap();
+        cli.write(b"c 0\n").unwrap();
+        // Rotates the file the CLI still has open
+        app.write(b"a 1\n").unwrap();
+        cli.write(b"c 1\n").unwrap();
+
+        let read = |n| fs::read_to_string(rotated_path(temp.path(), n)).unwrap();
+        assert_eq!(read(0), "a 1\nc 1\n");
+        assert_eq!(read(1), "a 0\nc 0\n");
+        assert!(!rotated_path(temp.path(), 2).exists());
+    }
+
+    #[test]
+    fn test_read_entries_filters_across_files() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(LOG_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        let older = [
+            line(
+                log::Level::Error,
+                "tenfoot_lib::stores::gog",
+                "database locked",
+            ),
+            line(log::Level::Info, "tenfoot_lib::stores::gog", "scanned"),
+        ]
+        .concat();
+        let newer = [
+            line(
+                log::Level::Warn,
+                "tenfoot_lib::stores::epic",
+                "bad manifest",
+            ),
+            line(log::Level::Debug, "tenfoot_lib::stores::gog", "query"),
+            line(log::Level::Warn, "tenfoot_lib::stores::gog", "slow scan"),
+        ]
+        .concat();
+        fs::write(rotated_path(&dir, 1), older).unwrap();
+        fs::write(rotated_path(&dir, 0), newer).unwrap();
+
+        let messages = |entries: Vec<LogEntry>| -> Vec<String> {
+            entries.into_iter().map(|e| e.message).collect()
+        };
+        assert_eq!(
+            messages(read_entries(temp.path(), Some(LogLevel::Warn), None, 10)),
+            ["database locked", "bad manifest", "slow scan"]
+        );
+        assert_eq!(
+            messages(read_entries(temp.path(), None, Some("gog"), 2)),
+            ["query", "slow scan"]
+        );
+        assert_eq!(read_entries(temp.path(), None, None, 100).len(), 5);
+    }
+}
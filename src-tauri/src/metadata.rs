@@ -0,0 +1,377 @@
+use crate::launcher_core::{DetailsProvider, GameDetails, LauncherError};
+use crate::storage::{CachedDetails, DetailsCache, Storage};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long fetched details are served before they're refreshed in the background
+pub const DEFAULT_DETAILS_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// What the cache holds for a game
+#[derive(Debug, Clone, PartialEq)]
+pub enum DetailsLookup {
+    /// Fetched within the TTL
+    Fresh(Option<GameDetails>),
+    /// Older than the TTL, still good enough to show while refreshing
+    Stale(Option<GameDetails>),
+    /// Never fetched
+    Missing,
+}
+
+/// Payload of the "game-details-updated" event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailsUpdate {
+    pub game_key: String,
+    pub details: Option<GameDetails>,
+}
+
+/// Serves game details for any unique key from a persistent cache.
+///
+/// Each store contributes a `DetailsProvider`. Fresh entries are returned as-is,
+/// stale ones are returned immediately and refreshed in the background
+/// (stale-while-revalidate), and missing ones are fetched on the spot. A failed
+/// refresh keeps the old entry, so details stay available offline.
+pub struct MetadataService {
+    storage: Storage,
+    providers: HashMap<String, Arc<dyn DetailsProvider>>,
+    ttl: Duration,
+    cache: Mutex<DetailsCache>,
+    refreshing: Mutex<HashSet<String>>,
+}
+
+impl MetadataService {
+    /// Create a service backed by the given storage. An unreadable cache is
+    /// treated as empty.
+    pub fn new(storage: Storage) -> Self {
+        let cache = storage.load_details_cache().unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable details cache: {e}");
+            DetailsCache::default()
+        });
+
+        Self {
+            storage,
+            providers: HashMap::new(),
+            ttl: DEFAULT_DETAILS_TTL,
+            cache: Mutex::new(cache),
+            refreshing: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Register the details source for a store
+    pub fn register_provider(&mut self, provider: Arc<dyn DetailsProvider>) {
+        let store_id = provider.store().to_string().to_lowercase();
+        self.providers.insert(store_id, provider);
+    }
+
+    /// Change how long details are considered fresh
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    /// Look up a game's details without fetching anything
+    pub fn cached(&self, game_key: &str, now: u64) -> DetailsLookup {
+        let Ok(cache) = self.cache.lock() else {
+            return DetailsLookup::Missing;
+        };
+
+        match cache.entries.get(game_key) {
+            Some(entry) if now.saturating_sub(entry.fetched_at) < self.ttl.as_secs() => {
+                DetailsLookup::Fresh(entry.details.clone())
+            }
+            Some(entry) => DetailsLookup::Stale(entry.details.clone()),
+            None => DetailsLookup::Missing,
+        }
+    }
+
+    /// Every cached entry that has details, keyed by unique key
+    pub fn all_cached(&self) -> HashMap<String, GameDetails> {
+        let Ok(cache) = self.cache.lock() else {
+            return HashMap::new();
+        };
+
+        cache
+            .entries
+            .iter()
+            .filter_map(|(key, entry)| Some((key.clone(), entry.details.clone()?)))
+            .collect()
+    }
+
+    /// Save details obtained elsewhere (e.g. during a library sync)
+    pub fn store(
+        &self,
+        entries: Vec<(String, Option<GameDetails>)>,
+        now: u64,
+    ) -> Result<(), LauncherError> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|e| LauncherError::ConfigError(e.to_string()))?;
+
+        let entries: Vec<(String, CachedDetails)> = entries
+            .into_iter()
+            .map(|(game_key, details)| {
+                let entry = CachedDetails {
+                    details,
+                    fetched_at: now,
+                };
+                (game_key, entry)
+            })
+            .collect();
+        self.storage.save_cached_details(&entries)?;
+        cache.entries.extend(entries);
+        Ok(())
+    }
+
+    /// Fetch details from the game's store and cache them.
+    /// Stores without a provider have no details and nothing is cached.
+    pub fn refresh(&self, game_key: &str, now: u64) -> Result<Option<GameDetails>, LauncherError> {
+        let (store_id, game_id) = game_key
+            .split_once(':')
+            .ok_or_else(|| LauncherError::GameNotFound(game_key.to_string()))?;
+
+        let Some(provider) = self.providers.get(store_id) else {
+            return Ok(None);
+        };
+
+        let details = provider.fetch_details(game_id)?;
+        self.store(vec![(game_key.to_string(), details.clone())], now)?;
+        Ok(details)
+    }
+
+    /// Get details for a game, fetching or revalidating as needed.
+    ///
+    /// When a stale entry is refreshed in the background and comes back
+    /// different, `on_update` is called with the new details.
+    pub fn get_details(
+        self: &Arc<Self>,
+        game_key: &str,
+        now: u64,
+        on_update: impl FnOnce(Option<GameDetails>) + Send + 'static,
+    ) -> Result<Option<GameDetails>, LauncherError> {
+        match self.cached(game_key, now) {
+            DetailsLookup::Fresh(details) => Ok(details),
+            DetailsLookup::Stale(details) => {
+                self.revalidate(game_key, details.clone(), now, on_update);
+                Ok(details)
+            }
+            DetailsLookup::Missing => self.refresh(game_key, now),
+        }
+    }
+
+    /// Refresh an entry on a background thread, at most once at a time per game
+    fn revalidate(
+        self: &Arc<Self>,
+        game_key: &str,
+        current: Option<GameDetails>,
+        now: u64,
+        on_update: impl FnOnce(Option<GameDetails>) + Send + 'static,
+    ) {
+        let Ok(mut refreshing) = self.refreshing.lock() else {
+            return;
+        };
+        if !refreshing.insert(game_key.to_string()) {
+            return;
+        }
+        drop(refreshing);
+
+        let service = Arc::clone(self);
+        let game_key = game_key.to_string();
+        std::thread::spawn(move || {
+            match service.refresh(&game_key, now) {
+                Ok(details) if details != current => on_update(details),
+                Ok(_) => {}
+                Err(e) => log::warn!("Keeping stale details for {game_key}: {e}"),
+            }
+
+            if let Ok(mut refreshing) = service.refreshing.lock() {
+                refreshing.remove(&game_key);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use tempfile::TempDir;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    struct MockProvider {
+        description: Mutex<Option<String>>,
+        fail: Mutex<bool>,
+        calls: AtomicUsize,
+    }
+
+    impl MockProvider {
+        fn new(description: &str) -> Arc<Self> {
+            Arc::new(Self {
+                description: Mutex::new(Some(description.to_string())),
+                fail: Mutex::new(false),
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn set_description(&self, description: &str) {
+            *self.description.lock().unwrap() = Some(description.to_string());
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl DetailsProvider for MockProvider {
+        fn store(&self) -> StoreType {
+            StoreType::Steam
+        }
+
+        fn fetch_details(&self, _game_id: &str) -> Result<Option<GameDetails>, LauncherError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if *self.fail.lock().unwrap() {
+                return Err(LauncherError::NetworkError("offline".to_string()));
+            }
+            Ok(Some(GameDetails {
+                description: self.description.lock().unwrap().clone(),
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn storage(temp: &TempDir) -> Storage {
+        Storage::open(temp.path()).unwrap()
+    }
+
+    fn service(temp: &TempDir, provider: Arc<MockProvider>) -> Arc<MetadataService> {
+        let mut service = MetadataService::new(storage(temp));
+        service.register_provider(provider);
+        Arc::new(service)
+    }
+
+    fn description(details: Option<GameDetails>) -> Option<String> {
+        details.and_then(|d| d.description)
+    }
+
+    #[test]
+    fn test_missing_details_are_fetched_and_cached() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Hats");
+        let service = service(&temp, provider.clone());
+
+        assert_eq!(service.cached("steam:440", 0), DetailsLookup::Missing);
+        let details = service.get_details("steam:440", 0, |_| {}).unwrap();
+        assert_eq!(description(details), Some("Hats".to_string()));
+
+        // Fresh entries don't hit the provider again
+        service.get_details("steam:440", DAY, |_| {}).unwrap();
+        assert_eq!(provider.calls(), 1);
+    }
+
+    #[test]
+    fn test_details_survive_restart() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Hats");
+        service(&temp, provider.clone())
+            .get_details("steam:440", 0, |_| {})
+            .unwrap();
+
+        // A new service with a failing provider still serves the cached entry
+        *provider.fail.lock().unwrap() = true;
+        let restarted = service(&temp, provider.clone());
+        let details = restarted.get_details("steam:440", DAY, |_| {}).unwrap();
+        assert_eq!(description(details), Some("Hats".to_string()));
+        assert_eq!(provider.calls(), 1);
+    }
+
+    #[test]
+    fn test_stale_details_are_served_then_revalidated() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Old");
+        let service = service(&temp, provider.clone());
+        service.get_details("steam:440", 0, |_| {}).unwrap();
+
+        provider.set_description("New");
+        let (tx, rx) = mpsc::channel();
+        let now = DEFAULT_DETAILS_TTL.as_secs() + 1;
+        let details = service
+            .get_details("steam:440", now, move |d| tx.send(d).unwrap())
+            .unwrap();
+
+        assert_eq!(description(details), Some("Old".to_string()));
+        let updated = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(description(updated), Some("New".to_string()));
+        assert!(matches!(
+            service.cached("steam:440", now),
+            DetailsLookup::Fresh(_)
+        ));
+    }
+
+    #[test]
+    fn test_failed_revalidation_keeps_stale_entry() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Old");
+        let service = service(&temp, provider.clone());
+        service.get_details("steam:440", 0, |_| {}).unwrap();
+
+        *provider.fail.lock().unwrap() = true;
+        let now = DEFAULT_DETAILS_TTL.as_secs() + 1;
+        assert!(service.refresh("steam:440", now).is_err());
+        assert_eq!(
+            service.cached("steam:440", now),
+            DetailsLookup::Stale(Some(GameDetails {
+                description: Some("Old".to_string()),
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_missing_details_offline_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Hats");
+        *provider.fail.lock().unwrap() = true;
+        let service = service(&temp, provider);
+
+        let result = service.get_details("steam:440", 0, |_| {});
+        assert!(matches!(result, Err(LauncherError::NetworkError(_))));
+    }
+
+    #[test]
+    fn test_store_without_provider_has_no_details() {
+        let temp = TempDir::new().unwrap();
+        let provider = MockProvider::new("Hats");
+        let service = service(&temp, provider.clone());
+
+        assert_eq!(
+            service.get_details("local:celeste", 0, |_| {}).unwrap(),
+            None
+        );
+        assert_eq!(service.cached("local:celeste", 0), DetailsLookup::Missing);
+        assert_eq!(provider.calls(), 0);
+    }
+
+    #[test]
+    fn test_store_seeds_cache() {
+        let temp = TempDir::new().unwrap();
+        let service = service(&temp, MockProvider::new("Hats"));
+        let details = GameDetails {
+            genres: Some(vec!["Action".to_string()]),
+            ..Default::default()
+        };
+
+        service
+            .store(vec![("epic:fn".to_string(), Some(details.clone()))], 10)
+            .unwrap();
+
+        assert_eq!(
+            service.cached("epic:fn", 10),
+            DetailsLookup::Fresh(Some(details.clone()))
+        );
+        assert_eq!(service.all_cached().get("epic:fn"), Some(&details));
+    }
+}
@@ -0,0 +1,584 @@
+use crate::collections::UserCollections;
+use crate::launcher_core::{Game, LauncherError, StoreType};
+use std::collections::HashMap;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// Extra per-game data a query can filter on, keyed by `Game::unique_key()`
+#[derive(Debug, Clone, Default)]
+pub struct QueryContext {
+    /// Current Unix time, used for relative dates like `last_played:<7d`
+    pub now: u64,
+    /// Genres, developers and publishers from cached store details
+    pub metadata: HashMap<String, GameMetadata>,
+    /// Last launch times recorded by the launcher
+    pub last_played: HashMap<String, u64>,
+    /// Playtime in minutes recorded by the launcher
+    pub playtime_minutes: HashMap<String, u64>,
+    /// Favorites, hidden games and collections
+    pub collections: UserCollections,
+}
+
+/// Descriptive metadata used by `genre:`, `developer:` and `publisher:` (and by search)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameMetadata {
+    pub genres: Vec<String>,
+    pub developers: Vec<String>,
+    pub publishers: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// Numeric comparison operator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Op {
+    fn compare(self, left: u64, right: u64) -> bool {
+        match self {
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+            Op::Gt => left > right,
+            Op::Ge => left >= right,
+            Op::Eq => left == right,
+        }
+    }
+}
+
+/// A numeric condition like `<60`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub op: Op,
+    pub value: u64,
+}
+
+impl Comparison {
+    fn matches(&self, value: u64) -> bool {
+        self.op.compare(value, self.value)
+    }
+}
+
+/// A condition on how long ago something happened
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AgeFilter {
+    /// It never happened
+    Never,
+    /// Age in seconds compared against a duration
+    Age(Comparison),
+}
+
+/// A single condition in a query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Bare word: matches the game name
+    Text(String),
+    Name(String),
+    Store(StoreType),
+    Installed(bool),
+    Favorite(bool),
+    Hidden(bool),
+    Collection(String),
+    /// Minutes played
+    Playtime(Comparison),
+    LastPlayed(AgeFilter),
+    Added(AgeFilter),
+    /// Size on disk in bytes
+    Size(Comparison),
+    Genre(String),
+    Developer(String),
+    Publisher(String),
+}
+
+/// A filter, optionally negated with a leading `-`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub negated: bool,
+    pub filter: Filter,
+}
+
+/// A parsed library query such as `store:epic installed:true playtime:<60 genre:rpg`.
+///
+/// All terms must match. Bare words match the game name, values with spaces
+/// can be quoted (`developer:"id software"`) and any term can be negated with `-`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+impl Query {
+    /// Parse and validate a query string
+    pub fn parse(input: &str) -> Result<Self, LauncherError> {
+        let terms = tokenize(input)?
+            .into_iter()
+            .map(|token| parse_term(&token))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { terms })
+    }
+
+    /// Check whether a game matches every term
+    pub fn matches(&self, game: &Game, ctx: &QueryContext) -> bool {
+        let key = game.unique_key();
+        self.terms
+            .iter()
+            .all(|term| term.filter.matches(game, &key, ctx) != term.negated)
+    }
+
+    /// Whether the query filters on hidden games explicitly
+    pub fn mentions_hidden(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|t| matches!(t.filter, Filter::Hidden(_)))
+    }
+
+    /// Keep only the games that match
+    pub fn filter(&self, games: Vec<Game>, ctx: &QueryContext) -> Vec<Game> {
+        games.into_iter().filter(|g| self.matches(g, ctx)).collect()
+    }
+}
+
+impl Filter {
+    fn matches(&self, game: &Game, key: &str, ctx: &QueryContext) -> bool {
+        match self {
+            Filter::Text(text) | Filter::Name(text) => contains_ignore_case(&game.name, text),
+            Filter::Store(store) => game.store == *store,
+            Filter::Installed(installed) => game.installed == *installed,
+            Filter::Favorite(favorite) => ctx.collections.is_favorite(key) == *favorite,
+            Filter::Hidden(hidden) => ctx.collections.is_hidden(key) == *hidden,
+            Filter::Collection(id) => ctx
+                .collections
+                .get(id)
+                .is_some_and(|c| c.game_keys.iter().any(|k| k == key)),
+            Filter::Playtime(cmp) => {
+                let store = game.playtime_minutes.unwrap_or(0);
+                let recorded = ctx.playtime_minutes.get(key).copied().unwrap_or(0);
+                cmp.matches(store.max(recorded))
+            }
+            Filter::LastPlayed(age) => {
+                let recorded = ctx.last_played.get(key).copied();
+                let last = game.last_played.max(recorded).filter(|t| *t > 0);
+                age.matches(last, ctx.now)
+            }
+            Filter::Added(age) => age.matches(game.installed_at, ctx.now),
+            Filter::Size(cmp) => game.size_bytes.is_some_and(|size| cmp.matches(size)),
+            Filter::Genre(text) => metadata_matches(ctx, key, text, |m| &m.genres),
+            Filter::Developer(text) => metadata_matches(ctx, key, text, |m| &m.developers),
+            Filter::Publisher(text) => metadata_matches(ctx, key, text, |m| &m.publishers),
+        }
+    }
+}
+
+impl AgeFilter {
+    /// Something that never happened counts as infinitely old
+    fn matches(&self, timestamp: Option<u64>, now: u64) -> bool {
+        match (self, timestamp) {
+            (AgeFilter::Never, timestamp) => timestamp.is_none(),
+            (AgeFilter::Age(cmp), Some(timestamp)) => cmp.matches(now.saturating_sub(timestamp)),
+            (AgeFilter::Age(cmp), None) => matches!(cmp.op, Op::Gt | Op::Ge),
+        }
+    }
+}
+
+fn metadata_matches(
+    ctx: &QueryContext,
+    key: &str,
+    text: &str,
+    values: impl Fn(&GameMetadata) -> &Vec<String>,
+) -> bool {
+    ctx.metadata
+        .get(key)
+        .is_some_and(|m| values(m).iter().any(|v| contains_ignore_case(v, text)))
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// Split a query into terms, keeping quoted values together
+fn tokenize(input: &str) -> Result<Vec<String>, LauncherError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(query_error("unterminated quote"));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &str) -> Result<Term, LauncherError> {
+    let (negated, token) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let Some((field, value)) = token.split_once(':') else {
+        return Ok(Term {
+            negated,
+            filter: Filter::Text(token.to_string()),
+        });
+    };
+
+    if value.is_empty() {
+        return Err(query_error(&format!("missing value for '{field}'")));
+    }
+
+    let filter = match field.to_lowercase().as_str() {
+        "name" => Filter::Name(value.to_string()),
+        "store" => Filter::Store(parse_store(value)?),
+        "installed" => Filter::Installed(parse_bool(field, value)?),
+        "favorite" | "favourite" => Filter::Favorite(parse_bool(field, value)?),
+        "hidden" => Filter::Hidden(parse_bool(field, value)?),
+        "is" => match value.to_lowercase().as_str() {
+            "installed" => Filter::Installed(true),
+            "favorite" | "favourite" => Filter::Favorite(true),
+            "hidden" => Filter::Hidden(true),
+            _ => return Err(query_error(&format!("unknown value 'is:{value}'"))),
+        },
+        "collection" => Filter::Collection(value.to_string()),
+        "playtime" => Filter::Playtime(parse_comparison(field, value, parse_minutes)?),
+        "last_played" | "played" => Filter::LastPlayed(parse_age(field, value)?),
+        "added" | "installed_at" => Filter::Added(parse_age(field, value)?),
+        "size" => Filter::Size(parse_comparison(field, value, parse_size)?),
+        "genre" => Filter::Genre(value.to_string()),
+        "developer" | "dev" => Filter::Developer(value.to_string()),
+        "publisher" => Filter::Publisher(value.to_string()),
+        _ => return Err(query_error(&format!("unknown field '{field}'"))),
+    };
+
+    Ok(Term { negated, filter })
+}
+
+fn parse_store(value: &str) -> Result<StoreType, LauncherError> {
+    match value.to_lowercase().as_str() {
+        "steam" => Ok(StoreType::Steam),
+        "epic" => Ok(StoreType::Epic),
+        "gog" => Ok(StoreType::Gog),
+        "local" => Ok(StoreType::Local),
+        _ => Err(query_error(&format!("unknown store '{value}'"))),
+    }
+}
+
+fn parse_bool(field: &str, value: &str) -> Result<bool, LauncherError> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(query_error(&format!(
+            "'{field}' expects true or false, got '{value}'"
+        ))),
+    }
+}
+
+/// Parse `<60`, `>=2h`, `=0` or a bare value (equality)
+fn parse_comparison(
+    field: &str,
+    value: &str,
+    parse_value: fn(&str) -> Option<u64>,
+) -> Result<Comparison, LauncherError> {
+    let (op, rest) = if let Some(rest) = value.strip_prefix("<=") {
+        (Op::Le, rest)
+    } else if let Some(rest) = value.strip_prefix(">=") {
+        (Op::Ge, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Op::Lt, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Op::Gt, rest)
+    } else if let Some(rest) = value.strip_prefix('=') {
+        (Op::Eq, rest)
+    } else {
+        (Op::Eq, value)
+    };
+
+    let value = parse_value(&rest.to_lowercase())
+        .ok_or_else(|| query_error(&format!("invalid value '{rest}' for '{field}'")))?;
+    Ok(Comparison { op, value })
+}
+
+fn parse_age(field: &str, value: &str) -> Result<AgeFilter, LauncherError> {
+    if value.eq_ignore_ascii_case("never") {
+        return Ok(AgeFilter::Never);
+    }
+    parse_comparison(field, value, parse_duration).map(AgeFilter::Age)
+}
+
+/// Split "30d" into (30, "d")
+fn split_unit(value: &str) -> Option<(u64, &str)> {
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let number = value[..digits].parse().ok()?;
+    Some((number, &value[digits..]))
+}
+
+/// Minutes; accepts `90`, `90m` or `2h`
+fn parse_minutes(value: &str) -> Option<u64> {
+    match split_unit(value)? {
+        (n, "" | "m" | "min") => Some(n),
+        (n, "h") => n.checked_mul(60),
+        _ => None,
+    }
+}
+
+/// Seconds; accepts `12h`, `30d`, `2w` or `1y` (bare numbers are days)
+fn parse_duration(value: &str) -> Option<u64> {
+    match split_unit(value)? {
+        (n, "h") => n.checked_mul(HOUR),
+        (n, "" | "d") => n.checked_mul(DAY),
+        (n, "w") => n.checked_mul(7 * DAY),
+        (n, "y") => n.checked_mul(365 * DAY),
+        _ => None,
+    }
+}
+
+/// Bytes; accepts `500mb`, `10gb` etc. (bare numbers are bytes)
+fn parse_size(value: &str) -> Option<u64> {
+    let (n, unit) = split_unit(value)?;
+    let multiplier: u64 = match unit {
+        "" | "b" => 1,
+        "kb" => 1 << 10,
+        "mb" => 1 << 20,
+        "gb" => 1 << 30,
+        "tb" => 1 << 40,
+        _ => return None,
+    };
+    n.checked_mul(multiplier)
+}
+
+fn query_error(message: &str) -> LauncherError {
+    LauncherError::ParseError(format!("Invalid query: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::GamesCache;
+    use crate::sync::combine_games;
+
+    const NOW: u64 = 1_800_000_000;
+
+    fn game(id: &str, name: &str, store: StoreType) -> Game {
+        Game::new(id, name, store)
+    }
+
+    fn library() -> Vec<Game> {
+        let mut doom = game("2280", "DOOM Eternal", StoreType::Steam);
+        doom.installed = true;
+        doom.playtime_minutes = Some(1200);
+        doom.last_played = Some(NOW - 2 * DAY);
+        doom.size_bytes = Some(80 << 30);
+
+        let mut hades = game("hades", "Hades", StoreType::Epic);
+        hades.installed = true;
+        hades.playtime_minutes = Some(45);
+        hades.installed_at = Some(NOW - 60 * DAY);
+
+        let witcher = game("1207664643", "The Witcher 3", StoreType::Gog);
+
+        vec![doom, hades, witcher]
+    }
+
+    fn context() -> QueryContext {
+        let mut ctx = QueryContext {
+            now: NOW,
+            ..Default::default()
+        };
+        ctx.metadata.insert(
+            "epic:hades".to_string(),
+            GameMetadata {
+                genres: vec!["Action".to_string(), "RPG".to_string()],
+                developers: vec!["Supergiant Games".to_string()],
+                publishers: vec!["Supergiant Games".to_string()],
+                description: None,
+            },
+        );
+        ctx.last_played
+            .insert("epic:hades".to_string(), NOW - 40 * DAY);
+        ctx
+    }
+
+    fn names(query: &str) -> Vec<String> {
+        Query::parse(query)
+            .unwrap()
+            .filter(library(), &context())
+            .into_iter()
+            .map(|g| g.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert_eq!(names("").len(), 3);
+        assert_eq!(names("   ").len(), 3);
+    }
+
+    #[test]
+    fn test_store_installed_playtime_genre() {
+        assert_eq!(
+            names("store:epic installed:true playtime:<60 genre:rpg"),
+            vec!["Hades"]
+        );
+        assert!(names("store:epic playtime:>60").is_empty());
+        assert_eq!(names("is:installed playtime:>=20h"), vec!["DOOM Eternal"]);
+    }
+
+    #[test]
+    fn test_installed_false_matches_owned_games() {
+        let cache = GamesCache {
+            steam_owned: vec![game("2280", "DOOM Eternal", StoreType::Steam)],
+            epic_owned: vec![game("celeste", "Celeste", StoreType::Epic)],
+            ..GamesCache::default()
+        };
+        let mut installed = game("2280", "DOOM Eternal", StoreType::Steam);
+        installed.installed = true;
+        let games = combine_games(cache, vec![installed]);
+
+        let not_installed: Vec<String> = Query::parse("installed:false")
+            .unwrap()
+            .filter(games, &context())
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+
+        assert_eq!(not_installed, vec!["Celeste"]);
+    }
+
+    #[test]
+    fn test_last_played() {
+        // Never-played games count as not played recently
+        assert_eq!(names("last_played:>30d"), vec!["Hades", "The Witcher 3"]);
+        assert_eq!(names("last_played:<1w"), vec!["DOOM Eternal"]);
+        assert_eq!(names("last_played:never"), vec!["The Witcher 3"]);
+    }
+
+    #[test]
+    fn test_recorded_playtime_is_used_when_larger() {
+        let mut ctx = context();
+        ctx.playtime_minutes
+            .insert("gog:1207664643".to_string(), 300);
+
+        let query = Query::parse("playtime:>=5h").unwrap();
+        let matched: Vec<String> = query
+            .filter(library(), &ctx)
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+        assert_eq!(matched, vec!["DOOM Eternal", "The Witcher 3"]);
+    }
+
+    #[test]
+    fn test_text_name_and_negation() {
+        assert_eq!(names("witcher"), vec!["The Witcher 3"]);
+        assert_eq!(names("name:\"doom eternal\""), vec!["DOOM Eternal"]);
+        assert_eq!(names("-store:steam -witcher"), vec!["Hades"]);
+        assert_eq!(names("developer:supergiant"), vec!["Hades"]);
+    }
+
+    #[test]
+    fn test_size_and_added() {
+        assert_eq!(names("size:>50gb"), vec!["DOOM Eternal"]);
+        assert_eq!(names("added:>30d store:epic"), vec!["Hades"]);
+        assert_eq!(names("added:never").len(), 2);
+    }
+
+    #[test]
+    fn test_favorites_hidden_and_collections() {
+        let mut ctx = context();
+        ctx.collections
+            .set_favorite(&["gog:1207664643".to_string()], true);
+        let id = ctx.collections.create("Shooters", 0).unwrap().id;
+        ctx.collections
+            .add_games(&id, &["steam:2280".to_string()])
+            .unwrap();
+
+        let run = |query: &str| -> Vec<String> {
+            Query::parse(query)
+                .unwrap()
+                .filter(library(), &ctx)
+                .into_iter()
+                .map(|g| g.name)
+                .collect()
+        };
+
+        assert_eq!(run("is:favorite"), vec!["The Witcher 3"]);
+        assert_eq!(run("collection:shooters"), vec!["DOOM Eternal"]);
+        assert_eq!(run("hidden:false").len(), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for query in [
+            "colour:red",
+            "store:origin",
+            "installed:maybe",
+            "playtime:<lots",
+            "last_played:>30x",
+            "size:>10zb",
+            "genre:",
+            "name:\"unterminated",
+            "is:broken",
+        ] {
+            let result = Query::parse(query);
+            assert!(
+                matches!(&result, Err(LauncherError::ParseError(msg)) if msg.starts_with("Invalid query")),
+                "expected error for {query}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_overflowing_numbers() {
+        for query in [
+            "playtime:>999999999999999999h",
+            "added:<99999999999999999y",
+            "last_played:>9999999999999999w",
+            "size:>99999999999tb",
+        ] {
+            assert!(Query::parse(query).is_err(), "expected error for {query}");
+        }
+    }
+
+    #[test]
+    fn test_parse_structure() {
+        let query = Query::parse("-playtime:>=2h last_played:<12h").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                Term {
+                    negated: true,
+                    filter: Filter::Playtime(Comparison {
+                        op: Op::Ge,
+                        value: 120
+                    }),
+                },
+                Term {
+                    negated: false,
+                    filter: Filter::LastPlayed(AgeFilter::Age(Comparison {
+                        op: Op::Lt,
+                        value: 12 * HOUR
+                    })),
+                },
+            ]
+        );
+    }
+}
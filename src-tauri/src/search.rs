@@ -0,0 +1,640 @@
+use crate::launcher_core::Game;
+use crate::query::GameMetadata;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Results returned when the caller doesn't ask for a specific number
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Searchable parts of a game, in order of importance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    Title,
+    Developer,
+    Genre,
+    Description,
+}
+
+impl SearchField {
+    /// How much a match in this field counts towards the score
+    fn boost(self) -> f32 {
+        match self {
+            SearchField::Title => 8.0,
+            SearchField::Developer => 4.0,
+            SearchField::Genre => 2.0,
+            SearchField::Description => 1.0,
+        }
+    }
+}
+
+/// Matched ranges within one field value.
+///
+/// Ranges are `[start, end)` offsets in UTF-16 code units so the UI can use them
+/// with `String.prototype.slice` directly.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHighlight {
+    pub field: SearchField,
+    pub value: String,
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// A game that matched a search, best matches first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchResult {
+    pub key: String,
+    pub score: f32,
+    pub highlights: Vec<SearchHighlight>,
+}
+
+/// A normalized word and where it came from in the original text
+#[derive(Debug, Clone)]
+struct Token {
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedValue {
+    field: SearchField,
+    value: String,
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedGame {
+    key: String,
+    sort_name: String,
+    values: Vec<IndexedValue>,
+}
+
+/// Pre-tokenized titles, developers, genres and descriptions for ranked search.
+///
+/// Matching is accent- and case-insensitive, accepts word prefixes (for
+/// search-as-you-type) and tolerates a typo or two in longer words. Every query
+/// word has to match somewhere for a game to be returned.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    games: Vec<IndexedGame>,
+}
+
+impl SearchIndex {
+    /// Build an index from games plus any cached metadata (keyed by unique key)
+    pub fn build(games: &[Game], metadata: &HashMap<String, GameMetadata>) -> Self {
+        let games = games
+            .iter()
+            .map(|game| {
+                let key = game.unique_key();
+                let mut values = vec![IndexedValue::new(SearchField::Title, &game.name)];
+
+                if let Some(meta) = metadata.get(&key) {
+                    values.extend(
+                        meta.developers
+                            .iter()
+                            .map(|d| IndexedValue::new(SearchField::Developer, d)),
+                    );
+                    values.extend(
+                        meta.genres
+                            .iter()
+                            .map(|g| IndexedValue::new(SearchField::Genre, g)),
+                    );
+                    values.extend(
+                        meta.description
+                            .iter()
+                            .map(|d| IndexedValue::new(SearchField::Description, d)),
+                    );
+                }
+
+                IndexedGame {
+                    key,
+                    sort_name: normalize(&game.name),
+                    values,
+                }
+            })
+            .collect();
+
+        Self { games }
+    }
+
+    /// Number of indexed games
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Check if the index has no games
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Rank games against a free-text query, returning at most `limit` results
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        self.search_where(query, limit, |_| true)
+    }
+
+    /// Like `search`, but only games whose unique key passes `keep` are ranked
+    pub fn search_where(
+        &self,
+        query: &str,
+        limit: usize,
+        keep: impl Fn(&str) -> bool,
+    ) -> Vec<SearchResult> {
+        let words: Vec<String> = tokenize(query).into_iter().map(|t| t.text).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scored: Vec<(&IndexedGame, SearchResult)> = self
+            .games
+            .iter()
+            .filter(|game| keep(&game.key))
+            .filter_map(|game| game.score(&words).map(|result| (game, result)))
+            .collect();
+
+        scored.sort_by(|(a, ra), (b, rb)| {
+            rb.score
+                .partial_cmp(&ra.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.sort_name.cmp(&b.sort_name))
+        });
+
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, result)| result)
+            .collect()
+    }
+}
+
+impl IndexedValue {
+    fn new(field: SearchField, value: &str) -> Self {
+        Self {
+            field,
+            value: value.to_string(),
+            tokens: tokenize(value),
+        }
+    }
+}
+
+impl IndexedGame {
+    /// Score this game against the query words, or None if any word is missing
+    fn score(&self, words: &[String]) -> Option<SearchResult> {
+        let mut score = 0.0;
+        let mut hits: Vec<(usize, usize)> = Vec::new();
+
+        for word in words {
+            let mut best: Option<(f32, usize, usize)> = None;
+            for (vi, value) in self.values.iter().enumerate() {
+                for (ti, token) in value.tokens.iter().enumerate() {
+                    let Some(quality) = match_quality(word, &token.text) else {
+                        continue;
+                    };
+                    let weighted = quality * value.field.boost();
+                    if best.is_none_or(|(s, _, _)| weighted > s) {
+                        best = Some((weighted, vi, ti));
+                    }
+                }
+            }
+
+            let (weighted, vi, ti) = best?;
+            score += weighted;
+            hits.push((vi, ti));
+        }
+
+        // Typing the whole title should beat titles that merely contain it
+        let title = &self.values[0];
+        let title_words: Vec<&str> = title.tokens.iter().map(|t| t.text.as_str()).collect();
+        if title_words == words {
+            score += SearchField::Title.boost();
+        }
+
+        Some(SearchResult {
+            key: self.key.clone(),
+            score,
+            highlights: self.highlights(hits),
+        })
+    }
+
+    fn highlights(&self, mut hits: Vec<(usize, usize)>) -> Vec<SearchHighlight> {
+        hits.sort_unstable();
+        hits.dedup();
+
+        let mut highlights: Vec<(usize, SearchHighlight)> = Vec::new();
+        for (vi, ti) in hits {
+            let value = &self.values[vi];
+            let token = &value.tokens[ti];
+            match highlights.iter_mut().find(|(i, _)| *i == vi) {
+                Some((_, highlight)) => highlight.ranges.push((token.start, token.end)),
+                None => highlights.push((
+                    vi,
+                    SearchHighlight {
+                        field: value.field,
+                        value: value.value.clone(),
+                        ranges: vec![(token.start, token.end)],
+                    },
+                )),
+            }
+        }
+
+        highlights.into_iter().map(|(_, h)| h).collect()
+    }
+}
+
+/// How well a query word matches an indexed word, from 0 to 1
+fn match_quality(word: &str, token: &str) -> Option<f32> {
+    if word == token {
+        return Some(1.0);
+    }
+
+    let word_len = word.chars().count();
+    let token_len = token.chars().count();
+
+    if token.starts_with(word) {
+        return Some(0.7 + 0.2 * word_len as f32 / token_len as f32);
+    }
+
+    let allowed = match word_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    if allowed > 0 {
+        let distance = edit_distance(word, token);
+        if distance <= allowed {
+            return Some(0.6 - 0.15 * (distance - 1) as f32);
+        }
+
+        // Typo in a word that's still being typed
+        let prefix: String = token.chars().take(word_len).collect();
+        let distance = edit_distance(word, &prefix);
+        if distance <= allowed {
+            return Some(0.5 - 0.15 * (distance - 1) as f32);
+        }
+    }
+
+    if word_len >= 3 && token.contains(word) {
+        return Some(0.4);
+    }
+
+    None
+}
+
+/// Edit distance counting insertions, deletions, substitutions and
+/// swaps of adjacent characters as one edit each
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Lowercase and strip accents ("Pokémon" -> "pokemon")
+pub fn normalize(text: &str) -> String {
+    text.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Split text into normalized words, tracking their UTF-16 offsets.
+/// Apostrophes are dropped rather than splitting ("Assassin's" -> "assassins").
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut start = 0;
+    let mut offset = 0;
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if word.is_empty() {
+                start = offset;
+            }
+            word.push(c);
+        } else if !word.is_empty() && !matches!(c, '\'' | '\u{2019}') {
+            tokens.push(Token {
+                text: normalize(&std::mem::take(&mut word)),
+                start,
+                end: offset,
+            });
+        }
+        offset += c.len_utf16();
+    }
+
+    if !word.is_empty() {
+        tokens.push(Token {
+            text: normalize(&word),
+            start,
+            end: offset,
+        });
+    }
+    tokens
+}
+
+/// An index of the whole library, with the games that are hidden from search
+/// unless asked for
+#[derive(Debug, Default)]
+pub struct LibrarySearch {
+    index: SearchIndex,
+    hidden: HashSet<String>,
+}
+
+impl LibrarySearch {
+    pub fn new(index: SearchIndex, hidden: HashSet<String>) -> Self {
+        Self { index, hidden }
+    }
+
+    /// Rank games against a query, leaving hidden games out unless `include_hidden`
+    pub fn search(&self, query: &str, limit: usize, include_hidden: bool) -> Vec<SearchResult> {
+        self.index.search_where(query, limit, |key| {
+            include_hidden || !self.hidden.contains(key)
+        })
+    }
+}
+
+/// The library index kept between searches, so typing doesn't rebuild it on
+/// every keystroke. Whatever changes the games, their metadata or the hidden
+/// list has to `invalidate` it.
+#[derive(Default)]
+pub struct SearchCache {
+    current: Mutex<CacheSlot>,
+}
+
+#[derive(Default)]
+struct CacheSlot {
+    index: Option<Arc<LibrarySearch>>,
+    generation: u64,
+}
+
+impl SearchCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop the index; the next search builds a new one
+    pub fn invalidate(&self) {
+        if let Ok(mut slot) = self.current.lock() {
+            slot.index = None;
+            slot.generation += 1;
+        }
+    }
+
+    /// The cached index, or a new one from `build`. The lock isn't held while
+    /// building; an index invalidated in the meantime is used once but not kept.
+    pub fn get_or_build<E>(
+        &self,
+        build: impl FnOnce() -> Result<LibrarySearch, E>,
+    ) -> Result<Arc<LibrarySearch>, E> {
+        let generation = match self.current.lock() {
+            Ok(slot) => match &slot.index {
+                Some(index) => return Ok(Arc::clone(index)),
+                None => slot.generation,
+            },
+            Err(_) => return build().map(Arc::new),
+        };
+
+        let index = Arc::new(build()?);
+        if let Ok(mut slot) = self.current.lock() {
+            if slot.generation == generation {
+                slot.index = Some(Arc::clone(&index));
+            }
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+
+    fn index() -> SearchIndex {
+        let games = vec![
+            Game::new(
+                "1".to_string(),
+                "Pokémon Sword".to_string(),
+                StoreType::Local,
+            ),
+            Game::new("2".to_string(), "The Witcher 3".to_string(), StoreType::Gog),
+            Game::new("hades".to_string(), "Hades".to_string(), StoreType::Epic),
+            Game::new("3".to_string(), "Portal 2".to_string(), StoreType::Steam),
+            Game::new("4".to_string(), "Portal".to_string(), StoreType::Steam),
+            Game::new(
+                "5".to_string(),
+                "Assassin's Creed".to_string(),
+                StoreType::Steam,
+            ),
+        ];
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "epic:hades".to_string(),
+            GameMetadata {
+                genres: vec!["Roguelike".to_string(), "Action".to_string()],
+                developers: vec!["Supergiant Games".to_string()],
+                publishers: Vec::new(),
+                description: Some("Defy the god of the dead.".to_string()),
+            },
+        );
+
+        SearchIndex::build(&games, &metadata)
+    }
+
+    fn keys(query: &str) -> Vec<String> {
+        index()
+            .search(query, 10)
+            .into_iter()
+            .map(|r| r.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_strips_accents() {
+        assert_eq!(normalize("Pokémon"), "pokemon");
+        assert_eq!(normalize("ÉLDEN Ring"), "elden ring");
+    }
+
+    #[test]
+    fn test_tokenize_offsets_are_utf16() {
+        let tokens = tokenize("Pokémon Sword");
+        assert_eq!(tokens[1].text, "sword");
+        assert_eq!((tokens[1].start, tokens[1].end), (8, 13));
+
+        let tokens = tokenize("Assassin's Creed");
+        assert_eq!(tokens[0].text, "assassins");
+        assert_eq!(tokens.len(), 2);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("witcher", "witcher"), 0);
+        assert_eq!(edit_distance("wticher", "witcher"), 1);
+        assert_eq!(edit_distance("witchr", "witcher"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_search_accent_insensitive() {
+        assert_eq!(keys("pokemon"), vec!["local:1"]);
+    }
+
+    #[test]
+    fn test_search_typo_tolerant() {
+        assert_eq!(keys("wticher"), vec!["gog:2"]);
+        assert_eq!(keys("witchr 3"), vec!["gog:2"]);
+        // Short words must match exactly or by prefix
+        assert!(keys("hsd").is_empty());
+    }
+
+    #[test]
+    fn test_search_prefix_and_apostrophes() {
+        assert_eq!(keys("assassin"), vec!["steam:5"]);
+        assert_eq!(keys("port"), vec!["steam:4", "steam:3"]);
+    }
+
+    #[test]
+    fn test_search_exact_title_ranks_first() {
+        assert_eq!(keys("portal"), vec!["steam:4", "steam:3"]);
+        assert_eq!(keys("portal 2")[0], "steam:3");
+    }
+
+    #[test]
+    fn test_search_metadata_fields() {
+        assert_eq!(keys("supergiant"), vec!["epic:hades"]);
+        assert_eq!(keys("roguelike"), vec!["epic:hades"]);
+        assert_eq!(keys("god dead"), vec!["epic:hades"]);
+    }
+
+    #[test]
+    fn test_search_field_boosts() {
+        let games = vec![
+            Game::new("a".to_string(), "Action Henk".to_string(), StoreType::Steam),
+            Game::new("b".to_string(), "Celeste".to_string(), StoreType::Steam),
+            Game::new("c".to_string(), "Braid".to_string(), StoreType::Steam),
+        ];
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "steam:b".to_string(),
+            GameMetadata {
+                genres: vec!["Action".to_string()],
+                ..Default::default()
+            },
+        );
+        metadata.insert(
+            "steam:c".to_string(),
+            GameMetadata {
+                description: Some("A puzzle game with action".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let results = SearchIndex::build(&games, &metadata).search("action", 10);
+        let keys: Vec<&str> = results.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["steam:a", "steam:b", "steam:c"]);
+    }
+
+    #[test]
+    fn test_search_requires_every_word() {
+        assert!(keys("portal witcher").is_empty());
+        assert!(keys("").is_empty());
+        assert!(keys("  -- ").is_empty());
+    }
+
+    #[test]
+    fn test_search_highlights() {
+        let results = index().search("pokemon swrd", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].highlights,
+            vec![SearchHighlight {
+                field: SearchField::Title,
+                value: "Pokémon Sword".to_string(),
+                ranges: vec![(0, 7), (8, 13)],
+            }]
+        );
+
+        let results = index().search("hades supergiant", 10);
+        let fields: Vec<SearchField> = results[0].highlights.iter().map(|h| h.field).collect();
+        assert_eq!(fields, vec![SearchField::Title, SearchField::Developer]);
+    }
+
+    #[test]
+    fn test_search_limit() {
+        assert_eq!(index().search("portal", 1).len(), 1);
+    }
+
+    #[test]
+    fn test_library_search_leaves_out_hidden_games() {
+        let hidden = HashSet::from(["steam:3".to_string()]);
+        let search = LibrarySearch::new(index(), hidden);
+
+        let visible: Vec<String> = search
+            .search("portal", 1, false)
+            .into_iter()
+            .map(|r| r.key)
+            .collect();
+        assert_eq!(visible, vec!["steam:4"]);
+        assert_eq!(search.search("portal", 10, true).len(), 2);
+    }
+
+    #[test]
+    fn test_search_cache_reuses_index_until_invalidated() {
+        let cache = SearchCache::new();
+        let mut builds = 0;
+        let mut build = || {
+            builds += 1;
+            Ok::<_, ()>(LibrarySearch::new(index(), HashSet::new()))
+        };
+
+        let first = cache.get_or_build(&mut build).unwrap();
+        let second = cache.get_or_build(&mut build).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        cache.invalidate();
+        let third = cache.get_or_build(&mut build).unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(builds, 2);
+    }
+
+    #[test]
+    fn test_search_cache_drops_index_invalidated_while_building() {
+        let cache = SearchCache::new();
+
+        let stale = cache
+            .get_or_build(|| {
+                cache.invalidate();
+                Ok::<_, ()>(LibrarySearch::default())
+            })
+            .unwrap();
+        let fresh = cache
+            .get_or_build(|| Ok::<_, ()>(LibrarySearch::new(index(), HashSet::new())))
+            .unwrap();
+
+        assert!(!Arc::ptr_eq(&stale, &fresh));
+        assert_eq!(fresh.search("portal", 10, false).len(), 2);
+    }
+}
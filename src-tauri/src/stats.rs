@@ -0,0 +1,549 @@
+use crate::storage::PlaySession;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Calendar period used to bucket playtime
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsPeriod {
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+/// Playtime totals for a single game
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GamePlaytime {
+    pub game_key: String,
+    pub total_seconds: u64,
+    pub session_count: u32,
+    pub longest_session_seconds: u64,
+    pub first_played: u64,
+    pub last_played: u64,
+}
+
+/// Playtime totals for a single store
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StorePlaytime {
+    pub store: String,
+    pub total_seconds: u64,
+    pub session_count: u32,
+    pub game_count: u32,
+}
+
+/// Playtime within one day, week or month
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PeriodPlaytime {
+    /// Local date the period starts on (YYYY-MM-DD)
+    pub start_date: String,
+    /// Unix timestamp of the period start (local midnight)
+    pub start_timestamp: i64,
+    pub total_seconds: u64,
+    pub session_count: u32,
+}
+
+/// Consecutive days with at least one play session
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+pub struct PlayStreaks {
+    /// Streak ending today (or yesterday, if nothing was played yet today)
+    pub current_days: u32,
+    pub longest_days: u32,
+    /// Local date the longest streak started on (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longest_start_date: Option<String>,
+}
+
+/// Overview of locally recorded playtime
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlaytimeSummary {
+    pub total_seconds: u64,
+    pub session_count: u32,
+    pub today_seconds: u64,
+    pub this_week_seconds: u64,
+    pub this_month_seconds: u64,
+    pub longest_sessions: Vec<PlaySession>,
+    pub streaks: PlayStreaks,
+}
+
+/// Calculates playtime statistics from the recorded session log.
+///
+/// Calendar buckets use the caller's UTC offset so days, weeks and months
+/// line up with the user's local time. Sessions crossing midnight are split
+/// between the days they span.
+pub struct PlaytimeStats<'a> {
+    sessions: Vec<&'a PlaySession>,
+    utc_offset_secs: i64,
+}
+
+impl<'a> PlaytimeStats<'a> {
+    /// Create statistics over all sessions
+    pub fn new(sessions: &'a [PlaySession], utc_offset_secs: i64) -> Self {
+        Self {
+            sessions: sessions.iter().collect(),
+            utc_offset_secs,
+        }
+    }
+
+    /// Restrict statistics to a single game
+    pub fn for_game(mut self, game_key: &str) -> Self {
+        self.sessions.retain(|s| s.game_key == game_key);
+        self
+    }
+
+    /// Restrict statistics to a single store (e.g. "epic")
+    pub fn for_store(mut self, store: &str) -> Self {
+        self.sessions.retain(|s| store_of(&s.game_key) == store);
+        self
+    }
+
+    /// Total playtime in seconds
+    pub fn total_seconds(&self) -> u64 {
+        self.sessions.iter().map(|s| s.duration_secs()).sum()
+    }
+
+    /// Playtime in seconds between two Unix timestamps (sessions are clipped to the range)
+    pub fn seconds_between(&self, from: i64, to: i64) -> u64 {
+        self.sessions
+            .iter()
+            .map(|s| {
+                let start = (s.started_at as i64).max(from);
+                let end = (s.ended_at as i64).min(to);
+                (end - start).max(0) as u64
+            })
+            .sum()
+    }
+
+    /// Playtime per game, most played first
+    pub fn per_game(&self) -> Vec<GamePlaytime> {
+        let mut games: HashMap<&str, GamePlaytime> = HashMap::new();
+
+        for session in &self.sessions {
+            let duration = session.duration_secs();
+            let entry = games
+                .entry(session.game_key.as_str())
+                .or_insert_with(|| GamePlaytime {
+                    game_key: session.game_key.clone(),
+                    total_seconds: 0,
+                    session_count: 0,
+                    longest_session_seconds: 0,
+                    first_played: session.started_at,
+                    last_played: session.started_at,
+                });
+            entry.total_seconds += duration;
+            entry.session_count += 1;
+            entry.longest_session_seconds = entry.longest_session_seconds.max(duration);
+            entry.first_played = entry.first_played.min(session.started_at);
+            entry.last_played = entry.last_played.max(session.started_at);
+        }
+
+        let mut games: Vec<GamePlaytime> = games.into_values().collect();
+        games.sort_by(|a, b| {
+            b.total_seconds
+                .cmp(&a.total_seconds)
+                .then_with(|| a.game_key.cmp(&b.game_key))
+        });
+        games
+    }
+
+    /// Playtime per store, most played first
+    pub fn per_store(&self) -> Vec<StorePlaytime> {
+        let mut stores: HashMap<&str, (StorePlaytime, HashSet<&str>)> = HashMap::new();
+
+        for session in &self.sessions {
+            let store = store_of(&session.game_key);
+            let (entry, games) = stores.entry(store).or_insert_with(|| {
+                (
+                    StorePlaytime {
+                        store: store.to_string(),
+                        total_seconds: 0,
+                        session_count: 0,
+                        game_count: 0,
+                    },
+                    HashSet::new(),
+                )
+            });
+            entry.total_seconds += session.duration_secs();
+            entry.session_count += 1;
+            games.insert(session.game_key.as_str());
+        }
+
+        let mut stores: Vec<StorePlaytime> = stores
+            .into_values()
+            .map(|(mut entry, games)| {
+                entry.game_count = games.len() as u32;
+                entry
+            })
+            .collect();
+        stores.sort_by(|a, b| {
+            b.total_seconds
+                .cmp(&a.total_seconds)
+                .then_with(|| a.store.cmp(&b.store))
+        });
+        stores
+    }
+
+    /// Playtime bucketed by day, week or month, oldest first
+    pub fn by_period(&self, period: StatsPeriod) -> Vec<PeriodPlaytime> {
+        // (seconds, sessions that started in this bucket)
+        let mut buckets: BTreeMap<i64, (u64, u32)> = BTreeMap::new();
+
+        for session in &self.sessions {
+            let start_day = period_start_day(self.local_day(session.started_at as i64), period);
+            buckets.entry(start_day).or_default().1 += 1;
+
+            for (day, seconds) in self.split_by_day(session) {
+                buckets.entry(period_start_day(day, period)).or_default().0 += seconds;
+            }
+        }
+
+        buckets
+            .into_iter()
+            .map(|(day, (total_seconds, session_count))| PeriodPlaytime {
+                start_date: format_date(day),
+                start_timestamp: self.day_start(day),
+                total_seconds,
+                session_count,
+            })
+            .collect()
+    }
+
+    /// Longest sessions first
+    pub fn longest_sessions(&self, limit: usize) -> Vec<PlaySession> {
+        let mut sessions = self.sessions.clone();
+        sessions.sort_by(|a, b| {
+            b.duration_secs()
+                .cmp(&a.duration_secs())
+                .then_with(|| b.started_at.cmp(&a.started_at))
+        });
+        sessions.into_iter().take(limit).cloned().collect()
+    }
+
+    /// Current and longest streaks of consecutive play days as of `now`
+    pub fn streaks(&self, now: i64) -> PlayStreaks {
+        let days: BTreeSet<i64> = self
+            .sessions
+            .iter()
+            .flat_map(|s| self.split_by_day(s).into_iter().map(|(day, _)| day))
+            .collect();
+
+        let mut streaks = PlayStreaks::default();
+        let mut run_start = 0;
+        let mut run_length = 0;
+        let mut previous: Option<i64> = None;
+
+        for &day in &days {
+            if previous == Some(day - 1) {
+                run_length += 1;
+            } else {
+                run_start = day;
+                run_length = 1;
+            }
+            if run_length > streaks.longest_days {
+                streaks.longest_days = run_length;
+                streaks.longest_start_date = Some(format_date(run_start));
+            }
+            previous = Some(day);
+        }
+
+        let today = self.local_day(now);
+        if let Some(last) = previous {
+            if last == today || last == today - 1 {
+                streaks.current_days = run_length;
+            }
+        }
+
+        streaks
+    }
+
+    /// Overview for the current day, week and month as of `now`
+    pub fn summary(&self, now: i64) -> PlaytimeSummary {
+        let today = self.local_day(now);
+        let week_start = period_start_day(today, StatsPeriod::Week);
+        let month_start = period_start_day(today, StatsPeriod::Month);
+
+        PlaytimeSummary {
+            total_seconds: self.total_seconds(),
+            session_count: self.sessions.len() as u32,
+            today_seconds: self.seconds_between(self.day_start(today), now),
+            this_week_seconds: self.seconds_between(self.day_start(week_start), now),
+            this_month_seconds: self.seconds_between(self.day_start(month_start), now),
+            longest_sessions: self.longest_sessions(5),
+            streaks: self.streaks(now),
+        }
+    }
+
+    /// Local day number (days since 1970-01-01) of a Unix timestamp
+    fn local_day(&self, timestamp: i64) -> i64 {
+        (timestamp + self.utc_offset_secs).div_euclid(SECS_PER_DAY)
+    }
+
+    /// Unix timestamp of local midnight on a day
+    fn day_start(&self, day: i64) -> i64 {
+        day * SECS_PER_DAY - self.utc_offset_secs
+    }
+
+    /// Split a session into seconds played on each local day
+    fn split_by_day(&self, session: &PlaySession) -> Vec<(i64, u64)> {
+        let mut parts = Vec::new();
+        let mut start = session.started_at as i64;
+        let end = session.ended_at as i64;
+
+        while start < end {
+            let day = self.local_day(start);
+            let segment_end = end.min(self.day_start(day + 1));
+            parts.push((day, (segment_end - start) as u64));
+            start = segment_end;
+        }
+
+        parts
+    }
+}
+
+/// Clip sessions to those played at or after `since`, trimming sessions that started earlier
+pub fn sessions_since(sessions: &[PlaySession], since: u64) -> Vec<PlaySession> {
+    sessions
+        .iter()
+        .filter(|s| s.ended_at > since)
+        .map(|s| PlaySession {
+            started_at: s.started_at.max(since),
+            ..s.clone()
+        })
+        .collect()
+}
+
+/// Store id part of a unique key ("steam:440" -> "steam")
+fn store_of(game_key: &str) -> &str {
+    game_key.split(':').next().unwrap_or(game_key)
+}
+
+/// First day of the period containing `day`
+fn period_start_day(day: i64, period: StatsPeriod) -> i64 {
+    match period {
+        StatsPeriod::Day => day,
+        // 1970-01-01 was a Thursday, so Monday is 3 days before it
+        StatsPeriod::Week => day - (day + 3).rem_euclid(7),
+        StatsPeriod::Month => {
+            let (year, month, _) = civil_from_days(day);
+            days_from_civil(year, month, 1)
+        }
+    }
+}
+
+fn format_date(day: i64) -> String {
+    let (year, month, date) = civil_from_days(day);
+    format!("{year:04}-{month:02}-{date:02}")
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) date
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm for the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Convert a (year, month, day) date to days since 1970-01-01
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::SessionExitStatus;
+
+    // 2026-10-15 00:00:00 UTC (a Thursday)
+    const OCT_15: u64 = 1_792_022_400;
+    const HOUR: u64 = 3_600;
+    const DAY: u64 = 86_400;
+
+    fn session(game_key: &str, started_at: u64, duration: u64) -> PlaySession {
+        PlaySession {
+            game_key: game_key.to_string(),
+            started_at,
+            ended_at: started_at + duration,
+            exit_status: SessionExitStatus::Exited,
+        }
+    }
+
+    fn sample_sessions() -> Vec<PlaySession> {
+        vec![
+            session("steam:440", OCT_15 + 10 * HOUR, 2 * HOUR),
+            session("steam:440", OCT_15 + DAY + 20 * HOUR, HOUR),
+            session("epic:Fortnite", OCT_15 + DAY + 12 * HOUR, 3 * HOUR),
+            session("gog:1207", OCT_15 - 20 * DAY, HOUR / 2),
+        ]
+    }
+
+    #[test]
+    fn test_civil_date_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(format_date((OCT_15 / DAY) as i64), "2026-10-15");
+        assert_eq!(days_from_civil(2026, 10, 15), (OCT_15 / DAY) as i64);
+        assert_eq!(
+            days_from_civil(2024, 2, 29) + 1,
+            days_from_civil(2024, 3, 1)
+        );
+    }
+
+    #[test]
+    fn test_total_seconds() {
+        let sessions = sample_sessions();
+        let stats = PlaytimeStats::new(&sessions, 0);
+        assert_eq!(stats.total_seconds(), 6 * HOUR + HOUR / 2);
+        assert_eq!(stats.for_game("steam:440").total_seconds(), 3 * HOUR);
+    }
+
+    #[test]
+    fn test_per_game_sorted_by_playtime() {
+        let sessions = sample_sessions();
+        let games = PlaytimeStats::new(&sessions, 0).per_game();
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].game_key, "epic:Fortnite");
+        assert_eq!(games[1].game_key, "steam:440");
+        assert_eq!(games[1].session_count, 2);
+        assert_eq!(games[1].longest_session_seconds, 2 * HOUR);
+        assert_eq!(games[1].first_played, OCT_15 + 10 * HOUR);
+        assert_eq!(games[1].last_played, OCT_15 + DAY + 20 * HOUR);
+    }
+
+    #[test]
+    fn test_per_store() {
+        let sessions = sample_sessions();
+        let stores = PlaytimeStats::new(&sessions, 0).per_store();
+
+        assert_eq!(stores[0].store, "epic");
+        assert_eq!(stores[1].store, "steam");
+        assert_eq!(stores[1].session_count, 2);
+        assert_eq!(stores[1].game_count, 1);
+        assert_eq!(stores[2].store, "gog");
+    }
+
+    #[test]
+    fn test_by_day_splits_sessions_across_midnight() {
+        let sessions = vec![session("steam:440", OCT_15 + 23 * HOUR, 2 * HOUR)];
+        let days = PlaytimeStats::new(&sessions, 0).by_period(StatsPeriod::Day);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].start_date, "2026-10-15");
+        assert_eq!(days[0].total_seconds, HOUR);
+        assert_eq!(days[0].session_count, 1);
+        assert_eq!(days[1].start_date, "2026-10-16");
+        assert_eq!(days[1].total_seconds, HOUR);
+        assert_eq!(days[1].session_count, 0);
+    }
+
+    #[test]
+    fn test_by_day_respects_utc_offset() {
+        // 23:00 UTC is already the next day at UTC+2
+        let sessions = vec![session("steam:440", OCT_15 + 23 * HOUR, HOUR / 2)];
+        let days = PlaytimeStats::new(&sessions, 2 * HOUR as i64).by_period(StatsPeriod::Day);
+
+        assert_eq!(days[0].start_date, "2026-10-16");
+        assert_eq!(days[0].start_timestamp, (OCT_15 + DAY - 2 * HOUR) as i64);
+    }
+
+    #[test]
+    fn test_by_week_starts_on_monday() {
+        let sessions = sample_sessions();
+        let weeks = PlaytimeStats::new(&sessions, 0).by_period(StatsPeriod::Week);
+
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[1].start_date, "2026-10-12");
+        assert_eq!(weeks[1].total_seconds, 6 * HOUR);
+    }
+
+    #[test]
+    fn test_by_month() {
+        let sessions = sample_sessions();
+        let months = PlaytimeStats::new(&sessions, 0).by_period(StatsPeriod::Month);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].start_date, "2026-09-01");
+        assert_eq!(months[1].start_date, "2026-10-01");
+        assert_eq!(months[1].session_count, 3);
+    }
+
+    #[test]
+    fn test_longest_sessions() {
+        let sessions = sample_sessions();
+        let longest = PlaytimeStats::new(&sessions, 0).longest_sessions(2);
+
+        assert_eq!(longest.len(), 2);
+        assert_eq!(longest[0].game_key, "epic:Fortnite");
+        assert_eq!(longest[1].duration_secs(), 2 * HOUR);
+    }
+
+    #[test]
+    fn test_streaks() {
+        let sessions = vec![
+            session("steam:440", OCT_15 - 10 * DAY, HOUR),
+            session("steam:440", OCT_15 - 9 * DAY, HOUR),
+            session("steam:440", OCT_15 - 8 * DAY, HOUR),
+            session("steam:440", OCT_15, HOUR),
+            session("steam:440", OCT_15 + DAY, HOUR),
+        ];
+        let stats = PlaytimeStats::new(&sessions, 0);
+
+        let streaks = stats.streaks((OCT_15 + 2 * DAY + HOUR) as i64);
+        assert_eq!(streaks.current_days, 2);
+        assert_eq!(streaks.longest_days, 3);
+        assert_eq!(streaks.longest_start_date.as_deref(), Some("2026-10-05"));
+
+        let streaks = stats.streaks((OCT_15 + 3 * DAY) as i64);
+        assert_eq!(streaks.current_days, 0);
+    }
+
+    #[test]
+    fn test_summary_this_week() {
+        let sessions = sample_sessions();
+        let now = (OCT_15 + 2 * DAY) as i64;
+        let summary = PlaytimeStats::new(&sessions, 0).summary(now);
+
+        assert_eq!(summary.session_count, 4);
+        assert_eq!(summary.today_seconds, 0);
+        assert_eq!(summary.this_week_seconds, 6 * HOUR);
+        assert_eq!(summary.this_month_seconds, 6 * HOUR);
+        assert_eq!(summary.longest_sessions.len(), 4);
+    }
+
+    #[test]
+    fn test_for_store() {
+        let sessions = sample_sessions();
+        let stats = PlaytimeStats::new(&sessions, 0).for_store("gog");
+        assert_eq!(stats.total_seconds(), HOUR / 2);
+    }
+
+    #[test]
+    fn test_sessions_since_clips_and_filters() {
+        let sessions = sample_sessions();
+        let recent = sessions_since(&sessions, OCT_15 + 11 * HOUR);
+
+        assert_eq!(recent.len(), 3);
+        assert_eq!(recent[0].started_at, OCT_15 + 11 * HOUR);
+        assert_eq!(recent[0].duration_secs(), HOUR);
+    }
+
+    #[test]
+    fn test_stats_period_deserialization() {
+        let period: StatsPeriod = serde_json::from_str("\"week\"").unwrap();
+        assert_eq!(period, StatsPeriod::Week);
+    }
+}
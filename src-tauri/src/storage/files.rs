@@ -0,0 +1,245 @@
+use crate::launcher_core::LauncherError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// How many earlier versions of each data file are kept
+pub const BACKUP_COUNT: usize = 3;
+
+/// `{file}.bak.{n}`; 1 is the newest
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    with_suffix(path, &format!("bak.{n}"))
+}
+
+/// Replace a file so that it ends up either fully written or untouched: the
+/// data goes to a temp file that is synced to disk, then renamed over it.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = with_suffix(path, "tmp");
+    let written = File::create(&tmp).and_then(|mut file| {
+        file.write_all(bytes)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    sync_parent(path);
+    Ok(())
+}
+
+/// Move backups 1..N-1 up a slot, dropping the oldest, so slot 1 is free
+pub fn shift_backups(path: &Path) -> io::Result<()> {
+    for n in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, n + 1))?;
+        }
+    }
+    Ok(())
+}
+
+/// Move an unusable file out of the way, keeping it for diagnostics.
+/// Returns where it went.
+pub fn set_aside(path: &Path) -> io::Result<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let aside = with_suffix(path, &format!("corrupt-{now}"));
+    fs::rename(path, &aside)?;
+    Ok(aside)
+}
+
+/// Read a JSON data file (the default if it doesn't exist). A file that no
+/// longer parses is set aside and replaced by its newest valid backup, so
+/// one bad write doesn't break every command that reads it.
+pub fn load_json<T: DeserializeOwned + Default>(
+    path: &Path,
+    what: &str,
+) -> Result<T, LauncherError> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = fs::read(path)
+        .map_err(|e| LauncherError::ConfigError(format!("Failed to read {what}: {e}")))?;
+    match serde_json::from_slice(&content) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            log::error!("{what} is corrupt ({e}), restoring the last good copy");
+            recover_json(path, what)
+        }
+    }
+}
+
+/// Write a JSON data file atomically, keeping the current version (if it is
+/// valid) as the newest backup
+pub fn save_json<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<(), LauncherError> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to serialize {what}: {e}")))?;
+
+    if is_valid_json(path) {
+        shift_backups(path)
+            .and_then(|_| fs::copy(path, backup_path(path, 1)).map(|_| ()))
+            .map_err(|e| LauncherError::ConfigError(format!("Failed to back up {what}: {e}")))?;
+    }
+    write_atomic(path, content.as_bytes())
+        .map_err(|e| LauncherError::ConfigError(format!("Failed to write {what}: {e}")))
+}
+
+fn recover_json<T: DeserializeOwned + Default>(
+    path: &Path,
+    what: &str,
+) -> Result<T, LauncherError> {
+    let aside = set_aside(path)
+        .map_err(|e| LauncherError::ConfigError(format!("Failed to move corrupt {what}: {e}")))?;
+
+    for n in 1..=BACKUP_COUNT {
+        let backup = backup_path(path, n);
+        let Ok(content) = fs::read(&backup) else {
+            continue;
+        };
+        if let Ok(value) = serde_json::from_slice(&content) {
+            write_atomic(path, &content).map_err(|e| {
+                LauncherError::ConfigError(format!("Failed to restore {what}: {e}"))
+            })?;
+            log::warn!("Restored {what} from {}", backup.display());
+            return Ok(value);
+        }
+    }
+
+    log::error!(
+        "No usable backup of {what}; starting empty (the corrupt copy is at {})",
+        aside.display()
+    );
+    Ok(T::default())
+}
+
+fn is_valid_json(path: &Path) -> bool {
+    fs::read(path)
+        .ok()
+        .is_some_and(|content| serde_json::from_slice::<serde_json::Value>(&content).is_ok())
+}
+
+/// `{file}.{suffix}` next to the file
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name: OsString = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Make a rename durable: on Unix the directory entry needs its own sync
+#[cfg(unix)]
+fn sync_parent(path: &Path) {
+    if let Some(dir) = path.parent() {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    type Data = HashMap<String, u32>;
+
+    fn data(value: u32) -> Data {
+        HashMap::from([("value".to_string(), value)])
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.json");
+
+        write_atomic(&path, b"one").unwrap();
+        write_atomic(&path, b"two").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"two");
+        assert!(!with_suffix(&path, "tmp").exists());
+    }
+
+    #[test]
+    fn test_save_json_rotates_backups() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.json");
+
+        for value in 1..=5 {
+            save_json(&path, &data(value), "data").unwrap();
+        }
+
+        assert_eq!(load_json::<Data>(&path, "data").unwrap(), data(5));
+        for n in 1..=BACKUP_COUNT {
+            let backup: Data =
+                serde_json::from_slice(&fs::read(backup_path(&path, n)).unwrap()).unwrap();
+            assert_eq!(backup, data(5 - n as u32));
+        }
+        assert!(!backup_path(&path, BACKUP_COUNT + 1).exists());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_not_backed_up() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.json");
+        save_json(&path, &data(1), "data").unwrap();
+        save_json(&path, &data(2), "data").unwrap();
+        fs::write(&path, "{\"val").unwrap();
+
+        save_json(&path, &data(3), "data").unwrap();
+
+        // The good copy stays the newest backup
+        let backup: Data =
+            serde_json::from_slice(&fs::read(backup_path(&path, 1)).unwrap()).unwrap();
+        assert_eq!(backup, data(1));
+    }
+
+    #[test]
+    fn test_load_json_recovers_from_backup() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.json");
+        for value in 1..=3 {
+            save_json(&path, &data(value), "data").unwrap();
+        }
+        // A torn write of both the file and its newest backup
+        fs::write(&path, "{\"value\": 4").unwrap();
+        fs::write(backup_path(&path, 1), "").unwrap();
+
+        let loaded: Data = load_json(&path, "data").unwrap();
+
+        assert_eq!(loaded, data(1));
+        // Restored in place, with the corrupt file kept for diagnostics
+        assert_eq!(
+            serde_json::from_slice::<Data>(&fs::read(&path).unwrap()).unwrap(),
+            data(1)
+        );
+        let kept = fs::read_dir(temp.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".corrupt-"))
+            .count();
+        assert_eq!(kept, 1);
+    }
+
+    #[test]
+    fn test_load_json_without_backups_starts_empty() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert_eq!(load_json::<Data>(&path, "data").unwrap(), Data::new());
+        assert!(!path.exists());
+        assert_eq!(load_json::<Data>(&path, "data").unwrap(), Data::new());
+    }
+}
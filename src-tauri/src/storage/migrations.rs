@@ -0,0 +1,278 @@
+use super::files;
+use super::{
+    write_credentials, write_details_cache, write_games_cache, write_play_history,
+    write_play_sessions, write_settings, AppSettings, DetailsCache, GamesCache, PlayHistory,
+    PlaySessions, StoredCredentials,
+};
+use crate::launcher_core::LauncherError;
+use rusqlite::{Connection, TransactionBehavior};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Schema changes, applied in order. A database's `user_version` is the
+/// number of these it has had applied.
+const MIGRATIONS: &[&str] = &[include_str!("migrations/0001_initial.sql")];
+
+/// Suffix given to JSON files once their data has been imported
+pub const MIGRATED_SUFFIX: &str = "migrated";
+
+/// Bring the database up to the current schema. A new database also gets the
+/// data from the JSON files earlier versions stored everything in.
+pub(super) fn migrate(conn: &mut Connection, data_dir: &Path) -> Result<(), LauncherError> {
+    // IMMEDIATE so two processes opening a new database don't both import
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let version: usize = tx.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    if version > MIGRATIONS.len() {
+        return Err(LauncherError::DatabaseError(format!(
+            "Database schema {version} is newer than this version of TenFoot supports"
+        )));
+    }
+    if version == MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+        tx.execute_batch(sql).map_err(|e| {
+            LauncherError::DatabaseError(format!("Migration {} failed: {e}", index + 1))
+        })?;
+    }
+    let imported = if version == 0 {
+        import_json(&tx, data_dir)?
+    } else {
+        Vec::new()
+    };
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    tx.commit()?;
+
+    // Only once the data is safely in the database
+    for path in imported {
+        let mut migrated = path.clone().into_os_string();
+        migrated.push(format!(".{MIGRATED_SUFFIX}"));
+        if let Err(e) = fs::rename(&path, &migrated) {
+            log::warn!("Failed to rename imported {}: {e}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Copy the legacy JSON files into the database, returning the ones imported.
+/// A file that can't be read fails the migration, so it is tried again on the
+/// next start. One that doesn't parse can never be imported; it is set aside
+/// (and logged) rather than blocking startup, so its contents are still kept.
+fn import_json(conn: &Connection, data_dir: &Path) -> Result<Vec<PathBuf>, LauncherError> {
+    let mut imported = Vec::new();
+
+    if let Some((path, creds)) = read_legacy::<StoredCredentials>(data_dir, "credentials.json")? {
+        write_credentials(conn, &creds)?;
+        imported.push(path);
+    }
+    if let Some((path, cache)) = read_legacy::<DetailsCache>(data_dir, "details_cache.json")? {
+        write_details_cache(conn, &cache)?;
+        imported.push(path);
+    }
+    if let Some((path, cache)) = read_legacy::<GamesCache>(data_dir, "games_cache.json")? {
+        write_games_cache(conn, &cache)?;
+        imported.push(path);
+    }
+    if let Some((path, history)) = read_legacy::<PlayHistory>(data_dir, "play_history.json")? {
+        write_play_history(conn, &history)?;
+        imported.push(path);
+    }
+    if let Some((path, sessions)) = read_legacy::<PlaySessions>(data_dir, "play_sessions.json")? {
+        write_play_sessions(conn, &sessions)?;
+        imported.push(path);
+    }
+    if let Some((path, settings)) = read_legacy::<AppSettings>(data_dir, "settings.json")? {
+        write_settings(conn, &settings)?;
+        imported.push(path);
+    }
+
+    if !imported.is_empty() {
+        log::info!(
+            "Imported {} JSON data files into the database",
+            imported.len()
+        );
+    }
+    Ok(imported)
+}
+
+fn read_legacy<T: DeserializeOwned>(
+    data_dir: &Path,
+    file: &str,
+) -> Result<Option<(PathBuf, T)>, LauncherError> {
+    let path = data_dir.join(file);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| LauncherError::ConfigError(format!("Failed to read {file}: {e}")))?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Ok(Some((path, value))),
+        Err(e) => {
+            let aside = files::set_aside(&path).map_err(|e| {
+                LauncherError::ConfigError(format!("Failed to move corrupt {file}: {e}"))
+            })?;
+            log::error!(
+                "Not importing {file}, it is corrupt ({e}); it was moved to {}",
+                aside.display()
+            );
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Storage;
+    use super::*;
+    use crate::launcher_core::{Game, StoreType};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_new_database_is_fully_migrated() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(temp.path()).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), MIGRATIONS.len());
+
+        // Opening again is a no-op
+        drop(storage);
+        let storage = Storage::open(temp.path()).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let temp = TempDir::new().unwrap();
+        let conn = Connection::open(temp.path().join(super::super::DATABASE_FILE)).unwrap();
+        conn.pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .unwrap();
+        drop(conn);
+
+        assert!(matches!(
+            Storage::open(temp.path()),
+            Err(LauncherError::DatabaseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_files_are_imported_once() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("play_history.json"),
+            r#"{"games":{"steam:440":{"last_played":1700000000,"installed_at":1600000000},"gog:1":{"last_played":null,"installed_at":5}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("play_sessions.json"),
+            r#"{"sessions":[{"game_key":"steam:440","started_at":10,"ended_at":70,"exit_status":"exited"}]}"#,
+        )
+        .unwrap();
+        let cache = GamesCache {
+            steam_owned: vec![Game::new("440", "Team Fortress 2", StoreType::Steam)],
+            last_sync: Some(1234),
+            ..Default::default()
+        };
+        fs::write(
+            dir.join("games_cache.json"),
+            serde_json::to_string(&cache).unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("settings.json"),
+            r#"{"launch_on_startup":true,"launch_fullscreen":false,"artwork_cache_mb":64}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("credentials.json"),
+            r#"{"steam":{"api_key":"k","steam_id":"7"}}"#,
+        )
+        .unwrap();
+
+        let storage = Storage::open(dir).unwrap();
+
+        let history = storage.load_play_history().unwrap();
+        assert_eq!(history.games.len(), 2);
+        assert_eq!(history.games["steam:440"].last_played, Some(1_700_000_000));
+        assert_eq!(history.games["steam:440"].installed_at, Some(1_600_000_000));
+        assert_eq!(history.games["gog:1"].last_played, None);
+        assert_eq!(storage.load_play_sessions().unwrap().sessions.len(), 1);
+        assert_eq!(storage.load_owned_games(StoreType::Steam).unwrap().len(), 1);
+        assert_eq!(storage.last_sync(StoreType::Steam).unwrap(), Some(1234));
+        assert!(storage.load_settings().unwrap().launch_on_startup);
+        assert_eq!(
+            storage
+                .load_plaintext_credentials()
+                .unwrap()
+                .unwrap()
+                .steam
+                .unwrap()
+                .steam_id,
+            "7"
+        );
+
+        // The files are kept, but renamed so they aren't imported again
+        assert!(!dir.join("play_history.json").exists());
+        assert!(dir.join("play_history.json.migrated").exists());
+        assert!(dir.join("games_cache.json.migrated").exists());
+
+        storage.record_game_launch("gog:1").unwrap();
+        drop(storage);
+        fs::write(dir.join("play_history.json"), r#"{"games":{}}"#).unwrap();
+        let storage = Storage::open(dir).unwrap();
+        assert_eq!(storage.load_play_history().unwrap().games.len(), 2);
+    }
+
+    #[test]
+    fn test_corrupt_json_is_set_aside() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(dir.join("play_history.json"), "{ truncated").unwrap();
+        fs::write(
+            dir.join("play_sessions.json"),
+            r#"{"sessions":[{"game_key":"steam:440","started_at":10,"ended_at":70,"exit_status":"exited"}]}"#,
+        )
+        .unwrap();
+
+        let storage = Storage::open(dir).unwrap();
+
+        assert!(storage.load_play_history().unwrap().games.is_empty());
+        assert_eq!(storage.load_play_sessions().unwrap().sessions.len(), 1);
+        assert!(dir.join("play_sessions.json.migrated").exists());
+
+        // The corrupt file is kept under a new name, not left to be skipped
+        assert!(!dir.join("play_history.json").exists());
+        let aside: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name.starts_with("play_history.json.corrupt-"))
+            .collect();
+        assert_eq!(aside.len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.join(&aside[0])).unwrap(),
+            "{ truncated"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_json_fails_migration() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        // A directory in place of the file can't be read
+        fs::create_dir(dir.join("play_history.json")).unwrap();
+        fs::write(dir.join("play_sessions.json"), r#"{"sessions":[]}"#).unwrap();
+
+        assert!(Storage::open(dir).is_err());
+
+        // Nothing was recorded as migrated, so the import runs again
+        assert!(dir.join("play_sessions.json").exists());
+        fs::remove_dir(dir.join("play_history.json")).unwrap();
+        let storage = Storage::open(dir).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), MIGRATIONS.len());
+        assert!(dir.join("play_sessions.json.migrated").exists());
+    }
+}
@@ -0,0 +1,48 @@
+-- Small settings-like objects stored whole, as JSON
+CREATE TABLE documents (
+    name TEXT PRIMARY KEY,
+    body TEXT NOT NULL
+);
+
+-- Games owned on a store account, from the last sync
+CREATE TABLE owned_games (
+    unique_key TEXT PRIMARY KEY,
+    store TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    game TEXT NOT NULL
+);
+CREATE INDEX owned_games_by_store ON owned_games (store, position);
+
+-- When each store account was last synced
+CREATE TABLE store_syncs (
+    store TEXT PRIMARY KEY,
+    synced_at INTEGER NOT NULL
+);
+
+-- Catalog metadata saved by Epic syncs, keyed by Epic game id
+CREATE TABLE epic_metadata (
+    game_id TEXT PRIMARY KEY,
+    metadata TEXT NOT NULL
+);
+
+CREATE TABLE play_history (
+    unique_key TEXT PRIMARY KEY,
+    last_played INTEGER,
+    installed_at INTEGER
+);
+
+CREATE TABLE play_sessions (
+    id INTEGER PRIMARY KEY,
+    game_key TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    ended_at INTEGER NOT NULL,
+    exit_status TEXT NOT NULL
+);
+CREATE INDEX play_sessions_by_game ON play_sessions (game_key, started_at);
+
+-- Game details from any store; details is NULL when the store had none
+CREATE TABLE details_cache (
+    unique_key TEXT PRIMARY KEY,
+    details TEXT,
+    fetched_at INTEGER NOT NULL
+);
@@ -1,4 +1,4 @@
-use crate::launcher_core::{Game, LauncherError, StoreType};
+use crate::launcher_core::{Game, GameDetails, LauncherError, StoreType};
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -120,18 +120,6 @@ struct ReleaseInfo {
     date_added: Option<String>,
 }
 
-/// Game details returned to frontend (matches Steam's GameDetails structure)
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct EpicGameDetails {
-    pub description: Option<String>,
-    pub developers: Option<Vec<String>>,
-    pub publishers: Option<Vec<String>>,
-    pub genres: Option<Vec<String>>,
-    pub platforms: Option<Vec<String>>,
-    pub release_date: Option<String>,
-}
-
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct KeyImage {
@@ -193,10 +181,13 @@ impl EpicApi {
                 "grant_type=authorization_code&code={auth_code}&token_type=eg1"
             ))
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         if !response.status().is_success() {
             let status = response.status();
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LauncherError::RateLimited("Epic".to_string()));
+            }
             let body = response.text().unwrap_or_default();
             return Err(LauncherError::AuthRequired(format!(
                 "OAuth failed ({status}): {body}"
@@ -234,10 +225,13 @@ impl EpicApi {
                 "grant_type=refresh_token&refresh_token={refresh_token}&token_type=eg1"
             ))
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         if !response.status().is_success() {
             let status = response.status();
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LauncherError::RateLimited("Epic".to_string()));
+            }
             let body = response.text().unwrap_or_default();
             return Err(LauncherError::AuthRequired(format!(
                 "Token refresh failed ({status}): {body}"
@@ -284,7 +278,7 @@ impl EpicApi {
     pub fn get_library(
         &self,
         credentials: &EpicCredentials,
-    ) -> Result<(Vec<Game>, HashMap<String, EpicGameDetails>), LauncherError> {
+    ) -> Result<(Vec<Game>, HashMap<String, GameDetails>), LauncherError> {
         let mut games = Vec::new();
         let mut metadata = HashMap::new();
         let mut cursor: Option<String> = None;
@@ -305,7 +299,7 @@ impl EpicApi {
                 .get(&url)
                 .header("Authorization", format!("Bearer {access_token}"))
                 .send()
-                .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+                .map_err(LauncherError::from_request)?;
 
             if !response.status().is_success() {
                 let status = response.status();
@@ -315,9 +309,7 @@ impl EpicApi {
                     ));
                 }
                 let body = response.text().unwrap_or_default();
-                return Err(LauncherError::NetworkError(format!(
-                    "Library fetch failed ({status}): {body}"
-                )));
+                return Err(LauncherError::from_status("Epic", status, &body));
             }
 
             let library: LibraryResponse = response
@@ -361,7 +353,7 @@ impl EpicApi {
         credentials: &EpicCredentials,
         namespace: &str,
         item_ids: &[String],
-    ) -> Result<Vec<(Game, EpicGameDetails)>, LauncherError> {
+    ) -> Result<Vec<(Game, GameDetails)>, LauncherError> {
         if item_ids.is_empty() {
             return Ok(Vec::new());
         }
@@ -377,7 +369,7 @@ impl EpicApi {
             .get(&url)
             .header("Authorization", format!("Bearer {access_token}"))
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         if !response.status().is_success() {
             return Ok(Vec::new()); // Don't fail on catalog errors
@@ -406,36 +398,32 @@ impl EpicApi {
                         | "OfferImageTall"
                         | "Thumbnail"
                         | "DieselStoreFrontTall"
-                        | "CodeRedemption_340x440" => {
-                            if !has_cover {
-                                game.set_cover_url(image.url.clone());
-                                has_cover = true;
-                            }
+                        | "CodeRedemption_340x440"
+                            if !has_cover =>
+                        {
+                            game.set_cover_url(image.url.clone());
+                            has_cover = true;
                         }
                         // Wide/hero images
                         "DieselGameBoxWide"
                         | "OfferImageWide"
                         | "DieselStoreFrontWide"
                         | "Featured"
-                        | "featuredMedia" => {
-                            if !has_hero {
-                                game.set_hero_url(image.url.clone());
-                                has_hero = true;
-                            }
+                        | "featuredMedia"
+                            if !has_hero =>
+                        {
+                            game.set_hero_url(image.url.clone());
+                            has_hero = true;
                         }
                         // Logo/icon
-                        "DieselGameBoxLogo" | "ProductLogo" => {
-                            if !has_icon {
-                                game.set_icon_url(image.url.clone());
-                                has_icon = true;
-                            }
+                        "DieselGameBoxLogo" | "ProductLogo" if !has_icon => {
+                            game.set_icon_url(image.url.clone());
+                            has_icon = true;
                         }
                         // Fallback: DieselGameBox can be either
-                        "DieselGameBox" => {
-                            if !has_cover {
-                                game.set_cover_url(image.url.clone());
-                                has_cover = true;
-                            }
+                        "DieselGameBox" if !has_cover => {
+                            game.set_cover_url(image.url.clone());
+                            has_cover = true;
                         }
                         _ => {}
                     }
@@ -489,7 +477,7 @@ impl EpicApi {
                 // Get release date from first release_info
                 let release_date = item.release_info.first().and_then(|r| r.date_added.clone());
 
-                let details = EpicGameDetails {
+                let details = GameDetails {
                     description: item.description.or(item.long_description),
                     developers,
                     publishers,
@@ -521,7 +509,7 @@ impl EpicApi {
             .get(&url)
             .header("Authorization", format!("Bearer {access_token}"))
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         Ok(response.status().is_success())
     }
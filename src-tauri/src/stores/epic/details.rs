@@ -0,0 +1,61 @@
+use crate::launcher_core::{DetailsProvider, GameDetails, LauncherError, StoreType};
+use crate::storage::Storage;
+
+/// Serves Epic game details from the catalog metadata saved by library sync
+pub struct EpicDetailsProvider {
+    storage: Storage,
+}
+
+impl EpicDetailsProvider {
+    /// Create a provider backed by the default app storage
+    pub fn new() -> Result<Self, LauncherError> {
+        Ok(Self {
+            storage: Storage::new()?,
+        })
+    }
+
+    /// Create a provider backed by a specific storage (for testing)
+    pub fn with_storage(storage: Storage) -> Self {
+        Self { storage }
+    }
+}
+
+impl DetailsProvider for EpicDetailsProvider {
+    fn store(&self) -> StoreType {
+        StoreType::Epic
+    }
+
+    fn fetch_details(&self, game_id: &str) -> Result<Option<GameDetails>, LauncherError> {
+        let metadata = self.storage.get_epic_metadata(game_id)?;
+        Ok(metadata.map(GameDetails::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::EpicGameMetadata;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fetch_details_from_synced_metadata() {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(temp.path()).unwrap();
+        let metadata = HashMap::from([(
+            "Fortnite".to_string(),
+            EpicGameMetadata {
+                developers: Some(vec!["Epic Games".to_string()]),
+                ..Default::default()
+            },
+        )]);
+        storage.save_epic_metadata(&metadata).unwrap();
+
+        let provider = EpicDetailsProvider::with_storage(storage);
+
+        let details = provider.fetch_details("Fortnite").unwrap().unwrap();
+        assert_eq!(details.developers, Some(vec!["Epic Games".to_string()]));
+        assert!(provider.fetch_details("Unknown").unwrap().is_none());
+        assert_eq!(provider.store(), StoreType::Epic);
+    }
+}
@@ -1,10 +1,12 @@
 pub mod api;
+mod details;
 mod manifest;
 mod paths;
 
 use crate::launcher_core::store::ArtworkType;
 use crate::launcher_core::{Game, GameStore, LauncherError};
-pub use api::{EpicApi, EpicCredentials, EpicGameDetails};
+pub use api::{EpicApi, EpicCredentials};
+pub use details::EpicDetailsProvider;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -125,6 +127,22 @@ impl GameStore for EpicStore {
         // Artwork is typically fetched from the manifest or store API
         None
     }
+
+    fn detected_paths(&self) -> Vec<(&'static str, Option<PathBuf>)> {
+        vec![
+            ("launcher_path", self.paths.launcher_path.clone()),
+            ("launcher_exe", self.paths.launcher_exe.clone()),
+            ("manifests_path", self.paths.manifests_path.clone()),
+        ]
+    }
+
+    fn client_process_names(&self) -> &'static [&'static str] {
+        &[
+            "EpicGamesLauncher",
+            "EpicGamesLauncher.exe",
+            "EpicGamesLauncher-Mac-Shipping",
+        ]
+    }
 }
 
 #[cfg(test)]
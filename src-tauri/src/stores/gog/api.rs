@@ -0,0 +1,324 @@
+use crate::launcher_core::game::GameArtwork;
+use crate::launcher_core::{DetailsProvider, GameDetails, LauncherError, StoreType};
+use serde::Deserialize;
+
+const GOG_API_BASE: &str = "https://api.gog.com";
+
+#[derive(Debug, Deserialize)]
+struct GameResponse {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "_embedded", default)]
+    embedded: Option<Embedded>,
+    #[serde(rename = "_links", default)]
+    links: Option<Links>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Links {
+    #[serde(default)]
+    box_art_image: Option<Link>,
+    #[serde(default)]
+    background_image: Option<Link>,
+    #[serde(default)]
+    logo: Option<Link>,
+    #[serde(default)]
+    icon_square: Option<Link>,
+    #[serde(default)]
+    icon: Option<Link>,
+}
+
+/// Image link; GOG templates the size and format (`{formatter}`, `{ext}`)
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+impl Link {
+    fn expand(&self, ext: &str) -> String {
+        self.href.replace("{formatter}", "").replace("{ext}", ext)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Embedded {
+    #[serde(default)]
+    product: Option<Product>,
+    #[serde(default)]
+    developers: Vec<Named>,
+    #[serde(default)]
+    publisher: Option<Named>,
+    #[serde(default)]
+    tags: Vec<Named>,
+    #[serde(default)]
+    supported_operating_systems: Vec<SupportedOs>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Product {
+    #[serde(default)]
+    global_release_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SupportedOs {
+    operating_system: Named,
+}
+
+/// Client for GOG's public catalog API (no login needed)
+pub struct GogApi {
+    client: reqwest::blocking::Client,
+}
+
+impl GogApi {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::blocking::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| reqwest::blocking::Client::new()),
+        }
+    }
+
+    /// Fetch game details for a GOG product ID
+    pub fn get_game_details(&self, product_id: &str) -> Result<Option<GameDetails>, LauncherError> {
+        match self.get_game(product_id)? {
+            Some(text) => parse_game_details(&text).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the real artwork URLs for a GOG product ID
+    pub fn get_artwork(&self, product_id: &str) -> Result<GameArtwork, LauncherError> {
+        match self.get_game(product_id)? {
+            Some(text) => parse_artwork(&text),
+            None => Ok(GameArtwork::new()),
+        }
+    }
+
+    /// Raw `/v2/games/{id}` response (None if GOG doesn't know the product)
+    fn get_game(&self, product_id: &str) -> Result<Option<String>, LauncherError> {
+        let url = format!("{GOG_API_BASE}/v2/games/{product_id}?locale=en-US");
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(LauncherError::from_request)?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => return Ok(None),
+            status if !status.is_success() => {
+                return Err(LauncherError::from_status("GOG", status, ""))
+            }
+            _ => {}
+        }
+
+        response
+            .text()
+            .map(Some)
+            .map_err(|e| LauncherError::ParseError(e.to_string()))
+    }
+}
+
+impl Default for GogApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DetailsProvider for GogApi {
+    fn store(&self) -> StoreType {
+        StoreType::Gog
+    }
+
+    fn fetch_details(&self, game_id: &str) -> Result<Option<GameDetails>, LauncherError> {
+        self.get_game_details(game_id)
+    }
+}
+
+/// Parse a `/v2/games/{id}` response
+fn parse_game_details(json: &str) -> Result<GameDetails, LauncherError> {
+    let response: GameResponse =
+        serde_json::from_str(json).map_err(|e| LauncherError::ParseError(e.to_string()))?;
+
+    let names = |items: Vec<Named>| -> Option<Vec<String>> {
+        let names: Vec<String> = items.into_iter().map(|n| n.name).collect();
+        (!names.is_empty()).then_some(names)
+    };
+
+    let embedded = response.embedded;
+    let (product, developers, publisher, tags, systems) = match embedded {
+        Some(e) => (
+            e.product,
+            e.developers,
+            e.publisher,
+            e.tags,
+            e.supported_operating_systems,
+        ),
+        None => Default::default(),
+    };
+
+    let platforms = systems
+        .into_iter()
+        .map(|os| match os.operating_system.name.as_str() {
+            "windows" => "Windows".to_string(),
+            "osx" => "macOS".to_string(),
+            "linux" => "Linux".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+
+    Ok(GameDetails {
+        description: response
+            .description
+            .map(|d| strip_html(&d))
+            .filter(|d| !d.is_empty()),
+        developers: names(developers),
+        publishers: names(publisher.into_iter().collect()),
+        genres: names(tags),
+        platforms: Some(platforms).filter(|p: &Vec<String>| !p.is_empty()),
+        release_date: product
+            .and_then(|p| p.global_release_date)
+            .map(|d| d.split('T').next().unwrap_or(&d).to_string()),
+    })
+}
+
+/// Parse the image links of a `/v2/games/{id}` response
+fn parse_artwork(json: &str) -> Result<GameArtwork, LauncherError> {
+    let response: GameResponse =
+        serde_json::from_str(json).map_err(|e| LauncherError::ParseError(e.to_string()))?;
+
+    let Some(links) = response.links else {
+        return Ok(GameArtwork::new());
+    };
+
+    Ok(GameArtwork {
+        cover: links.box_art_image.map(|l| l.expand("jpg")),
+        hero: links.background_image.map(|l| l.expand("jpg")),
+        logo: links.logo.map(|l| l.expand("png")),
+        icon: links.icon_square.or(links.icon).map(|l| l.expand("png")),
+    })
+}
+
+/// GOG descriptions are HTML; keep just the text
+fn strip_html(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_game_details() {
+        let json = r#"{
+            "description": "<p>Become a <b>monster slayer</b> &amp; more.</p>",
+            "_embedded": {
+                "product": { "title": "The Witcher 3", "globalReleaseDate": "2015-05-18T22:00:00+03:00" },
+                "developers": [{ "name": "CD PROJEKT RED" }],
+                "publisher": { "name": "CD PROJEKT RED" },
+                "tags": [{ "name": "RPG" }, { "name": "Open World" }],
+                "supportedOperatingSystems": [
+                    { "operatingSystem": { "name": "windows" } },
+                    { "operatingSystem": { "name": "osx" } }
+                ]
+            }
+        }"#;
+
+        let details = parse_game_details(json).unwrap();
+
+        assert_eq!(
+            details.description.as_deref(),
+            Some("Become a monster slayer & more.")
+        );
+        assert_eq!(details.developers, Some(vec!["CD PROJEKT RED".to_string()]));
+        assert_eq!(details.publishers, Some(vec!["CD PROJEKT RED".to_string()]));
+        assert_eq!(
+            details.genres,
+            Some(vec!["RPG".to_string(), "Open World".to_string()])
+        );
+        assert_eq!(
+            details.platforms,
+            Some(vec!["Windows".to_string(), "macOS".to_string()])
+        );
+        assert_eq!(details.release_date.as_deref(), Some("2015-05-18"));
+    }
+
+    #[test]
+    fn test_parse_game_details_minimal() {
+        let details = parse_game_details("{}").unwrap();
+        assert_eq!(details, GameDetails::default());
+    }
+
+    #[test]
+    fn test_parse_artwork() {
+        let json = r#"{
+            "_links": {
+                "boxArtImage": { "href": "https://images.gog-statics.com/abc{formatter}.{ext}", "templated": true },
+                "backgroundImage": { "href": "https://images.gog-statics.com/bg.jpg" },
+                "logo": { "href": "https://images.gog-statics.com/logo{formatter}.{ext}" },
+                "icon": { "href": "https://images.gog-statics.com/icon.png" }
+            }
+        }"#;
+
+        let artwork = parse_artwork(json).unwrap();
+
+        assert_eq!(
+            artwork.cover.as_deref(),
+            Some("https://images.gog-statics.com/abc.jpg")
+        );
+        assert_eq!(
+            artwork.hero.as_deref(),
+            Some("https://images.gog-statics.com/bg.jpg")
+        );
+        assert_eq!(
+            artwork.logo.as_deref(),
+            Some("https://images.gog-statics.com/logo.png")
+        );
+        assert_eq!(
+            artwork.icon.as_deref(),
+            Some("https://images.gog-statics.com/icon.png")
+        );
+        assert!(!parse_artwork("{}").unwrap().has_any());
+    }
+
+    #[test]
+    fn test_parse_game_details_invalid() {
+        assert!(matches!(
+            parse_game_details("not json"),
+            Err(LauncherError::ParseError(_))
+        ));
+    }
+}
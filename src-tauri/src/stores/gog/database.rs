@@ -2,6 +2,30 @@ use crate::launcher_core::{Game, LauncherError, StoreType};
 use rusqlite::{Connection, OpenFlags};
 use std::path::Path;
 
+/// Galaxy tables the scan reads. Installed games come from the first two
+/// (either will do); the rest fill in titles and install paths.
+pub const GALAXY_TABLES: &[&str] = &[
+    "InstalledBaseProducts",
+    "LibraryReleases",
+    "GamePieces",
+    "GamePieceTypes",
+    "ProductConfiguration",
+];
+
+/// Which of `GALAXY_TABLES` the database doesn't have
+pub fn missing_tables(db_path: &Path) -> Result<Vec<&'static str>, LauncherError> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type='table' AND name=?1")?;
+
+    let mut missing = Vec::new();
+    for table in GALAXY_TABLES {
+        if !stmt.exists([table])? {
+            missing.push(*table);
+        }
+    }
+    Ok(missing)
+}
+
 /// Query installed games from GOG Galaxy's SQLite database
 pub fn query_installed_games(db_path: &Path) -> Result<Vec<Game>, LauncherError> {
     // Open database in read-only mode
@@ -204,6 +228,15 @@ mod tests {
         (temp, db_path)
     }
 
+    #[test]
+    fn test_missing_tables() {
+        let (_temp, db_path) = create_test_db();
+        assert_eq!(
+            missing_tables(&db_path).unwrap(),
+            vec!["GamePieces", "GamePieceTypes"]
+        );
+    }
+
     #[test]
     fn test_query_installed_games_empty_db() {
         let (_temp, db_path) = create_test_db();
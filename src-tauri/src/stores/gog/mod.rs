@@ -1,3 +1,4 @@
+pub mod api;
 mod database;
 mod paths;
 
@@ -6,7 +7,8 @@ use crate::launcher_core::{Game, GameStore, LauncherError};
 use std::path::PathBuf;
 use std::process::Command;
 
-pub use database::query_installed_games;
+pub use api::GogApi;
+pub use database::{missing_tables, query_installed_games, GALAXY_TABLES};
 pub use paths::GogPaths;
 
 /// GOG Galaxy store integration
@@ -100,18 +102,22 @@ impl GameStore for GogStore {
         Ok(())
     }
 
-    fn get_artwork_url(&self, game_id: &str, art_type: ArtworkType) -> Option<String> {
-        // GOG has a CDN for artwork
-        match art_type {
-            ArtworkType::Cover => Some(format!(
-                "https://images.gog-statics.com/{game_id}_cover.jpg"
-            )),
-            ArtworkType::Hero => Some(format!(
-                "https://images.gog-statics.com/{game_id}_background.jpg"
-            )),
-            ArtworkType::Icon => Some(format!("https://images.gog-statics.com/{game_id}_icon.png")),
-            ArtworkType::Logo => Some(format!("https://images.gog-statics.com/{game_id}_logo.png")),
-        }
+    fn get_artwork_url(&self, _game_id: &str, _art_type: ArtworkType) -> Option<String> {
+        // GOG image paths are opaque hashes; the artwork resolver looks them
+        // up through the catalog API instead
+        None
+    }
+
+    fn detected_paths(&self) -> Vec<(&'static str, Option<PathBuf>)> {
+        vec![
+            ("galaxy_path", self.paths.galaxy_path.clone()),
+            ("galaxy_exe", self.paths.galaxy_exe.clone()),
+            ("database_path", self.paths.database_path.clone()),
+        ]
+    }
+
+    fn client_process_names(&self) -> &'static [&'static str] {
+        &["GalaxyClient", "GalaxyClient.exe", "GOG Galaxy"]
     }
 }
 
@@ -221,36 +227,9 @@ mod tests {
     }
 
     #[test]
-    fn test_get_artwork_url_cover() {
-        let store = GogStore::new();
-        let url = store.get_artwork_url("12345", ArtworkType::Cover);
-        assert!(url.is_some());
-        let url_str = url.unwrap();
-        assert!(url_str.contains("12345"));
-        assert!(url_str.contains("cover"));
-    }
-
-    #[test]
-    fn test_get_artwork_url_hero() {
-        let store = GogStore::new();
-        let url = store.get_artwork_url("12345", ArtworkType::Hero);
-        assert!(url.is_some());
-        assert!(url.unwrap().contains("background"));
-    }
-
-    #[test]
-    fn test_get_artwork_url_icon() {
-        let store = GogStore::new();
-        let url = store.get_artwork_url("12345", ArtworkType::Icon);
-        assert!(url.is_some());
-        assert!(url.unwrap().contains("icon"));
-    }
-
-    #[test]
-    fn test_get_artwork_url_logo() {
+    fn test_get_artwork_url_returns_none() {
         let store = GogStore::new();
-        let url = store.get_artwork_url("12345", ArtworkType::Logo);
-        assert!(url.is_some());
-        assert!(url.unwrap().contains("logo"));
+        assert!(store.get_artwork_url("12345", ArtworkType::Cover).is_none());
+        assert!(store.get_artwork_url("12345", ArtworkType::Hero).is_none());
     }
 }
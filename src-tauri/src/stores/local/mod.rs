@@ -0,0 +1,233 @@
+use crate::launcher_core::store::ArtworkType;
+use crate::launcher_core::{Game, GameStore, LauncherError, StoreType};
+use crate::storage::{LocalGame, Storage};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Store for games the user added manually (DRM-free games, emulators, itch.io downloads)
+pub struct LocalStore {
+    storage: Storage,
+}
+
+impl LocalStore {
+    /// Create a local store backed by the default app storage
+    pub fn new() -> Result<Self, LauncherError> {
+        Ok(Self {
+            storage: Storage::new()?,
+        })
+    }
+
+    /// Create a local store backed by a specific storage (for testing)
+    pub fn with_storage(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    fn find_entry(&self, game_id: &str) -> Result<LocalGame, LauncherError> {
+        self.storage
+            .load_local_games()?
+            .games
+            .into_iter()
+            .find(|g| g.id == game_id)
+            .ok_or_else(|| LauncherError::GameNotFound(format!("local:{game_id}")))
+    }
+}
+
+/// Convert a stored local entry into a library game
+pub fn to_game(entry: &LocalGame) -> Game {
+    let mut game = Game::new(entry.id.clone(), entry.name.clone(), StoreType::Local);
+    game.installed = entry.executable.exists();
+    game.executable = Some(entry.executable.clone());
+    // The executable's folder may be shared (/usr/bin, ~/Applications), so only
+    // an explicit working directory counts as the game's folder
+    game.install_path = entry.working_dir.clone();
+    game.cover_url = entry.cover_url.clone();
+    game.hero_url = entry.hero_url.clone();
+    game.icon_url = entry.icon_url.clone();
+    if entry.added_at > 0 {
+        game.installed_at = Some(entry.added_at);
+    }
+    game
+}
+
+impl GameStore for LocalStore {
+    fn store_id(&self) -> &'static str {
+        "local"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Local"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn get_client_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn get_installed_games(&self) -> Result<Vec<Game>, LauncherError> {
+        let local = self.storage.load_local_games()?;
+        Ok(local.games.iter().map(to_game).collect())
+    }
+
+    fn launch_game(&self, game_id: &str) -> Result<(), LauncherError> {
+        let entry = self.find_entry(game_id)?;
+
+        if !entry.executable.exists() {
+            return Err(LauncherError::LaunchError(format!(
+                "Executable not found: {}",
+                entry.executable.display()
+            )));
+        }
+
+        let working_dir = entry
+            .working_dir
+            .clone()
+            .or_else(|| entry.executable.parent().map(PathBuf::from));
+
+        #[cfg(target_os = "macos")]
+        let mut command = if entry.executable.extension().is_some_and(|ext| ext == "app") {
+            // App bundles have to be started through LaunchServices
+            let mut command = Command::new("open");
+            command.arg("-a").arg(&entry.executable).arg("--args");
+            command
+        } else {
+            Command::new(&entry.executable)
+        };
+
+        #[cfg(not(target_os = "macos"))]
+        let mut command = Command::new(&entry.executable);
+
+        command.args(&entry.arguments);
+        if let Some(dir) = working_dir.filter(|d| d.is_dir()) {
+            command.current_dir(dir);
+        }
+
+        command
+            .spawn()
+            .map_err(|e| LauncherError::LaunchError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_artwork_url(&self, game_id: &str, art_type: ArtworkType) -> Option<String> {
+        let entry = self.find_entry(game_id).ok()?;
+        match art_type {
+            ArtworkType::Cover => entry.cover_url,
+            ArtworkType::Hero => entry.hero_url,
+            ArtworkType::Icon => entry.icon_url,
+            ArtworkType::Logo => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_store() -> (TempDir, LocalStore) {
+        let temp = TempDir::new().unwrap();
+        let storage = Storage::open(temp.path()).unwrap();
+        (temp, LocalStore::with_storage(storage))
+    }
+
+    fn entry(name: &str, executable: PathBuf) -> LocalGame {
+        LocalGame {
+            id: String::new(),
+            name: name.to_string(),
+            executable,
+            working_dir: None,
+            arguments: Vec::new(),
+            cover_url: Some("https://example.com/cover.png".to_string()),
+            hero_url: None,
+            icon_url: None,
+            added_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_local_store_identity() {
+        let (_temp, store) = create_test_store();
+        assert_eq!(store.store_id(), "local");
+        assert_eq!(store.display_name(), "Local");
+        assert!(store.is_available());
+        assert!(store.get_client_path().is_none());
+    }
+
+    #[test]
+    fn test_get_installed_games_from_storage() {
+        let (temp, store) = create_test_store();
+        let exe = temp.path().join("game.sh");
+        fs::write(&exe, "").unwrap();
+        let mut celeste = entry("Celeste", exe);
+        celeste.working_dir = Some(temp.path().to_path_buf());
+        store.storage.add_local_game(celeste).unwrap();
+        store
+            .storage
+            .add_local_game(entry("Missing", temp.path().join("nope")))
+            .unwrap();
+
+        let games = store.get_installed_games().unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].unique_key(), "local:celeste");
+        assert_eq!(games[0].store, StoreType::Local);
+        assert!(games[0].installed);
+        assert_eq!(games[0].install_path.as_deref(), Some(temp.path()));
+        assert!(!games[1].installed);
+        assert!(games[1].install_path.is_none());
+    }
+
+    #[test]
+    fn test_get_artwork_url() {
+        let (temp, store) = create_test_store();
+        let added = store
+            .storage
+            .add_local_game(entry("Celeste", temp.path().join("game")))
+            .unwrap();
+
+        assert_eq!(
+            store.get_artwork_url(&added.id, ArtworkType::Cover),
+            Some("https://example.com/cover.png".to_string())
+        );
+        assert!(store
+            .get_artwork_url(&added.id, ArtworkType::Hero)
+            .is_none());
+        assert!(store
+            .get_artwork_url("unknown", ArtworkType::Cover)
+            .is_none());
+    }
+
+    #[test]
+    fn test_launch_game_not_found() {
+        let (_temp, store) = create_test_store();
+        let result = store.launch_game("unknown");
+        assert!(matches!(result, Err(LauncherError::GameNotFound(_))));
+    }
+
+    #[test]
+    fn test_launch_game_missing_executable() {
+        let (temp, store) = create_test_store();
+        let added = store
+            .storage
+            .add_local_game(entry("Gone", temp.path().join("missing")))
+            .unwrap();
+
+        let result = store.launch_game(&added.id);
+        assert!(matches!(result, Err(LauncherError::LaunchError(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_launch_game_spawns_executable() {
+        let (temp, store) = create_test_store();
+        let mut game = entry("True", PathBuf::from("/bin/true"));
+        game.working_dir = Some(temp.path().to_path_buf());
+        let added = store.storage.add_local_game(game).unwrap();
+
+        assert!(store.launch_game(&added.id).is_ok());
+    }
+}
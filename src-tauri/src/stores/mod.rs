@@ -1,7 +1,20 @@
 pub mod epic;
 pub mod gog;
+pub mod local;
 pub mod steam;
 
 pub use epic::EpicStore;
 pub use gog::GogStore;
+pub use local::LocalStore;
 pub use steam::SteamStore;
+
+use crate::launcher_core::{GameLibrary, LauncherError};
+
+/// Register every supported store with a library
+pub fn register_default_stores(library: &mut GameLibrary) -> Result<(), LauncherError> {
+    library.register_store(Box::new(SteamStore::new()));
+    library.register_store(Box::new(EpicStore::new()));
+    library.register_store(Box::new(GogStore::new()));
+    library.register_store(Box::new(LocalStore::new()?));
+    Ok(())
+}
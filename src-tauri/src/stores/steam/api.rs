@@ -1,4 +1,4 @@
-use crate::launcher_core::{Game, LauncherError, StoreType};
+use crate::launcher_core::{DetailsProvider, Game, GameDetails, LauncherError, StoreType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -16,7 +16,6 @@ struct OwnedGamesResponse {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct OwnedGamesData {
     game_count: Option<u32>,
     games: Option<Vec<OwnedGame>>,
@@ -72,18 +71,6 @@ struct ReleaseDate {
     date: Option<String>,
 }
 
-/// Game details returned to frontend
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GameDetails {
-    pub description: Option<String>,
-    pub developers: Option<Vec<String>>,
-    pub publishers: Option<Vec<String>>,
-    pub genres: Option<Vec<String>>,
-    pub platforms: Option<Vec<String>>,
-    pub release_date: Option<String>,
-}
-
 pub struct SteamApi {
     client: reqwest::blocking::Client,
 }
@@ -109,50 +96,21 @@ impl SteamApi {
             STEAM_API_BASE, credentials.api_key, credentials.steam_id
         );
 
-        let response: OwnedGamesResponse = self
+        let response = self
             .client
             .get(&url)
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?
+            .map_err(LauncherError::from_request)?;
+        let status = response.status();
+        if !status.is_success() {
+            // The body is an HTML page; the status says enough
+            return Err(LauncherError::from_status("Steam", status, ""));
+        }
+        let response: OwnedGamesResponse = response
             .json()
             .map_err(|e| LauncherError::ParseError(e.to_string()))?;
 
-        let games = response
-            .response
-            .games
-            .unwrap_or_default()
-            .into_iter()
-            .map(|g| {
-                // Use name from API, or fallback to app ID if not provided
-                let name = g.name.unwrap_or_else(|| format!("App {}", g.appid));
-                let mut game = Game::new(g.appid.to_string(), name, StoreType::Steam);
-                game.installed = false; // Will be updated when merging with installed games
-
-                if let Some(playtime) = g.playtime_forever {
-                    game.set_playtime(playtime);
-                }
-
-                if let Some(last_played) = g.rtime_last_played {
-                    game.last_played = Some(last_played);
-                }
-
-                // Set artwork URLs from Steam CDN
-                let app_id = g.appid;
-                game.set_cover_url(format!(
-                    "https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/library_600x900.jpg"
-                ));
-                game.set_hero_url(format!(
-                    "https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/library_hero.jpg"
-                ));
-                game.set_icon_url(format!(
-                    "https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/header.jpg"
-                ));
-
-                game
-            })
-            .collect();
-
-        Ok(games)
+        owned_games_from(response.response)
     }
 
     /// Validate Steam credentials by making a test API call
@@ -169,7 +127,7 @@ impl SteamApi {
             .client
             .get(&url)
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         Ok(response.status().is_success())
     }
@@ -182,7 +140,7 @@ impl SteamApi {
             .client
             .get(&url)
             .send()
-            .map_err(|e| LauncherError::NetworkError(e.to_string()))?;
+            .map_err(LauncherError::from_request)?;
 
         let text = response
             .text()
@@ -247,6 +205,48 @@ impl Default for SteamApi {
     }
 }
 
+impl DetailsProvider for SteamApi {
+    fn store(&self) -> StoreType {
+        StoreType::Steam
+    }
+
+    fn fetch_details(&self, game_id: &str) -> Result<Option<GameDetails>, LauncherError> {
+        self.get_game_details(game_id)
+    }
+}
+
+/// Games from a GetOwnedGames response. Steam answers a private profile (or
+/// one whose game details are private) with an empty response, not an error.
+fn owned_games_from(data: OwnedGamesData) -> Result<Vec<Game>, LauncherError> {
+    if data.game_count.is_none() && data.games.is_none() {
+        return Err(LauncherError::PrivateProfile("Steam".to_string()));
+    }
+
+    Ok(data
+        .games
+        .unwrap_or_default()
+        .into_iter()
+        .map(|g| {
+            // Use name from API, or fallback to app ID if not provided
+            let name = g.name.unwrap_or_else(|| format!("App {}", g.appid));
+            let mut game = Game::new(g.appid.to_string(), name, StoreType::Steam);
+            game.installed = false; // Will be updated when merging with installed games
+
+            if let Some(playtime) = g.playtime_forever {
+                game.set_playtime(playtime);
+            }
+
+            if let Some(last_played) = g.rtime_last_played {
+                game.last_played = Some(last_played);
+            }
+
+            super::set_default_artwork(&mut game);
+
+            game
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,7 +255,6 @@ mod tests {
     fn test_steam_api_new() {
         let api = SteamApi::new();
         // Just verify it creates without panicking
-        assert!(true);
         let _ = api;
     }
 
@@ -283,4 +282,28 @@ mod tests {
         assert_eq!(creds.api_key, "mykey");
         assert_eq!(creds.steam_id, "67890");
     }
+
+    #[test]
+    fn test_owned_games_response() {
+        let response: OwnedGamesResponse = serde_json::from_str(
+            r#"{"response":{"game_count":1,"games":[{"appid":440,"name":"Team Fortress 2","playtime_forever":90}]}}"#,
+        )
+        .unwrap();
+        let games = owned_games_from(response.response).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Team Fortress 2");
+
+        let empty: OwnedGamesResponse =
+            serde_json::from_str(r#"{"response":{"game_count":0}}"#).unwrap();
+        assert!(owned_games_from(empty.response).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_private_profile() {
+        let response: OwnedGamesResponse = serde_json::from_str(r#"{"response":{}}"#).unwrap();
+        assert!(matches!(
+            owned_games_from(response.response),
+            Err(LauncherError::PrivateProfile(_))
+        ));
+    }
 }
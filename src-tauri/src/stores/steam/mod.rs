@@ -7,10 +7,41 @@ use crate::launcher_core::{Game, GameStore, LauncherError};
 use std::path::PathBuf;
 use std::process::Command;
 
-pub use api::{GameDetails, SteamApi, SteamCredentials};
-pub use parser::{parse_acf_file, parse_library_folders};
+pub use api::{SteamApi, SteamCredentials};
+pub use parser::{listed_library_folders, parse_acf_file, parse_library_folders};
 pub use paths::SteamPaths;
 
+const STEAM_CDN: &str = "https://steamcdn-a.akamaihd.net/steam/apps";
+
+/// CDN images to try for an app, best first. Older titles predate the
+/// library assets (`library_600x900.jpg`, `library_hero.jpg`) but nearly
+/// every app has a `header.jpg`.
+pub fn artwork_candidates(app_id: &str, art_type: ArtworkType) -> Vec<String> {
+    let files: &[&str] = match art_type {
+        ArtworkType::Cover => &["library_600x900.jpg", "header.jpg"],
+        ArtworkType::Hero => &["library_hero.jpg", "header.jpg"],
+        ArtworkType::Logo => &["logo.png"],
+        ArtworkType::Icon => &["header.jpg", "capsule_184x69.jpg"],
+    };
+    files
+        .iter()
+        .map(|file| format!("{STEAM_CDN}/{app_id}/{file}"))
+        .collect()
+}
+
+/// Fill in the preferred CDN artwork for a Steam game
+pub(crate) fn set_default_artwork(game: &mut Game) {
+    let first = |art| artwork_candidates(&game.id, art).into_iter().next();
+    let (cover, hero, icon) = (
+        first(ArtworkType::Cover),
+        first(ArtworkType::Hero),
+        first(ArtworkType::Icon),
+    );
+    game.cover_url = cover;
+    game.hero_url = hero;
+    game.icon_url = icon;
+}
+
 /// Steam store integration
 pub struct SteamStore {
     paths: SteamPaths,
@@ -73,20 +104,10 @@ impl SteamStore {
 
     /// Get Steam CDN URL for game artwork
     fn get_steam_cdn_url(app_id: &str, art_type: ArtworkType) -> String {
-        match art_type {
-            ArtworkType::Cover => {
-                format!("https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/library_600x900.jpg")
-            }
-            ArtworkType::Hero => {
-                format!("https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/library_hero.jpg")
-            }
-            ArtworkType::Logo => {
-                format!("https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/logo.png")
-            }
-            ArtworkType::Icon => {
-                format!("https://steamcdn-a.akamaihd.net/steam/apps/{app_id}/header.jpg")
-            }
-        }
+        artwork_candidates(app_id, art_type)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
     }
 
     /// Trigger Steam to install a game
@@ -189,6 +210,17 @@ impl GameStore for SteamStore {
     fn get_artwork_url(&self, game_id: &str, art_type: ArtworkType) -> Option<String> {
         Some(Self::get_steam_cdn_url(game_id, art_type))
     }
+
+    fn detected_paths(&self) -> Vec<(&'static str, Option<PathBuf>)> {
+        vec![
+            ("steam_path", self.paths.steam_path.clone()),
+            ("steam_exe", self.paths.steam_exe.clone()),
+        ]
+    }
+
+    fn client_process_names(&self) -> &'static [&'static str] {
+        &["steam", "steam.exe", "steam_osx"]
+    }
 }
 
 #[cfg(test)]
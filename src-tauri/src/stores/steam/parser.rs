@@ -39,25 +39,31 @@ pub fn parse_acf_file(path: &Path) -> Result<Game, LauncherError> {
         }
     }
 
-    // Set artwork URLs
-    game.cover_url = Some(format!(
-        "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_600x900.jpg",
-        game.id
-    ));
-    game.hero_url = Some(format!(
-        "https://steamcdn-a.akamaihd.net/steam/apps/{}/library_hero.jpg",
-        game.id
-    ));
-    game.icon_url = Some(format!(
-        "https://steamcdn-a.akamaihd.net/steam/apps/{}/header.jpg",
-        game.id
-    ));
+    super::set_default_artwork(&mut game);
 
     Ok(game)
 }
 
 /// Parse Steam library folders VDF file
 pub fn parse_library_folders(path: &Path) -> Result<Vec<PathBuf>, LauncherError> {
+    let mut folders: Vec<PathBuf> = listed_library_folders(path)?
+        .into_iter()
+        .filter(|folder| folder.exists())
+        .collect();
+
+    // If no folders found, try the parent directory of the VDF file
+    if folders.is_empty() {
+        if let Some(parent) = path.parent().and_then(|p| p.parent()) {
+            folders.push(parent.to_path_buf());
+        }
+    }
+
+    Ok(folders)
+}
+
+/// Every library path listed in a library folders VDF file, whether or not
+/// it exists (a removed drive still shows up here)
+pub fn listed_library_folders(path: &Path) -> Result<Vec<PathBuf>, LauncherError> {
     let content = fs::read_to_string(path).map_err(|e| LauncherError::IoError(e.to_string()))?;
 
     let mut folders = Vec::new();
@@ -87,23 +93,13 @@ pub fn parse_library_folders(path: &Path) -> Result<Vec<PathBuf>, LauncherError>
         // Look for "path" key
         if line.starts_with("\"path\"") {
             if let Some(path_value) = extract_quoted_value(line, "path") {
-                let folder = PathBuf::from(path_value.replace("\\\\", "\\"));
-                if folder.exists() {
-                    folders.push(folder);
-                }
+                folders.push(PathBuf::from(path_value.replace("\\\\", "\\")));
             }
         }
 
         i += 1;
     }
 
-    // If no folders found, try the parent directory of the VDF file
-    if folders.is_empty() {
-        if let Some(parent) = path.parent().and_then(|p| p.parent()) {
-            folders.push(parent.to_path_buf());
-        }
-    }
-
     Ok(folders)
 }
 
@@ -323,6 +319,30 @@ mod tests {
         assert!(!folders.is_empty());
     }
 
+    #[test]
+    fn test_listed_library_folders_keeps_missing_paths() {
+        let temp = TempDir::new().unwrap();
+        let content = format!(
+            r#"
+"libraryfolders"
+{{
+    "0" {{ }}
+        "path"      "{}"
+    "1" {{ }}
+        "path"      "/unplugged/SteamLibrary"
+}}
+"#,
+            temp.path().display().to_string().replace('\\', "\\\\")
+        );
+        let path = temp.path().join("libraryfolders.vdf");
+        fs::write(&path, content).unwrap();
+
+        let listed = listed_library_folders(&path).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1], PathBuf::from("/unplugged/SteamLibrary"));
+        assert_eq!(parse_library_folders(&path).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_parse_library_folders_not_found() {
         let path = PathBuf::from("/nonexistent/libraryfolders.vdf");
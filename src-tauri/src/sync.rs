@@ -0,0 +1,205 @@
+//! Online library syncs (Steam, Epic): merging owned games with what's
+//! installed, and caching the result. Shared by the app's commands and the
+//! `tenfoot-cli` binary.
+
+use crate::launcher_core::{
+    CredentialStatus, Game, GameDetails, LauncherError, StoreStatus, StoreType,
+};
+use crate::storage::{EpicGameMetadata, GamesCache, Storage, StoredCredentials};
+use std::collections::HashMap;
+
+/// The store a store id names, if it has an online library to sync
+pub fn synced_store(store_id: &str) -> Option<StoreType> {
+    match store_id {
+        "steam" => Some(StoreType::Steam),
+        "epic" => Some(StoreType::Epic),
+        _ => None,
+    }
+}
+
+/// Mark freshly fetched owned games as installed where the local scan found
+/// them, taking the install details from the scan
+pub fn merge_owned_games(
+    owned: Vec<Game>,
+    installed_games: &[Game],
+    store: StoreType,
+) -> Vec<Game> {
+    let installed_map: HashMap<&str, &Game> = installed_games
+        .iter()
+        .filter(|g| g.store == store)
+        .map(|g| (g.id.as_str(), g))
+        .collect();
+
+    owned
+        .into_iter()
+        .map(|mut game| {
+            if let Some(installed) = installed_map.get(game.id.as_str()) {
+                game.installed = true;
+                game.install_path = installed.install_path.clone();
+                game.executable = installed.executable.clone();
+                game.size_bytes = installed.size_bytes;
+                // Use name from installed game if the Steam API didn't provide one
+                if store == StoreType::Steam && game.name.starts_with("App ") {
+                    game.name = installed.name.clone();
+                }
+            }
+            game
+        })
+        .collect()
+}
+
+/// Mark cached owned games of one store as installed based on the local scan.
+/// Falls back to the installed games when nothing has been synced yet.
+pub fn merge_cached_games(
+    owned: Vec<Game>,
+    installed_games: &[Game],
+    store: StoreType,
+) -> Vec<Game> {
+    if owned.is_empty() {
+        // No cache, return installed games only
+        return installed_games
+            .iter()
+            .filter(|g| g.store == store)
+            .cloned()
+            .collect();
+    }
+
+    // Create a map of installed games for this store
+    let installed_map: HashMap<&str, &Game> = installed_games
+        .iter()
+        .filter(|g| g.store == store)
+        .map(|g| (g.id.as_str(), g))
+        .collect();
+
+    owned
+        .into_iter()
+        .map(|mut game| {
+            if let Some(installed) = installed_map.get(game.id.as_str()) {
+                game.installed = true;
+                game.install_path = installed.install_path.clone();
+                game.size_bytes = installed.size_bytes;
+                // Use name from installed game if API didn't provide one
+                if game.name.starts_with("App ") {
+                    game.name = installed.name.clone();
+                }
+            } else {
+                game.installed = false;
+            }
+            game
+        })
+        .collect()
+}
+
+/// Merge owned games from the sync cache with locally detected ones
+pub fn combine_games(cache: GamesCache, installed_games: Vec<Game>) -> Vec<Game> {
+    let mut games = merge_cached_games(cache.steam_owned, &installed_games, StoreType::Steam);
+    games.extend(merge_cached_games(
+        cache.epic_owned,
+        &installed_games,
+        StoreType::Epic,
+    ));
+    games.extend(
+        installed_games
+            .into_iter()
+            .filter(|g| !matches!(g.store, StoreType::Steam | StoreType::Epic)),
+    );
+    games
+}
+
+/// Cache the game metadata an Epic library sync returned, giving it back as
+/// entries for the details cache
+pub fn save_epic_metadata(
+    storage: &Storage,
+    metadata: HashMap<String, GameDetails>,
+) -> Result<Vec<(String, Option<GameDetails>)>, LauncherError> {
+    let details = metadata
+        .iter()
+        .map(|(id, d)| (format!("epic:{id}"), Some(d.clone())))
+        .collect();
+    let storage_metadata: HashMap<String, EpicGameMetadata> = metadata
+        .into_iter()
+        .map(|(id, details)| (id, details.into()))
+        .collect();
+    storage.save_epic_metadata(&storage_metadata)?;
+    Ok(details)
+}
+
+/// Fill in the online side of store statuses: whether the credentials for
+/// syncing are stored (`None` while the vault is locked) and when the last
+/// sync was
+pub fn apply_account_status(
+    statuses: &mut [StoreStatus],
+    credentials: Option<&StoredCredentials>,
+    storage: &Storage,
+) -> Result<(), LauncherError> {
+    for status in statuses {
+        let Some(store) = synced_store(&status.store_id) else {
+            continue;
+        };
+        status.credentials = match credentials {
+            None => CredentialStatus::Locked,
+            Some(creds) if store == StoreType::Steam && creds.steam.is_some() => {
+                CredentialStatus::Present
+            }
+            Some(creds) if store == StoreType::Epic && creds.epic.is_some() => {
+                CredentialStatus::Present
+            }
+            Some(_) => CredentialStatus::Missing,
+        };
+        status.last_sync_at = storage.last_sync(store)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn game(id: &str, name: &str, store: StoreType, installed: bool) -> Game {
+        Game {
+            installed,
+            install_path: installed.then(|| PathBuf::from(format!("/games/{id}"))),
+            ..Game::new(id, name, store)
+        }
+    }
+
+    #[test]
+    fn test_merge_owned_games() {
+        let owned = vec![
+            game("440", "App 440", StoreType::Steam, false),
+            game("570", "Dota 2", StoreType::Steam, false),
+        ];
+        let installed = [
+            game("440", "Team Fortress 2", StoreType::Steam, true),
+            game("570", "Dota 2", StoreType::Epic, true),
+        ];
+
+        let merged = merge_owned_games(owned, &installed, StoreType::Steam);
+
+        assert_eq!(merged[0].name, "Team Fortress 2");
+        assert!(merged[0].installed);
+        assert_eq!(merged[0].install_path, Some(PathBuf::from("/games/440")));
+        // Same id on another store doesn't count
+        assert!(!merged[1].installed);
+    }
+
+    #[test]
+    fn test_combine_games() {
+        let cache = GamesCache {
+            steam_owned: vec![game("440", "Team Fortress 2", StoreType::Steam, true)],
+            ..GamesCache::default()
+        };
+        let installed = vec![
+            game("Fortnite", "Fortnite", StoreType::Epic, true),
+            game("1207658924", "Unreal Tournament", StoreType::Gog, true),
+        ];
+
+        let games = combine_games(cache, installed);
+        let keys: Vec<String> = games.iter().map(|g| g.unique_key()).collect();
+
+        assert_eq!(keys, ["steam:440", "epic:Fortnite", "gog:1207658924"]);
+        // Owned but not found by the scan
+        assert!(!games[0].installed);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::launcher_core::LauncherError;
+use crate::storage::files;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Encrypted credentials, used when there is no OS secret store
+pub const VAULT_FILE: &str = "credentials.vault";
+
+/// Random half of the machine secret, generated on first use
+pub const MACHINE_KEY_FILE: &str = "vault.key";
+
+const FORMAT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+/// PBKDF2-HMAC-SHA256 rounds for new vaults (cut down in tests, where
+/// every derivation runs unoptimized)
+const KDF_ITERATIONS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// What the vault key is derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Protection {
+    /// A secret tied to this machine; opens without asking
+    Machine,
+    /// A passphrase the user enters to unlock
+    Passphrase,
+}
+
+/// The file's contents: everything needed to re-derive the key, plus the
+/// AES-256-GCM ciphertext
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    protection: Protection,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A derived key, with the parameters it was derived with
+pub struct VaultKey {
+    key: [u8; 32],
+    salt: Vec<u8>,
+    iterations: u32,
+    protection: Protection,
+}
+
+impl VaultKey {
+    /// A key for a new vault, with a fresh salt
+    pub fn generate(secret: &[u8], protection: Protection) -> Self {
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(secret, salt, KDF_ITERATIONS, protection)
+    }
+
+    fn derive(secret: &[u8], salt: Vec<u8>, iterations: u32, protection: Protection) -> Self {
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(secret, &salt, iterations, &mut key);
+        Self {
+            key,
+            salt,
+            iterations,
+            protection,
+        }
+    }
+}
+
+/// The vault file in a data directory
+pub struct EncryptedFile {
+    path: PathBuf,
+}
+
+impl EncryptedFile {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(VAULT_FILE),
+        }
+    }
+
+    /// How the vault is protected, or None if it hasn't been created
+    pub fn protection(&self) -> Result<Option<Protection>, LauncherError> {
+        Ok(self.read()?.map(|envelope| envelope.protection))
+    }
+
+    /// Decrypt the vault with a secret. Returns the key (to seal later
+    /// changes with) and the contents, or None if it hasn't been created.
+    pub fn open(&self, secret: &[u8]) -> Result<Option<(VaultKey, Vec<u8>)>, LauncherError> {
+        let Some(envelope) = self.read()? else {
+            return Ok(None);
+        };
+
+        let salt = decode(&envelope.salt)?;
+        let nonce = decode(&envelope.nonce)?;
+        let ciphertext = decode(&envelope.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(invalid("bad nonce"));
+        }
+
+        let key = VaultKey::derive(secret, salt, envelope.iterations, envelope.protection);
+        let plaintext = cipher(&key)
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| match envelope.protection {
+                Protection::Passphrase => {
+                    LauncherError::ConfigError("Incorrect vault passphrase".to_string())
+                }
+                Protection::Machine => LauncherError::ConfigError(
+                    "The credential vault can't be opened on this machine".to_string(),
+                ),
+            })?;
+        Ok(Some((key, plaintext)))
+    }
+
+    /// Encrypt contents under a key, replacing the file
+    pub fn seal(&self, key: &VaultKey, plaintext: &[u8]) -> Result<(), LauncherError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher(key)
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| LauncherError::ConfigError("Failed to encrypt the vault".to_string()))?;
+
+        let envelope = Envelope {
+            version: FORMAT_VERSION,
+            protection: key.protection,
+            iterations: key.iterations,
+            salt: BASE64.encode(&key.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let content = serde_json::to_vec_pretty(&envelope)
+            .map_err(|e| LauncherError::ParseError(format!("Failed to serialize vault: {e}")))?;
+        files::write_atomic(&self.path, &content)
+            .map_err(|e| LauncherError::ConfigError(format!("Failed to write vault: {e}")))?;
+        restrict_permissions(&self.path);
+        Ok(())
+    }
+
+    pub fn remove(&self) -> Result<(), LauncherError> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Move an unreadable vault out of the way so a new one can be made
+    pub fn set_aside(&self) -> Result<PathBuf, LauncherError> {
+        Ok(files::set_aside(&self.path)?)
+    }
+
+    fn read(&self) -> Result<Option<Envelope>, LauncherError> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read(&self.path)?;
+        let envelope: Envelope =
+            serde_json::from_slice(&content).map_err(|e| invalid(&e.to_string()))?;
+        if envelope.version > FORMAT_VERSION {
+            return Err(LauncherError::ConfigError(format!(
+                "Credential vault format {} is newer than this version of TenFoot supports",
+                envelope.version
+            )));
+        }
+        Ok(Some(envelope))
+    }
+}
+
+/// Secret for machine-protected vaults: the OS machine id where there is one,
+/// plus random bytes kept (owner-only) in the data dir. A copy of the data
+/// dir alone doesn't open the vault on another machine.
+pub fn machine_secret(data_dir: &Path) -> Result<Vec<u8>, LauncherError> {
+    let mut secret = machine_id().unwrap_or_default().into_bytes();
+
+    let path = data_dir.join(MACHINE_KEY_FILE);
+    let random = match fs::read(&path) {
+        Ok(bytes) if !bytes.is_empty() => bytes,
+        _ => {
+            let mut bytes = vec![0u8; 32];
+            OsRng.fill_bytes(&mut bytes);
+            files::write_atomic(&path, &bytes).map_err(|e| {
+                LauncherError::ConfigError(format!("Failed to write vault key: {e}"))
+            })?;
+            restrict_permissions(&path);
+            bytes
+        }
+    };
+    secret.extend_from_slice(&random);
+    Ok(secret)
+}
+
+#[cfg(target_os = "linux")]
+fn machine_id() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn machine_id() -> Option<String> {
+    None
+}
+
+fn cipher(key: &VaultKey) -> Aes256Gcm {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key.key))
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, LauncherError> {
+    BASE64.decode(value).map_err(|e| invalid(&e.to_string()))
+}
+
+fn invalid(reason: &str) -> LauncherError {
+    LauncherError::ParseError(format!("Invalid credential vault: {reason}"))
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Err(e) = fs::set_permissions(path, fs::Permissions::from_mode(0o600)) {
+        log::warn!("Failed to restrict {}: {e}", path.display());
+    }
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_seal_and_open() {
+        let temp = TempDir::new().unwrap();
+        let file = EncryptedFile::new(temp.path());
+        assert!(file.protection().unwrap().is_none());
+        assert!(file.open(b"secret").unwrap().is_none());
+
+        let key = VaultKey::generate(b"secret", Protection::Passphrase);
+        file.seal(&key, b"{\"steam\":null}").unwrap();
+
+        assert_eq!(file.protection().unwrap(), Some(Protection::Passphrase));
+        let (_, plaintext) = file.open(b"secret").unwrap().unwrap();
+        assert_eq!(plaintext, b"{\"steam\":null}");
+        let on_disk = fs::read_to_string(temp.path().join(VAULT_FILE)).unwrap();
+        assert!(!on_disk.contains("steam"));
+    }
+
+    #[test]
+    fn test_wrong_secret_is_rejected() {
+        let temp = TempDir::new().unwrap();
+        let file = EncryptedFile::new(temp.path());
+        file.seal(
+            &VaultKey::generate(b"secret", Protection::Passphrase),
+            b"data",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            file.open(b"guess"),
+            Err(LauncherError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_each_seal_uses_a_new_nonce() {
+        let temp = TempDir::new().unwrap();
+        let file = EncryptedFile::new(temp.path());
+        let key = VaultKey::generate(b"secret", Protection::Machine);
+
+        file.seal(&key, b"data").unwrap();
+        let first = file.read().unwrap().unwrap();
+        file.seal(&key, b"data").unwrap();
+        let second = file.read().unwrap().unwrap();
+
+        assert_eq!(first.salt, second.salt);
+        assert_ne!(first.nonce, second.nonce);
+        assert_ne!(first.ciphertext, second.ciphertext);
+    }
+
+    #[test]
+    fn test_machine_secret_is_stable() {
+        let temp = TempDir::new().unwrap();
+        let first = machine_secret(temp.path()).unwrap();
+        assert_eq!(machine_secret(temp.path()).unwrap(), first);
+
+        let other = TempDir::new().unwrap();
+        assert_ne!(machine_secret(other.path()).unwrap(), first);
+    }
+}
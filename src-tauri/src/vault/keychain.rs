@@ -0,0 +1,48 @@
+use crate::launcher_core::LauncherError;
+
+/// Service and account the credentials are filed under in the OS store
+const SERVICE: &str = "tenfoot";
+const ACCOUNT: &str = "credentials";
+
+/// Whether the OS secret store can be used: it answers, and is a real store
+/// (keyring falls back to an in-memory mock on platforms it doesn't support)
+pub fn available() -> bool {
+    if !cfg!(any(target_os = "linux", target_os = "macos", windows)) {
+        return false;
+    }
+    match read() {
+        Ok(_) => true,
+        Err(e) => {
+            log::info!("OS secret store unavailable, using the encrypted vault file: {e}");
+            false
+        }
+    }
+}
+
+/// The stored secret, or None if nothing has been stored yet
+pub fn read() -> Result<Option<String>, LauncherError> {
+    match entry()?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(error(e)),
+    }
+}
+
+pub fn write(secret: &str) -> Result<(), LauncherError> {
+    entry()?.set_password(secret).map_err(error)
+}
+
+pub fn delete() -> Result<(), LauncherError> {
+    match entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(error(e)),
+    }
+}
+
+fn entry() -> Result<keyring::Entry, LauncherError> {
+    keyring::Entry::new(SERVICE, ACCOUNT).map_err(error)
+}
+
+fn error(e: keyring::Error) -> LauncherError {
+    LauncherError::ConfigError(format!("OS secret store: {e}"))
+}
@@ -0,0 +1,466 @@
+mod encrypted;
+mod keychain;
+
+pub use encrypted::{Protection, MACHINE_KEY_FILE, VAULT_FILE};
+
+use crate::launcher_core::redact::register_secret;
+use crate::launcher_core::LauncherError;
+use crate::storage::{Storage, StoredCredentials};
+use encrypted::{EncryptedFile, VaultKey};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Where the vault keeps its contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultBackend {
+    /// The OS secret store (Secret Service, Keychain, Credential Manager)
+    Keyring,
+    /// An encrypted file in the data dir
+    EncryptedFile,
+}
+
+/// What the settings screen shows about the vault
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultStatus {
+    pub backend: VaultBackend,
+    pub locked: bool,
+    /// Unlocking needs the user's passphrase
+    pub passphrase_protected: bool,
+}
+
+/// Store credentials (API keys, refresh tokens) kept out of plaintext: in the
+/// OS secret store where there is one, otherwise in an encrypted file whose
+/// key comes from a machine secret or a passphrase.
+///
+/// While unlocked the credentials are held in memory; locking forgets them
+/// until `unlock` is called again.
+pub struct CredentialVault {
+    data_dir: PathBuf,
+    file: EncryptedFile,
+    keyring_available: bool,
+    state: Mutex<State>,
+}
+
+struct State {
+    backend: VaultBackend,
+    unlocked: Option<Unlocked>,
+}
+
+struct Unlocked {
+    credentials: StoredCredentials,
+    /// Key the file is sealed with (file backend only)
+    key: Option<VaultKey>,
+}
+
+impl CredentialVault {
+    /// Open the vault in a data dir, unlocking it unless it needs a passphrase
+    pub fn open(data_dir: &Path) -> Self {
+        Self::open_with(data_dir, keychain::available())
+    }
+
+    fn open_with(data_dir: &Path, keyring_available: bool) -> Self {
+        let file = EncryptedFile::new(data_dir);
+        let protection = match file.protection() {
+            Ok(protection) => protection,
+            Err(e) => {
+                log::error!("Credential vault is unreadable ({e}), starting a new one");
+                if let Err(e) = file.set_aside() {
+                    log::error!("Failed to move the unreadable vault: {e}");
+                }
+                None
+            }
+        };
+        // An existing file keeps being used, so a secret store that appears
+        // later doesn't hide what's in it
+        let backend = if protection.is_none() && keyring_available {
+            VaultBackend::Keyring
+        } else {
+            VaultBackend::EncryptedFile
+        };
+
+        let vault = Self {
+            data_dir: data_dir.to_path_buf(),
+            file,
+            keyring_available,
+            state: Mutex::new(State {
+                backend,
+                unlocked: None,
+            }),
+        };
+        if protection != Some(Protection::Passphrase) {
+            if let Err(e) = vault.unlock(None) {
+                log::error!("Failed to open the credential vault: {e}");
+            }
+        }
+        vault
+    }
+
+    pub fn status(&self) -> VaultStatus {
+        let state = self.state();
+        let passphrase_protected = state.backend == VaultBackend::EncryptedFile
+            && self.file.protection().ok().flatten() == Some(Protection::Passphrase);
+        VaultStatus {
+            backend: state.backend,
+            locked: state.unlocked.is_none(),
+            passphrase_protected,
+        }
+    }
+
+    /// The stored credentials; fails while the vault is locked
+    pub fn load(&self) -> Result<StoredCredentials, LauncherError> {
+        self.state()
+            .unlocked
+            .as_ref()
+            .map(|unlocked| unlocked.credentials.clone())
+            .ok_or_else(locked)
+    }
+
+    pub fn save(&self, credentials: &StoredCredentials) -> Result<(), LauncherError> {
+        self.update(|stored| *stored = credentials.clone())
+    }
+
+    /// Change the stored credentials in place
+    pub fn update(&self, f: impl FnOnce(&mut StoredCredentials)) -> Result<(), LauncherError> {
+        let mut state = self.state();
+        let backend = state.backend;
+        let unlocked = state.unlocked.as_mut().ok_or_else(locked)?;
+
+        let mut credentials = unlocked.credentials.clone();
+        f(&mut credentials);
+        register_secrets(&credentials);
+        let json = to_json(&credentials)?;
+        match (backend, &unlocked.key) {
+            (VaultBackend::Keyring, _) => keychain::write(&json)?,
+            (VaultBackend::EncryptedFile, Some(key)) => self.file.seal(key, json.as_bytes())?,
+            (VaultBackend::EncryptedFile, None) => return Err(locked()),
+        }
+        unlocked.credentials = credentials;
+        Ok(())
+    }
+
+    /// Forget the credentials until the vault is unlocked again
+    pub fn lock(&self) {
+        self.state().unlocked = None;
+    }
+
+    /// Read the credentials back in. A passphrase is only needed (and only
+    /// checked) when the vault is protected by one.
+    pub fn unlock(&self, passphrase: Option<&str>) -> Result<(), LauncherError> {
+        let mut state = self.state();
+        if state.unlocked.is_some() {
+            return Ok(());
+        }
+
+        let unlocked = match state.backend {
+            VaultBackend::Keyring => Unlocked {
+                credentials: match keychain::read()? {
+                    Some(json) => from_json(json.as_bytes())?,
+                    None => StoredCredentials::default(),
+                },
+                key: None,
+            },
+            VaultBackend::EncryptedFile => {
+                let secret = match self.file.protection()? {
+                    Some(Protection::Passphrase) => passphrase
+                        .ok_or_else(|| LauncherError::AuthRequired("the credential vault".into()))?
+                        .as_bytes()
+                        .to_vec(),
+                    _ => encrypted::machine_secret(&self.data_dir)?,
+                };
+                match self.file.open(&secret)? {
+                    Some((key, plaintext)) => Unlocked {
+                        credentials: from_json(&plaintext)?,
+                        key: Some(key),
+                    },
+                    None => Unlocked {
+                        credentials: StoredCredentials::default(),
+                        key: Some(VaultKey::generate(&secret, Protection::Machine)),
+                    },
+                }
+            }
+        };
+        register_secrets(&unlocked.credentials);
+        state.unlocked = Some(unlocked);
+        Ok(())
+    }
+
+    /// Protect the vault with a passphrase, or with None go back to not
+    /// asking for one (the OS secret store if there is one). Moves the
+    /// credentials to wherever they now belong; the vault must be unlocked.
+    pub fn set_passphrase(&self, passphrase: Option<&str>) -> Result<(), LauncherError> {
+        if passphrase.is_some_and(|p| p.trim().is_empty()) {
+            return Err(LauncherError::ConfigError(
+                "The vault passphrase can't be empty".to_string(),
+            ));
+        }
+
+        let mut state = self.state();
+        let previous = state.backend;
+        let unlocked = state.unlocked.as_mut().ok_or_else(locked)?;
+        let json = to_json(&unlocked.credentials)?;
+
+        let backend = match passphrase {
+            None if self.keyring_available => {
+                keychain::write(&json)?;
+                self.file.remove()?;
+                unlocked.key = None;
+                VaultBackend::Keyring
+            }
+            _ => {
+                let key = match passphrase {
+                    Some(passphrase) => {
+                        VaultKey::generate(passphrase.as_bytes(), Protection::Passphrase)
+                    }
+                    None => VaultKey::generate(
+                        &encrypted::machine_secret(&self.data_dir)?,
+                        Protection::Machine,
+                    ),
+                };
+                self.file.seal(&key, json.as_bytes())?;
+                if previous == VaultBackend::Keyring {
+                    if let Err(e) = keychain::delete() {
+                        log::warn!("Failed to remove credentials from the OS secret store: {e}");
+                    }
+                }
+                unlocked.key = Some(key);
+                VaultBackend::EncryptedFile
+            }
+        };
+        state.backend = backend;
+        Ok(())
+    }
+
+    /// Throw away everything in the vault (e.g. a forgotten passphrase) and
+    /// start again empty and unlocked
+    pub fn reset(&self) -> Result<(), LauncherError> {
+        let mut state = self.state();
+        self.file.remove()?;
+        if self.keyring_available {
+            keychain::delete()?;
+        }
+        state.backend = if self.keyring_available {
+            VaultBackend::Keyring
+        } else {
+            VaultBackend::EncryptedFile
+        };
+        state.unlocked = None;
+        drop(state);
+        self.unlock(None)
+    }
+
+    /// One-time move of credentials that earlier versions kept in plaintext.
+    /// Anything already in the vault wins. Returns whether there was
+    /// anything to move.
+    pub fn import_plaintext(&self, storage: &Storage) -> Result<bool, LauncherError> {
+        let Some(legacy) = storage.load_plaintext_credentials()? else {
+            return Ok(false);
+        };
+
+        self.update(|creds| {
+            creds.steam = creds.steam.take().or(legacy.steam);
+            creds.epic = creds.epic.take().or(legacy.epic);
+            creds.steamgriddb_api_key = creds
+                .steamgriddb_api_key
+                .take()
+                .or(legacy.steamgriddb_api_key);
+        })?;
+        storage.remove_plaintext_credentials()?;
+        log::info!("Moved stored credentials into the credential vault");
+        Ok(true)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Have the values scrubbed from error messages and logs
+fn register_secrets(credentials: &StoredCredentials) {
+    if let Some(steam) = &credentials.steam {
+        register_secret(&steam.api_key);
+    }
+    if let Some(epic) = &credentials.epic {
+        register_secret(&epic.access_token);
+        register_secret(&epic.refresh_token);
+    }
+    if let Some(key) = &credentials.steamgriddb_api_key {
+        register_secret(key);
+    }
+}
+
+fn locked() -> LauncherError {
+    LauncherError::AuthRequired("the credential vault".to_string())
+}
+
+fn to_json(credentials: &StoredCredentials) -> Result<String, LauncherError> {
+    serde_json::to_string(credentials)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to serialize credentials: {e}")))
+}
+
+fn from_json(json: &[u8]) -> Result<StoredCredentials, LauncherError> {
+    serde_json::from_slice(json)
+        .map_err(|e| LauncherError::ParseError(format!("Failed to parse credentials: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stores::steam::SteamCredentials;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn steam(api_key: &str) -> Option<SteamCredentials> {
+        Some(SteamCredentials {
+            api_key: api_key.to_string(),
+            steam_id: "76561197960287930".to_string(),
+        })
+    }
+
+    fn file_vault(dir: &Path) -> CredentialVault {
+        CredentialVault::open_with(dir, false)
+    }
+
+    #[test]
+    fn test_machine_vault_opens_unlocked() {
+        let temp = TempDir::new().unwrap();
+        let vault = file_vault(temp.path());
+        assert_eq!(
+            vault.status(),
+            VaultStatus {
+                backend: VaultBackend::EncryptedFile,
+                locked: false,
+                passphrase_protected: false,
+            }
+        );
+        assert!(vault.load().unwrap().steam.is_none());
+
+        vault
+            .update(|creds| creds.steam = steam("secret-key"))
+            .unwrap();
+        let on_disk = fs::read_to_string(temp.path().join(VAULT_FILE)).unwrap();
+        assert!(!on_disk.contains("secret-key"));
+
+        let reopened = file_vault(temp.path());
+        assert_eq!(
+            reopened.load().unwrap().steam.unwrap().api_key,
+            "secret-key"
+        );
+    }
+
+    #[test]
+    fn test_lock_and_unlock() {
+        let temp = TempDir::new().unwrap();
+        let vault = file_vault(temp.path());
+        vault.update(|creds| creds.steam = steam("k")).unwrap();
+
+        vault.lock();
+        assert!(vault.status().locked);
+        assert!(matches!(vault.load(), Err(LauncherError::AuthRequired(_))));
+        assert!(vault.update(|creds| creds.steam = None).is_err());
+
+        vault.unlock(None).unwrap();
+        assert_eq!(vault.load().unwrap().steam.unwrap().api_key, "k");
+    }
+
+    #[test]
+    fn test_passphrase_protection() {
+        let temp = TempDir::new().unwrap();
+        let vault = file_vault(temp.path());
+        vault.update(|creds| creds.steam = steam("k")).unwrap();
+
+        assert!(vault.set_passphrase(Some("  ")).is_err());
+        vault.set_passphrase(Some("hunter2")).unwrap();
+        assert!(vault.status().passphrase_protected);
+
+        // Starts locked, and only the passphrase opens it
+        let reopened = file_vault(temp.path());
+        assert!(reopened.status().locked);
+        assert!(matches!(
+            reopened.unlock(None),
+            Err(LauncherError::AuthRequired(_))
+        ));
+        assert!(reopened.unlock(Some("hunter3")).is_err());
+        assert!(reopened.status().locked);
+        reopened.unlock(Some("hunter2")).unwrap();
+        assert_eq!(reopened.load().unwrap().steam.unwrap().api_key, "k");
+
+        // And back to opening without one
+        reopened.set_passphrase(None).unwrap();
+        let reopened = file_vault(temp.path());
+        assert!(!reopened.status().locked);
+        assert!(!reopened.status().passphrase_protected);
+        assert_eq!(reopened.load().unwrap().steam.unwrap().api_key, "k");
+    }
+
+    #[test]
+    fn test_reset_forgets_everything() {
+        let temp = TempDir::new().unwrap();
+        let vault = file_vault(temp.path());
+        vault.update(|creds| creds.steam = steam("k")).unwrap();
+        vault.set_passphrase(Some("forgotten")).unwrap();
+        let vault = file_vault(temp.path());
+
+        vault.reset().unwrap();
+
+        assert!(!vault.status().locked);
+        assert!(vault.load().unwrap().steam.is_none());
+    }
+
+    #[test]
+    fn test_unreadable_vault_is_set_aside() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(VAULT_FILE), "{ not a vault").unwrap();
+
+        let vault = file_vault(temp.path());
+
+        assert!(!vault.status().locked);
+        let kept = fs::read_dir(temp.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".corrupt-"))
+            .count();
+        assert_eq!(kept, 1);
+    }
+
+    #[test]
+    fn test_plaintext_credentials_are_imported_once() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("credentials.json"),
+            r#"{"steam":{"api_key":"old","steam_id":"7"},"steamgriddb_api_key":"sgdb"}"#,
+        )
+        .unwrap();
+        let storage = Storage::open(dir).unwrap();
+        let vault = file_vault(dir);
+        vault.update(|creds| creds.steam = steam("new")).unwrap();
+
+        assert!(vault.import_plaintext(&storage).unwrap());
+
+        let creds = vault.load().unwrap();
+        assert_eq!(creds.steam.unwrap().api_key, "new");
+        assert_eq!(creds.steamgriddb_api_key.as_deref(), Some("sgdb"));
+        assert!(storage.load_plaintext_credentials().unwrap().is_none());
+        assert!(!vault.import_plaintext(&storage).unwrap());
+    }
+
+    #[test]
+    fn test_import_waits_for_a_locked_vault() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join("credentials.json"),
+            r#"{"steam":{"api_key":"old","steam_id":"7"}}"#,
+        )
+        .unwrap();
+        let storage = Storage::open(dir).unwrap();
+        let vault = file_vault(dir);
+        vault.lock();
+
+        assert!(vault.import_plaintext(&storage).is_err());
+        assert!(storage.load_plaintext_credentials().unwrap().is_some());
+    }
+}
@@ -0,0 +1,638 @@
+use crate::launcher_core::{Game, LauncherError};
+use crate::stores::epic::{parse_manifest_file, EpicPaths};
+use crate::stores::gog::{query_installed_games, GogPaths};
+use crate::stores::steam::{parse_acf_file, parse_library_folders, SteamPaths};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default quiet period before a changed file is re-parsed
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// How often pending changes are checked against the debounce period
+const TICK: Duration = Duration::from_millis(100);
+
+/// A location on disk that reflects which games a store has installed
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchTarget {
+    /// A Steam `steamapps` folder containing `appmanifest_*.acf` files
+    SteamApps(PathBuf),
+    /// The Epic manifests folder containing `.item` files
+    EpicManifests(PathBuf),
+    /// The GOG Galaxy SQLite database
+    GogDatabase(PathBuf),
+}
+
+impl WatchTarget {
+    /// Directory registered with the OS watcher
+    fn watch_dir(&self) -> Option<&Path> {
+        match self {
+            WatchTarget::SteamApps(dir) | WatchTarget::EpicManifests(dir) => Some(dir),
+            WatchTarget::GogDatabase(db) => db.parent(),
+        }
+    }
+}
+
+/// Find the folders and files to watch for the detected store installations
+pub fn detect_targets() -> Vec<WatchTarget> {
+    targets_from_paths(
+        &SteamPaths::detect(),
+        &EpicPaths::detect(),
+        &GogPaths::detect(),
+    )
+}
+
+/// Build watch targets from store paths
+pub fn targets_from_paths(
+    steam: &SteamPaths,
+    epic: &EpicPaths,
+    gog: &GogPaths,
+) -> Vec<WatchTarget> {
+    let mut targets = Vec::new();
+
+    if let Some(steam_path) = &steam.steam_path {
+        let vdf_path = steam_path.join("steamapps").join("libraryfolders.vdf");
+        let libraries = if vdf_path.exists() {
+            parse_library_folders(&vdf_path).unwrap_or_else(|e| {
+                log::warn!("Failed to read Steam library folders: {e}");
+                vec![steam_path.clone()]
+            })
+        } else {
+            vec![steam_path.clone()]
+        };
+
+        for library in libraries {
+            let target = WatchTarget::SteamApps(library.join("steamapps"));
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+
+    if let Some(manifests) = &epic.manifests_path {
+        targets.push(WatchTarget::EpicManifests(manifests.clone()));
+    }
+
+    if let Some(db) = &gog.database_path {
+        targets.push(WatchTarget::GogDatabase(db.clone()));
+    }
+
+    targets
+}
+
+/// A change to the installed library detected on disk
+#[derive(Debug, Clone, PartialEq)]
+pub enum LibraryChange {
+    Added(Game),
+    Removed(Game),
+    Updated(Game),
+}
+
+impl LibraryChange {
+    /// Name of the frontend event for this change
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            LibraryChange::Added(_) => "game-added",
+            LibraryChange::Removed(_) => "game-removed",
+            LibraryChange::Updated(_) => "game-updated",
+        }
+    }
+
+    /// The game that changed (for removals, its last known state)
+    pub fn game(&self) -> &Game {
+        match self {
+            LibraryChange::Added(game)
+            | LibraryChange::Removed(game)
+            | LibraryChange::Updated(game) => game,
+        }
+    }
+}
+
+/// Last known installed games per watched file, used to diff changes
+struct LibrarySnapshot {
+    targets: Vec<WatchTarget>,
+    manifests: HashMap<PathBuf, Game>,
+    gog_games: HashMap<String, Game>,
+}
+
+impl LibrarySnapshot {
+    /// Read the current state of every target
+    fn scan(targets: Vec<WatchTarget>) -> Self {
+        let mut snapshot = Self {
+            targets,
+            manifests: HashMap::new(),
+            gog_games: HashMap::new(),
+        };
+
+        for target in snapshot.targets.clone() {
+            match &target {
+                WatchTarget::SteamApps(dir) | WatchTarget::EpicManifests(dir) => {
+                    let Ok(entries) = std::fs::read_dir(dir) else {
+                        continue;
+                    };
+                    for path in entries.flatten().map(|e| e.path()) {
+                        if Self::is_manifest(&target, &path) {
+                            snapshot.handle_manifest(&target, &path);
+                        }
+                    }
+                }
+                WatchTarget::GogDatabase(db) => {
+                    snapshot.handle_gog_database(db);
+                }
+            }
+        }
+
+        snapshot
+    }
+
+    fn is_manifest(target: &WatchTarget, path: &Path) -> bool {
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        match target {
+            WatchTarget::SteamApps(dir) => {
+                path.parent() == Some(dir.as_path())
+                    && file_name.starts_with("appmanifest_")
+                    && file_name.ends_with(".acf")
+            }
+            WatchTarget::EpicManifests(dir) => {
+                path.parent() == Some(dir.as_path()) && file_name.ends_with(".item")
+            }
+            WatchTarget::GogDatabase(_) => false,
+        }
+    }
+
+    /// Map a raw filesystem event path to the file that should be re-read.
+    /// SQLite journal and WAL writes count as changes to the GOG database.
+    fn change_key(&self, path: &Path) -> Option<PathBuf> {
+        self.targets.iter().find_map(|target| match target {
+            WatchTarget::GogDatabase(db) => {
+                let db_name = db.file_name()?.to_str()?;
+                let name = path.file_name()?.to_str()?;
+                let same_dir = path.parent() == db.parent();
+                (same_dir && name.starts_with(db_name)).then(|| db.clone())
+            }
+            _ => Self::is_manifest(target, path).then(|| path.to_path_buf()),
+        })
+    }
+
+    /// Re-read a changed file and return what changed in the library
+    fn handle_change(&mut self, path: &Path) -> Vec<LibraryChange> {
+        let Some(target) = self
+            .targets
+            .iter()
+            .find(|t| match t {
+                WatchTarget::GogDatabase(db) => db == path,
+                _ => Self::is_manifest(t, path),
+            })
+            .cloned()
+        else {
+            return Vec::new();
+        };
+
+        match &target {
+            WatchTarget::GogDatabase(db) => self.handle_gog_database(db),
+            _ => self.handle_manifest(&target, path).into_iter().collect(),
+        }
+    }
+
+    fn handle_manifest(&mut self, target: &WatchTarget, path: &Path) -> Option<LibraryChange> {
+        let parsed = if path.exists() {
+            let result = match target {
+                WatchTarget::SteamApps(_) => parse_acf_file(path),
+                _ => parse_manifest_file(path),
+            };
+            match result {
+                Ok(game) => Some(game),
+                Err(e) => {
+                    // Partially written or incomplete installs are treated as absent
+                    log::debug!("Skipping manifest {path:?}: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        match parsed {
+            Some(game) => match self.manifests.insert(path.to_path_buf(), game.clone()) {
+                None => Some(LibraryChange::Added(game)),
+                Some(previous) if previous != game => Some(LibraryChange::Updated(game)),
+                Some(_) => None,
+            },
+            None => self.manifests.remove(path).map(LibraryChange::Removed),
+        }
+    }
+
+    fn handle_gog_database(&mut self, db: &Path) -> Vec<LibraryChange> {
+        let games = if db.exists() {
+            match query_installed_games(db) {
+                Ok(games) => games,
+                Err(e) => {
+                    // Galaxy may hold a write lock; wait for the next change
+                    log::debug!("Failed to read GOG database: {e}");
+                    return Vec::new();
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        let current: HashMap<String, Game> = games.into_iter().map(|g| (g.id.clone(), g)).collect();
+        let changes = diff_games(&self.gog_games, &current);
+        self.gog_games = current;
+        changes
+    }
+}
+
+/// Compare two sets of games keyed by id
+fn diff_games(
+    previous: &HashMap<String, Game>,
+    current: &HashMap<String, Game>,
+) -> Vec<LibraryChange> {
+    let mut changes: Vec<LibraryChange> = current
+        .iter()
+        .filter_map(|(id, game)| match previous.get(id) {
+            None => Some(LibraryChange::Added(game.clone())),
+            Some(old) if old != game => Some(LibraryChange::Updated(game.clone())),
+            Some(_) => None,
+        })
+        .collect();
+
+    changes.extend(
+        previous
+            .iter()
+            .filter(|(id, _)| !current.contains_key(*id))
+            .map(|(_, game)| LibraryChange::Removed(game.clone())),
+    );
+
+    changes.sort_by(|a, b| a.game().id.cmp(&b.game().id));
+    changes
+}
+
+/// Watches store manifests and reports installs, uninstalls and updates.
+///
+/// Filesystem events are debounced per file, then only the affected
+/// manifest (or the GOG database) is re-read. Dropping the watcher stops it.
+pub struct LibraryWatcher {
+    _watcher: RecommendedWatcher,
+    watched: Vec<PathBuf>,
+}
+
+impl LibraryWatcher {
+    /// Start watching the given targets, calling `on_change` from a worker thread
+    pub fn start<F>(
+        targets: Vec<WatchTarget>,
+        debounce: Duration,
+        on_change: F,
+    ) -> Result<Self, LauncherError>
+    where
+        F: Fn(LibraryChange) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Err(e) => log::warn!("Library watcher error: {e}"),
+            })
+            .map_err(|e| LauncherError::IoError(format!("Failed to create watcher: {e}")))?;
+
+        let mut watched = Vec::new();
+        for target in &targets {
+            let Some(dir) = target.watch_dir() else {
+                continue;
+            };
+            if watched.iter().any(|w| w == dir) {
+                continue;
+            }
+            match watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => watched.push(dir.to_path_buf()),
+                Err(e) => log::warn!("Failed to watch {dir:?}: {e}"),
+            }
+        }
+
+        thread::Builder::new()
+            .name("library-watcher".to_string())
+            .spawn(move || run_watch_loop(targets, debounce, rx, on_change))
+            .map_err(|e| LauncherError::IoError(format!("Failed to start watcher: {e}")))?;
+
+        Ok(Self {
+            _watcher: watcher,
+            watched,
+        })
+    }
+
+    /// Directories currently being watched
+    pub fn watched_dirs(&self) -> &[PathBuf] {
+        &self.watched
+    }
+}
+
+/// Collect events, wait for each file to settle, then diff it against the snapshot
+fn run_watch_loop<F>(
+    targets: Vec<WatchTarget>,
+    debounce: Duration,
+    rx: mpsc::Receiver<PathBuf>,
+    on_change: F,
+) where
+    F: Fn(LibraryChange),
+{
+    let mut snapshot = LibrarySnapshot::scan(targets);
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    loop {
+        // Settled paths are checked after every event too, so a steady stream
+        // of events for other files doesn't hold them back
+        match rx.recv_timeout(TICK) {
+            Ok(path) => {
+                if let Some(key) = snapshot.change_key(&path) {
+                    pending.insert(key, Instant::now());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            // The watcher was dropped
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        let now = Instant::now();
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in settled {
+            pending.remove(&path);
+            for change in snapshot.handle_change(&path) {
+                on_change(change);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher_core::StoreType;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_acf(dir: &Path, app_id: &str, name: &str) -> PathBuf {
+        let path = dir.join(format!("appmanifest_{app_id}.acf"));
+        let content = format!(
+            "\"AppState\"\n{{\n    \"appid\"    \"{app_id}\"\n    \"name\"    \"{name}\"\n}}\n"
+        );
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn write_item(dir: &Path, app_name: &str, incomplete: bool) -> PathBuf {
+        let path = dir.join(format!("{app_name}.item"));
+        let content = format!(
+            r#"{{"AppName": "{app_name}", "DisplayName": "{app_name}", "InstallLocation": "/nonexistent", "LaunchExecutable": "game.exe", "bIsIncompleteInstall": {incomplete}}}"#
+        );
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn steam_target(temp: &TempDir) -> (PathBuf, WatchTarget) {
+        let steamapps = temp.path().join("steamapps");
+        fs::create_dir_all(&steamapps).unwrap();
+        (steamapps.clone(), WatchTarget::SteamApps(steamapps))
+    }
+
+    #[test]
+    fn test_targets_from_paths() {
+        let temp = TempDir::new().unwrap();
+        let steam = SteamPaths {
+            steam_path: Some(temp.path().to_path_buf()),
+            steam_exe: None,
+        };
+        let epic = EpicPaths {
+            launcher_path: None,
+            launcher_exe: None,
+            manifests_path: Some(PathBuf::from("/epic/Manifests")),
+        };
+        let gog = GogPaths {
+            galaxy_path: None,
+            galaxy_exe: None,
+            database_path: Some(PathBuf::from("/gog/storage/galaxy-2.0.db")),
+        };
+
+        let targets = targets_from_paths(&steam, &epic, &gog);
+
+        assert_eq!(
+            targets,
+            vec![
+                WatchTarget::SteamApps(temp.path().join("steamapps")),
+                WatchTarget::EpicManifests(PathBuf::from("/epic/Manifests")),
+                WatchTarget::GogDatabase(PathBuf::from("/gog/storage/galaxy-2.0.db")),
+            ]
+        );
+        assert_eq!(targets[2].watch_dir(), Some(Path::new("/gog/storage")));
+    }
+
+    #[test]
+    fn test_targets_from_empty_paths() {
+        let targets = targets_from_paths(
+            &SteamPaths::empty(),
+            &EpicPaths::empty(),
+            &GogPaths::empty(),
+        );
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_detects_steam_add_update_remove() {
+        let temp = TempDir::new().unwrap();
+        let (steamapps, target) = steam_target(&temp);
+        write_acf(&steamapps, "10", "Existing");
+        let mut snapshot = LibrarySnapshot::scan(vec![target]);
+
+        let path = write_acf(&steamapps, "440", "Team Fortress 2");
+        let changes = snapshot.handle_change(&path);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].event_name(), "game-added");
+        assert_eq!(changes[0].game().id, "440");
+
+        // Rewriting without changes is not reported
+        write_acf(&steamapps, "440", "Team Fortress 2");
+        assert!(snapshot.handle_change(&path).is_empty());
+
+        write_acf(&steamapps, "440", "Team Fortress 2 Classic");
+        let changes = snapshot.handle_change(&path);
+        assert!(
+            matches!(&changes[0], LibraryChange::Updated(g) if g.name == "Team Fortress 2 Classic")
+        );
+
+        fs::remove_file(&path).unwrap();
+        let changes = snapshot.handle_change(&path);
+        assert_eq!(changes[0].event_name(), "game-removed");
+        assert_eq!(changes[0].game().store, StoreType::Steam);
+    }
+
+    #[test]
+    fn test_snapshot_existing_manifest_removed() {
+        let temp = TempDir::new().unwrap();
+        let (steamapps, target) = steam_target(&temp);
+        let path = write_acf(&steamapps, "10", "Existing");
+        let mut snapshot = LibrarySnapshot::scan(vec![target]);
+
+        fs::remove_file(&path).unwrap();
+        let changes = snapshot.handle_change(&path);
+
+        assert_eq!(
+            changes,
+            vec![LibraryChange::Removed(parse_test_game("10", "Existing"))]
+        );
+    }
+
+    fn parse_test_game(id: &str, name: &str) -> Game {
+        let mut game = Game::new(id, name, StoreType::Steam);
+        game.installed = true;
+        game.cover_url = Some(format!(
+            "https://steamcdn-a.akamaihd.net/steam/apps/{id}/library_600x900.jpg"
+        ));
+        game.hero_url = Some(format!(
+            "https://steamcdn-a.akamaihd.net/steam/apps/{id}/library_hero.jpg"
+        ));
+        game.icon_url = Some(format!(
+            "https://steamcdn-a.akamaihd.net/steam/apps/{id}/header.jpg"
+        ));
+        game
+    }
+
+    #[test]
+    fn test_snapshot_epic_incomplete_install_is_not_added() {
+        let temp = TempDir::new().unwrap();
+        let manifests = temp.path().join("Manifests");
+        fs::create_dir_all(&manifests).unwrap();
+        let mut snapshot =
+            LibrarySnapshot::scan(vec![WatchTarget::EpicManifests(manifests.clone())]);
+
+        let path = write_item(&manifests, "Fortnite", true);
+        assert!(snapshot.handle_change(&path).is_empty());
+
+        write_item(&manifests, "Fortnite", false);
+        let changes = snapshot.handle_change(&path);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].event_name(), "game-added");
+        assert_eq!(changes[0].game().store, StoreType::Epic);
+    }
+
+    #[test]
+    fn test_change_key_filters_unrelated_files() {
+        let temp = TempDir::new().unwrap();
+        let (steamapps, target) = steam_target(&temp);
+        let db = temp.path().join("storage").join("galaxy-2.0.db");
+        let snapshot = LibrarySnapshot::scan(vec![target, WatchTarget::GogDatabase(db.clone())]);
+
+        assert_eq!(
+            snapshot.change_key(&steamapps.join("appmanifest_1.acf")),
+            Some(steamapps.join("appmanifest_1.acf"))
+        );
+        assert_eq!(
+            snapshot.change_key(&steamapps.join("libraryfolders.vdf")),
+            None
+        );
+        assert_eq!(
+            snapshot.change_key(&steamapps.join("downloading").join("appmanifest_1.acf")),
+            None
+        );
+        assert_eq!(
+            snapshot.change_key(&db.with_file_name("galaxy-2.0.db-wal")),
+            Some(db.clone())
+        );
+        assert_eq!(snapshot.change_key(&db.with_file_name("other.db")), None);
+    }
+
+    #[test]
+    fn test_diff_games() {
+        let mut previous = HashMap::new();
+        previous.insert("1".to_string(), Game::new("1", "Kept", StoreType::Gog));
+        previous.insert("2".to_string(), Game::new("2", "Gone", StoreType::Gog));
+        previous.insert("3".to_string(), Game::new("3", "Old Name", StoreType::Gog));
+
+        let mut current = HashMap::new();
+        current.insert("1".to_string(), Game::new("1", "Kept", StoreType::Gog));
+        current.insert("3".to_string(), Game::new("3", "New Name", StoreType::Gog));
+        current.insert("4".to_string(), Game::new("4", "New", StoreType::Gog));
+
+        let events: Vec<(&str, String)> = diff_games(&previous, &current)
+            .iter()
+            .map(|c| (c.event_name(), c.game().id.clone()))
+            .collect();
+
+        assert_eq!(
+            events,
+            vec![
+                ("game-removed", "2".to_string()),
+                ("game-updated", "3".to_string()),
+                ("game-added", "4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_watch_loop_settles_during_steady_events() {
+        let temp = TempDir::new().unwrap();
+        let (steamapps, target) = steam_target(&temp);
+        let (event_tx, event_rx) = mpsc::channel();
+        let (change_tx, change_rx) = mpsc::channel();
+
+        let debounce = Duration::from_millis(100);
+        thread::spawn(move || {
+            run_watch_loop(vec![target], debounce, event_rx, move |c| {
+                let _ = change_tx.send(c);
+            })
+        });
+        thread::sleep(Duration::from_millis(50));
+
+        let portal = write_acf(&steamapps, "620", "Portal 2");
+        event_tx.send(portal).unwrap();
+
+        // Another manifest keeps changing faster than the loop's tick
+        let busy = steamapps.join("appmanifest_999.acf");
+        let started = Instant::now();
+        let mut change = None;
+        while started.elapsed() < Duration::from_secs(2) {
+            event_tx.send(busy.clone()).unwrap();
+            if let Ok(c) = change_rx.recv_timeout(TICK / 4) {
+                change = Some(c);
+                break;
+            }
+        }
+
+        let change = change.expect("change while other events keep arriving");
+        assert_eq!(change.event_name(), "game-added");
+        assert_eq!(change.game().name, "Portal 2");
+        assert!(started.elapsed() >= debounce);
+    }
+
+    #[test]
+    fn test_watcher_reports_new_manifest() {
+        let temp = TempDir::new().unwrap();
+        let (steamapps, target) = steam_target(&temp);
+        let (tx, rx) = mpsc::channel();
+
+        let watcher = LibraryWatcher::start(vec![target], Duration::from_millis(100), move |c| {
+            let _ = tx.send(c);
+        })
+        .unwrap();
+        assert_eq!(watcher.watched_dirs(), std::slice::from_ref(&steamapps));
+
+        // Give the worker a moment to take its initial snapshot
+        thread::sleep(Duration::from_millis(200));
+        write_acf(&steamapps, "620", "Portal 2");
+
+        let change = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(change.event_name(), "game-added");
+        assert_eq!(change.game().name, "Portal 2");
+    }
+}
@@ -1,5 +1,6 @@
 import { useState, forwardRef } from 'react';
 import type { Game } from '../types';
+import { artworkSources, useArtworkSource } from '../utils/artwork';
 import { SteamIcon, EpicIcon, GOGIcon } from './icons/StoreIcons';
 
 export interface GameCardProps {
@@ -14,7 +15,6 @@ export interface GameCardProps {
 export const GameCard = forwardRef<HTMLButtonElement, GameCardProps>(
   function GameCard({ game, onClick, onFocus, onKeyDown, tabIndex = 0, featured = false }, ref) {
     const [isActive, setIsActive] = useState(false);
-    const [imageError, setImageError] = useState(false);
 
     const handleKeyDown = (e: React.KeyboardEvent) => {
       if (e.key === 'Enter' || e.key === ' ') {
@@ -36,7 +36,7 @@ export const GameCard = forwardRef<HTMLButtonElement, GameCardProps>(
     const handleMouseUp = () => setIsActive(false);
     const handleMouseLeave = () => setIsActive(false);
 
-    const coverUrl = game.cover_url || game.icon_url;
+    const cover = useArtworkSource(artworkSources(game, 'cover', game.cover_url, [game.icon_url]));
 
     const StoreIconComponent = () => {
       switch (game.store) {
@@ -86,12 +86,12 @@ export const GameCard = forwardRef<HTMLButtonElement, GameCardProps>(
           data-installed={game.installed}
         >
           <div className="absolute inset-0 overflow-hidden bg-tertiary">
-            {coverUrl && !imageError ? (
+            {cover.src ? (
               <img
-                src={coverUrl}
+                src={cover.src}
                 alt={game.name}
                 className="w-full h-full object-cover"
-                onError={() => setImageError(true)}
+                onError={cover.onError}
               />
             ) : (
               <div className="w-full h-full flex items-center justify-center bg-gradient-to-br from-surface to-tertiary">
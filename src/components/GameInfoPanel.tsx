@@ -1,5 +1,6 @@
 import { useRef, useEffect, useCallback, useState } from 'react';
-import type { Game } from '../types';
+import type { Game, GameArtworkInfo } from '../types';
+import { artworkSources, useArtworkSource } from '../utils/artwork';
 import { SteamIcon, EpicIcon, GOGIcon } from './icons/StoreIcons';
 
 export interface GameInfoPanelProps {
@@ -27,16 +28,22 @@ async function fetchGameDetails(gameId: string, store: string): Promise<GameDeta
 
   try {
     const { invoke } = await import('@tauri-apps/api/core');
+    return await invoke<GameDetails | null>('get_details', { gameKey: `${store}:${gameId}` });
+  } catch (err) {
+    console.error('Failed to fetch game details:', err);
+    return null;
+  }
+}
 
-    if (store === 'steam') {
-      return await invoke<GameDetails | null>('get_game_details', { gameId });
-    } else if (store === 'epic') {
-      return await invoke<GameDetails | null>('get_epic_game_details', { gameId });
-    }
+async function fetchArtworkInfo(gameKey: string): Promise<GameArtworkInfo | null> {
+  if (!isTauri()) return null;
 
-    return null;
+  try {
+    const { invoke } = await import('@tauri-apps/api/core');
+    const info = await invoke<Record<string, GameArtworkInfo>>('get_artwork_info', { gameKeys: [gameKey] });
+    return info[gameKey] ?? null;
   } catch (err) {
-    console.error('Failed to fetch game details:', err);
+    console.error('Failed to fetch artwork info:', err);
     return null;
   }
 }
@@ -45,6 +52,7 @@ export function GameInfoPanel({ game, onPlay, onInstall, onClose }: GameInfoPane
   const playButtonRef = useRef<HTMLButtonElement>(null);
   const [details, setDetails] = useState<GameDetails | null>(null);
   const [loadingDetails, setLoadingDetails] = useState(false);
+  const [artworkInfo, setArtworkInfo] = useState<GameArtworkInfo | null>(null);
 
   useEffect(() => {
     playButtonRef.current?.focus();
@@ -58,6 +66,36 @@ export function GameInfoPanel({ game, onPlay, onInstall, onClose }: GameInfoPane
       setLoadingDetails(false);
     };
     loadDetails();
+
+    // Cached details may be refreshed in the background after we show them
+    if (!isTauri()) return;
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    import('@tauri-apps/api/event').then(({ listen }) =>
+      listen<{ gameKey: string; details: GameDetails | null }>('game-details-updated', event => {
+        if (event.payload.gameKey === `${game.store}:${game.id}`) {
+          setDetails(event.payload.details);
+        }
+      })
+    ).then(fn => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [game.id, game.store]);
+
+  useEffect(() => {
+    let cancelled = false;
+    fetchArtworkInfo(`${game.store}:${game.id}`).then(info => {
+      if (!cancelled) setArtworkInfo(info);
+    });
+    return () => {
+      cancelled = true;
+    };
   }, [game.id, game.store]);
 
   const formatPlaytime = (minutes?: number) => {
@@ -108,7 +146,7 @@ export function GameInfoPanel({ game, onPlay, onInstall, onClose }: GameInfoPane
     return () => window.removeEventListener('keydown', handleKeyDown);
   }, [handleKeyDown]);
 
-  const coverUrl = game.cover_url || game.icon_url;
+  const cover = useArtworkSource(artworkSources(game, 'cover', game.cover_url, [game.icon_url]));
 
   const cleanDescription = (html?: string) => {
     if (!html) return null;
@@ -118,9 +156,12 @@ export function GameInfoPanel({ game, onPlay, onInstall, onClose }: GameInfoPane
   return (
     <div className="fixed inset-0 bottom-bottom-bar z-modal flex bg-primary animate-fade-in overflow-hidden">
       <div className="flex w-full h-full overflow-hidden">
-        <div className="flex-[0_0_40%] max-w-[500px] h-full bg-tertiary flex items-center justify-center overflow-hidden">
-          {coverUrl ? (
-            <img src={coverUrl} alt={game.name} className="w-full h-full object-cover" />
+        <div
+          className="flex-[0_0_40%] max-w-[500px] h-full bg-tertiary flex items-center justify-center overflow-hidden"
+          style={artworkInfo?.cover ? { backgroundColor: artworkInfo.cover.dominantColor } : undefined}
+        >
+          {cover.src ? (
+            <img src={cover.src} alt={game.name} className="w-full h-full object-cover" onError={cover.onError} />
           ) : (
             <div className="w-full h-full flex items-center justify-center bg-gradient-to-br from-surface to-tertiary">
               <span className="text-[6rem] font-bold text-text-muted uppercase">{game.name.charAt(0)}</span>
@@ -26,6 +26,7 @@ export {
   // General
   getPlayHistory,
   hasSyncedLibrary,
+  getStoreStatus,
 } from './useGames';
 export type { PlayHistory, EpicCredentials } from './useGames';
 export { useStoreConnections } from './useStoreConnections';
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
-import type { Game, StoreType } from '../types';
+import type { Game, StoreStatus, StoreType } from '../types';
+import { errorMessage } from '../utils/errors';
 
 interface UseGamesResult {
   games: Game[];
@@ -121,6 +122,10 @@ export async function getPlayHistory(): Promise<PlayHistory> {
   return invokeCommand<PlayHistory>('get_play_history');
 }
 
+export async function getStoreStatus(): Promise<StoreStatus[]> {
+  return invokeCommand<StoreStatus[]>('get_store_status');
+}
+
 export async function hasSyncedLibrary(): Promise<boolean> {
   if (!isTauri()) return false;
   return invokeCommand<boolean>('has_synced_library');
@@ -195,7 +200,7 @@ export function useGames(): UseGamesResult {
 
       setGames(enrichedGames);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -13,6 +13,7 @@ import {
   getEpicGamesCached,
 } from '../hooks';
 import type { Game } from '../types';
+import { errorMessage } from '../utils/errors';
 
 export interface EpicConnectScreenProps {
   onBack?: () => void;
@@ -116,7 +117,7 @@ export function EpicConnectScreen({ onBack, onNavigateDown }: EpicConnectScreenP
       setHasOAuthCredentials(true);
       setStep('results');
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setSyncing(false);
     }
@@ -133,7 +134,7 @@ export function EpicConnectScreen({ onBack, onNavigateDown }: EpicConnectScreenP
         await refreshInstalled();
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setSyncing(false);
     }
@@ -1,5 +1,8 @@
 import { useState, useEffect, useRef } from 'react';
+import { ErrorMessage } from '../components/ErrorMessage';
 import { PageHeader } from '../components/PageHeader';
+import type { DoctorReport, LogEntry, LogLevel, VaultStatus } from '../types';
+import { errorMessage } from '../utils/errors';
 
 export interface SettingsScreenProps {
   onNavigateDown?: () => void;
@@ -20,13 +23,37 @@ async function invokeCommand<T>(command: string, args?: Record<string, unknown>)
 interface AppSettings {
   launch_on_startup: boolean;
   launch_fullscreen: boolean;
+  log_level?: LogLevel;
 }
 
+const LOG_LEVELS: LogLevel[] = ['error', 'warn', 'info', 'debug', 'trace'];
+
+const LOG_LEVEL_LABELS: Record<LogLevel, string> = {
+  error: 'Errors',
+  warn: 'Warnings',
+  info: 'Info',
+  debug: 'Debug',
+  trace: 'Trace',
+};
+
+/** The level after `level`, wrapping around */
+const nextLogLevel = (level: LogLevel) => LOG_LEVELS[(LOG_LEVELS.indexOf(level) + 1) % LOG_LEVELS.length];
+
 export function SettingsScreen({ onNavigateDown }: SettingsScreenProps) {
   const [settings, setSettings] = useState<AppSettings>({
     launch_on_startup: false,
     launch_fullscreen: false,
   });
+  const [vault, setVault] = useState<VaultStatus | null>(null);
+  const [passphrase, setPassphrase] = useState('');
+  const [vaultError, setVaultError] = useState<string | null>(null);
+  const [report, setReport] = useState<DoctorReport | null>(null);
+  const [bundlePath, setBundlePath] = useState<string | null>(null);
+  const [diagnosticsError, setDiagnosticsError] = useState<string | null>(null);
+  const [logEntries, setLogEntries] = useState<LogEntry[] | null>(null);
+  const [logFilterLevel, setLogFilterLevel] = useState<LogLevel>('warn');
+  const [logFilterModule, setLogFilterModule] = useState('');
+  const [logsError, setLogsError] = useState<string | null>(null);
   const [loading, setLoading] = useState(true);
   const firstOptionRef = useRef<HTMLButtonElement>(null);
 
@@ -37,6 +64,7 @@ export function SettingsScreen({ onNavigateDown }: SettingsScreenProps) {
         if (saved) {
           setSettings(saved);
         }
+        setVault(await invokeCommand<VaultStatus>('get_vault_status') ?? null);
       } catch (err) {
         console.error('Failed to load settings:', err);
       } finally {
@@ -71,7 +99,93 @@ export function SettingsScreen({ onNavigateDown }: SettingsScreenProps) {
     }
   };
 
+  const runVaultCommand = async (command: string, args?: Record<string, unknown>) => {
+    setVaultError(null);
+    try {
+      await invokeCommand(command, args);
+      setPassphrase('');
+    } catch (err) {
+      setVaultError(errorMessage(err));
+    }
+    setVault(await invokeCommand<VaultStatus>('get_vault_status') ?? null);
+  };
+
+  const toggleLock = () => {
+    if (!vault) return;
+    if (vault.locked) {
+      runVaultCommand('unlock_vault', { passphrase: passphrase || null });
+    } else {
+      runVaultCommand('lock_vault');
+    }
+  };
+
+  const changePassphrase = () => {
+    if (!vault) return;
+    if (passphrase) {
+      runVaultCommand('set_vault_passphrase', { passphrase });
+    } else if (vault.passphraseProtected) {
+      runVaultCommand('set_vault_passphrase', { passphrase: null });
+    }
+  };
+
+  const runDiagnostics = async () => {
+    setDiagnosticsError(null);
+    try {
+      setReport(await invokeCommand<DoctorReport>('run_diagnostics') ?? null);
+    } catch (err) {
+      setDiagnosticsError(errorMessage(err));
+    }
+  };
+
+  const createSupportBundle = async () => {
+    setDiagnosticsError(null);
+    setBundlePath(null);
+    try {
+      setBundlePath(await invokeCommand<string>('create_support_bundle') ?? null);
+    } catch (err) {
+      setDiagnosticsError(errorMessage(err));
+    }
+  };
+
+  const changeLogLevel = async () => {
+    const newSettings = { ...settings, log_level: nextLogLevel(settings.log_level ?? 'info') };
+    setSettings(newSettings);
+    try {
+      await invokeCommand('save_app_settings', { settings: newSettings });
+    } catch (err) {
+      console.error('Failed to save settings:', err);
+    }
+  };
+
+  const loadLogs = async (level: LogLevel = logFilterLevel) => {
+    setLogsError(null);
+    try {
+      setLogEntries(await invokeCommand<LogEntry[]>('get_log_entries', {
+        level,
+        module: logFilterModule.trim() || null,
+        limit: 200,
+      }) ?? null);
+    } catch (err) {
+      setLogsError(errorMessage(err));
+    }
+  };
+
+  const changeLogFilterLevel = () => {
+    const level = nextLogLevel(logFilterLevel);
+    setLogFilterLevel(level);
+    if (logEntries) {
+      loadLogs(level);
+    }
+  };
+
+  // Index of an option among those currently shown (the credentials
+  // section has a varying number of them)
+  const optionIndex = (element: EventTarget) =>
+    Array.from(document.querySelectorAll('[data-settings-option]')).indexOf(element as Element);
+
   const handleKeyDown = (e: React.KeyboardEvent, currentIndex: number) => {
+    // Letters typed into a text field aren't navigation
+    if (e.target instanceof HTMLInputElement && e.key.length === 1) return;
     const options = document.querySelectorAll('[data-settings-option]');
 
     switch (e.key) {
@@ -148,6 +262,206 @@ export function SettingsScreen({ onNavigateDown }: SettingsScreenProps) {
             </div>
           </button>
         </div>
+
+        {vault && (
+          <div className="mb-xl">
+            <h3 className="text-sm font-semibold text-text-secondary m-0 mb-md uppercase tracking-wide">Credentials</h3>
+            <p className="text-[0.85rem] text-text-muted m-0 mb-md">
+              Store logins and API keys are kept in {vault.backend === 'keyring' ? 'your system keyring' : 'an encrypted file'}
+              {vault.passphraseProtected ? ', protected by a passphrase' : ''}.
+              {vault.locked ? ' The vault is locked: store syncs need it unlocked.' : ''}
+            </p>
+
+            {(vault.locked ? vault.passphraseProtected : true) && (
+              <input
+                data-settings-option
+                type="password"
+                className="w-full p-md mb-sm bg-surface border-none rounded text-base text-text-primary placeholder:text-text-muted focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+                placeholder={vault.locked ? 'Passphrase' : 'New passphrase'}
+                value={passphrase}
+                onChange={(e) => setPassphrase(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') {
+                    if (vault.locked) toggleLock();
+                    else changePassphrase();
+                  } else {
+                    handleKeyDown(e, 2);
+                  }
+                }}
+              />
+            )}
+
+            <button
+              data-settings-option
+              className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+              onClick={toggleLock}
+              onKeyDown={(e) => handleKeyDown(e, vault.locked && !vault.passphraseProtected ? 2 : 3)}
+            >
+              <div className="flex flex-col gap-xs">
+                <span className="text-base font-medium text-text-primary">
+                  {vault.locked ? 'Unlock credentials' : 'Lock credentials'}
+                </span>
+                <span className="text-[0.85rem] text-text-muted">
+                  {vault.locked
+                    ? 'Make stored logins available again'
+                    : 'Forget stored logins until they are unlocked again'}
+                </span>
+              </div>
+            </button>
+
+            {!vault.locked && (
+              <button
+                data-settings-option
+                className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+                onClick={changePassphrase}
+                onKeyDown={(e) => handleKeyDown(e, 4)}
+              >
+                <div className="flex flex-col gap-xs">
+                  <span className="text-base font-medium text-text-primary">
+                    {passphrase || !vault.passphraseProtected ? 'Set passphrase' : 'Remove passphrase'}
+                  </span>
+                  <span className="text-[0.85rem] text-text-muted">
+                    {passphrase || !vault.passphraseProtected
+                      ? 'Ask for the passphrase above each time the launcher starts'
+                      : 'Stop asking for a passphrase at startup'}
+                  </span>
+                </div>
+              </button>
+            )}
+
+            {vaultError && <ErrorMessage message={vaultError} className="mt-sm" />}
+          </div>
+        )}
+
+        <div className="mb-xl">
+          <h3 className="text-sm font-semibold text-text-secondary m-0 mb-md uppercase tracking-wide">Troubleshooting</h3>
+
+          <button
+            data-settings-option
+            className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            onClick={runDiagnostics}
+            onKeyDown={(e) => handleKeyDown(e, optionIndex(e.currentTarget))}
+          >
+            <div className="flex flex-col gap-xs">
+              <span className="text-base font-medium text-text-primary">Run diagnostics</span>
+              <span className="text-[0.85rem] text-text-muted">
+                Check store installs, libraries and the launcher's own files
+              </span>
+            </div>
+          </button>
+
+          <button
+            data-settings-option
+            className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            onClick={createSupportBundle}
+            onKeyDown={(e) => handleKeyDown(e, optionIndex(e.currentTarget))}
+          >
+            <div className="flex flex-col gap-xs">
+              <span className="text-base font-medium text-text-primary">Create support bundle</span>
+              <span className="text-[0.85rem] text-text-muted">
+                {bundlePath
+                  ? `Saved to ${bundlePath}`
+                  : 'Save a zip to attach to bug reports (API keys and tokens are removed)'}
+              </span>
+            </div>
+          </button>
+
+          {report && (
+            <ul className="list-none m-0 p-0 text-[0.85rem]">
+              {report.checks
+                .filter((check) => check.status !== 'skipped')
+                .map((check, i) => (
+                  <li key={i} className="py-xs text-text-muted">
+                    <span className={check.status === 'error' ? 'text-[#ff6b6b]' : check.status === 'warning' ? 'text-[#f0c36b]' : 'text-text-secondary'}>
+                      {check.area} · {check.name}
+                    </span>
+                    {check.detail && `: ${check.detail}`}
+                  </li>
+                ))}
+            </ul>
+          )}
+
+          {diagnosticsError && <ErrorMessage message={diagnosticsError} className="mt-sm" />}
+        </div>
+
+        <div className="mb-xl">
+          <h3 className="text-sm font-semibold text-text-secondary m-0 mb-md uppercase tracking-wide">Logs</h3>
+
+          <button
+            data-settings-option
+            className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            onClick={changeLogLevel}
+            onKeyDown={(e) => handleKeyDown(e, optionIndex(e.currentTarget))}
+          >
+            <div className="flex flex-col gap-xs">
+              <span className="text-base font-medium text-text-primary">Log level</span>
+              <span className="text-[0.85rem] text-text-muted">
+                How much the launcher writes to its log files (Debug and Trace make them grow quickly)
+              </span>
+            </div>
+            <span className="text-base text-text-secondary shrink-0">{LOG_LEVEL_LABELS[settings.log_level ?? 'info']}</span>
+          </button>
+
+          <input
+            data-settings-option
+            type="text"
+            className="w-full p-md mb-sm bg-surface border-none rounded text-base text-text-primary placeholder:text-text-muted focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            placeholder="Only modules containing (e.g. steam)"
+            value={logFilterModule}
+            onChange={(e) => setLogFilterModule(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter') {
+                loadLogs();
+              } else {
+                handleKeyDown(e, optionIndex(e.currentTarget));
+              }
+            }}
+          />
+
+          <button
+            data-settings-option
+            className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            onClick={changeLogFilterLevel}
+            onKeyDown={(e) => handleKeyDown(e, optionIndex(e.currentTarget))}
+          >
+            <div className="flex flex-col gap-xs">
+              <span className="text-base font-medium text-text-primary">Show at least</span>
+              <span className="text-[0.85rem] text-text-muted">Least severe entries to show</span>
+            </div>
+            <span className="text-base text-text-secondary shrink-0">{LOG_LEVEL_LABELS[logFilterLevel]}</span>
+          </button>
+
+          <button
+            data-settings-option
+            className="flex items-center justify-between w-full p-md mb-sm bg-surface border-none rounded cursor-pointer transition-colors duration-fast text-left hover:bg-surface-hover focus:outline-none focus-visible:ring-2 focus-visible:ring-accent focus-visible:ring-offset-2 focus-visible:ring-offset-primary"
+            onClick={() => loadLogs()}
+            onKeyDown={(e) => handleKeyDown(e, optionIndex(e.currentTarget))}
+          >
+            <div className="flex flex-col gap-xs">
+              <span className="text-base font-medium text-text-primary">{logEntries ? 'Refresh logs' : 'Show logs'}</span>
+              <span className="text-[0.85rem] text-text-muted">The most recent matching log entries</span>
+            </div>
+          </button>
+
+          {logEntries && (
+            <ul className="list-none m-0 p-0 text-[0.8rem] font-mono">
+              {logEntries.length === 0 && <li className="py-xs text-text-muted">No matching entries</li>}
+              {logEntries
+                .slice()
+                .reverse()
+                .map((entry, i) => (
+                  <li key={i} className="py-xs text-text-muted whitespace-pre-wrap break-words">
+                    <span className={entry.level === 'error' ? 'text-[#ff6b6b]' : entry.level === 'warn' ? 'text-[#f0c36b]' : 'text-text-secondary'}>
+                      {entry.timestamp} {entry.level.toUpperCase()} {entry.module}
+                    </span>
+                    {`: ${entry.message}`}
+                  </li>
+                ))}
+            </ul>
+          )}
+
+          {logsError && <ErrorMessage message={logsError} className="mt-sm" />}
+        </div>
       </div>
     </div>
   );
@@ -18,6 +18,7 @@ import {
   useGamesByStore,
 } from '../hooks';
 import type { Game } from '../types';
+import { errorMessage } from '../utils/errors';
 
 export interface SteamConnectScreenProps {
   onBack?: () => void;
@@ -158,7 +159,7 @@ export function SteamConnectScreen({ onBack, onNavigateDown }: SteamConnectScree
       setHasApiCredentials(true);
       setStep('results');
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setSyncing(false);
     }
@@ -175,7 +176,7 @@ export function SteamConnectScreen({ onBack, onNavigateDown }: SteamConnectScree
         await refreshInstalled();
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setSyncing(false);
     }
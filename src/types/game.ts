@@ -1,4 +1,4 @@
-export type StoreType = 'steam' | 'epic' | 'gog';
+export type StoreType = 'steam' | 'epic' | 'gog' | 'local';
 
 export interface Game {
   id: string;
@@ -12,11 +12,117 @@ export interface Game {
   cover_url?: string;
   hero_url?: string;
   icon_url?: string;
+  logo_url?: string;
   size_bytes?: number;
   version?: string;
   installed_at?: number;
 }
 
+/** Colors, blurhash and thumbnail info extracted from cached artwork */
+export interface ArtworkInfo {
+  width: number;
+  height: number;
+  dominantColor: string;
+  accentColor: string;
+  palette: string[];
+  blurhash: string;
+  hasThumbnail: boolean;
+}
+
+export interface GameArtworkInfo {
+  cover?: ArtworkInfo;
+  hero?: ArtworkInfo;
+}
+
+/** Alternative artwork offered by a provider (e.g. SteamGridDB) */
+export interface ArtworkCandidate {
+  url: string;
+  thumbnailUrl: string | null;
+  provider: string;
+  width: number | null;
+  height: number | null;
+  style: string | null;
+}
+
+/** Where credentials are kept, and whether they can be read right now */
+export interface VaultStatus {
+  backend: 'keyring' | 'encrypted_file';
+  locked: boolean;
+  passphraseProtected: boolean;
+}
+
+/** What a failed command rejects with */
+export interface CommandError {
+  code:
+    | 'store_not_found'
+    | 'game_not_found'
+    | 'parse_error'
+    | 'io_error'
+    | 'http_error'
+    | 'json_error'
+    | 'database_error'
+    | 'launch_error'
+    | 'auth_required'
+    | 'platform_not_supported'
+    | 'network_error'
+    | 'config_error'
+    | 'timeout'
+    | 'rate_limited'
+    | 'private_profile'
+    | 'not_connected'
+    | 'invalid_input'
+    | 'internal';
+  store: StoreType | null;
+  /** Trying again later may succeed (network trouble, rate limits) */
+  retryable: boolean;
+  message: string;
+  details: string | null;
+}
+
+/** Health of one store, for troubleshooting missing games */
+export interface StoreStatus {
+  storeId: string;
+  displayName: string;
+  available: boolean;
+  paths: { name: string; path: string | null; exists: boolean }[];
+  clientPath: string | null;
+  clientInstalled: boolean;
+  clientRunning: boolean;
+  credentials: 'not_needed' | 'missing' | 'present' | 'locked';
+  lastScanAt: number | null;
+  lastScanDurationMs: number | null;
+  gameCount: number | null;
+  lastError: string | null;
+  lastSyncAt: number | null;
+}
+
+/** One check made by the diagnostics */
+export interface DiagnosticCheck {
+  area: string;
+  name: string;
+  status: 'ok' | 'warning' | 'error' | 'skipped';
+  detail: string;
+}
+
+/** What `run_diagnostics` found */
+export interface DoctorReport {
+  generatedAt: number;
+  version: string;
+  os: string;
+  checks: DiagnosticCheck[];
+  stores: StoreStatus[];
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+/** One record from the log files, as returned by `get_log_entries` */
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  module: string;
+  message: string;
+}
+
 export interface StoreConnection {
   storeId: StoreType;
   connected: boolean;
@@ -1 +1 @@
-export type { Game, StoreType, StoreConnection } from './game';
+export type { ArtworkCandidate, ArtworkInfo, CommandError, DiagnosticCheck, DoctorReport, Game, GameArtworkInfo, LogEntry, LogLevel, StoreType, StoreConnection, StoreStatus, VaultStatus } from './game';
@@ -0,0 +1,46 @@
+import { describe, it, expect, afterEach } from 'vitest';
+import type { Game } from '../types';
+import { artworkSources } from './artwork';
+
+const game: Game = {
+  id: '440',
+  name: 'Team Fortress 2',
+  store: 'steam',
+  installed: true,
+  cover_url: 'https://cdn.example.com/440/cover.jpg',
+  icon_url: 'https://cdn.example.com/440/icon.jpg',
+};
+
+describe('artworkSources', () => {
+  afterEach(() => {
+    delete (window as unknown as Record<string, unknown>).__TAURI_INTERNALS__;
+  });
+
+  it('uses the remote URLs directly outside the app', () => {
+    expect(artworkSources(game, 'cover', game.cover_url, [game.icon_url])).toEqual([
+      'https://cdn.example.com/440/cover.jpg',
+      'https://cdn.example.com/440/icon.jpg',
+    ]);
+  });
+
+  it('falls back from the artwork protocol to the remote URL in the app', () => {
+    (window as unknown as Record<string, unknown>).__TAURI_INTERNALS__ = {};
+
+    const sources = artworkSources(game, 'cover', game.cover_url, [game.icon_url]);
+
+    expect(sources).toHaveLength(3);
+    expect(sources[0]).toMatch(/tenfoot-art.*\/steam\/440\/cover$/);
+    expect(sources.slice(1)).toEqual([
+      'https://cdn.example.com/440/cover.jpg',
+      'https://cdn.example.com/440/icon.jpg',
+    ]);
+  });
+
+  it('skips missing and duplicate URLs', () => {
+    const noArt: Game = { ...game, cover_url: undefined, icon_url: undefined };
+    expect(artworkSources(noArt, 'cover', noArt.cover_url, [noArt.icon_url])).toEqual([]);
+    expect(artworkSources(game, 'cover', game.cover_url, [game.cover_url])).toEqual([
+      'https://cdn.example.com/440/cover.jpg',
+    ]);
+  });
+});
@@ -0,0 +1,60 @@
+import { useState } from 'react';
+import type { Game } from '../types';
+
+export type ArtworkKind = 'cover' | 'hero' | 'logo' | 'icon';
+
+const isTauri = () => {
+  return typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
+};
+
+/**
+ * Image source for a game's artwork. Inside the app this goes through the
+ * `tenfoot-art://` protocol, which serves the locally cached copy or resolves
+ * a working remote image (even when the store gave no URL, as for GOG). In a
+ * plain browser the remote URL is used directly.
+ */
+export function artworkSrc(game: Game, kind: ArtworkKind, remoteUrl?: string): string | undefined {
+  if (!isTauri()) return remoteUrl;
+  // Custom artwork already points at the protocol (with a version to bust caches)
+  if (remoteUrl?.includes('tenfoot-art')) return remoteUrl;
+
+  const path = `${game.store}/${encodeURIComponent(game.id)}/${kind}`;
+  return navigator.userAgent.includes('Windows')
+    ? `http://tenfoot-art.localhost/${path}`
+    : `tenfoot-art://localhost/${path}`;
+}
+
+/**
+ * Image sources to try in order: the app's artwork (cached or resolved), then
+ * the store's own URL, then any extra remote URLs. If the protocol can't serve
+ * an image, the remote copy may still load.
+ */
+export function artworkSources(
+  game: Game,
+  kind: ArtworkKind,
+  remoteUrl?: string,
+  fallbackUrls: (string | undefined)[] = [],
+): string[] {
+  const remotes = [remoteUrl, ...fallbackUrls].filter(
+    (url): url is string => !!url && !url.includes('tenfoot-art'),
+  );
+  const sources = [artworkSrc(game, kind, remoteUrl), ...remotes].filter(
+    (url): url is string => !!url,
+  );
+  return sources.filter((url, index) => sources.indexOf(url) === index);
+}
+
+/**
+ * Current image source from `sources`, and an `onError` handler that moves on
+ * to the next one. `src` is undefined once every source has failed.
+ */
+export function useArtworkSource(sources: string[]): { src?: string; onError: () => void } {
+  const key = sources.join('\n');
+  const [failed, setFailed] = useState({ key, count: 0 });
+  // Start over when the sources change (a different game)
+  const count = failed.key === key ? failed.count : 0;
+  return {
+    src: sources[count],
+    onError: () => setFailed({ key, count: count + 1 }),
+  };
+}
@@ -0,0 +1,13 @@
+import type { CommandError } from '../types';
+
+function isCommandError(err: unknown): err is CommandError {
+  return typeof err === 'object' && err !== null && 'code' in err && 'message' in err;
+}
+
+/** Text to show for a rejected command or any other thrown value */
+export function errorMessage(err: unknown): string {
+  if (isCommandError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  return String(err);
+}
+